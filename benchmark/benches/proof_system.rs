@@ -44,8 +44,11 @@ fn main() {
     k_range.for_each(|k| systems.iter().for_each(|system| system.bench(k, circuit)));
 }
 
-fn bench_plonkish_backend<B, C>(system: System, k: usize)
-where
+fn bench_plonkish_backend<B, C>(
+    system: System,
+    k: usize,
+    map_pp: impl FnOnce(B::ProverParam) -> B::ProverParam,
+) where
     B: PlonkishBackend<Fr> + WitnessEncoding,
     C: CircuitExt<Fr>,
     Keccak256Transcript<Cursor<Vec<u8>>>: TranscriptRead<CommitmentChunk<Fr, B::Pcs>, Fr>
@@ -63,6 +66,7 @@ where
 
     let timer = start_timer(|| format!("{system}_preprocess-{k}"));
     let (pp, vp) = B::preprocess(&param, &circuit_info).unwrap();
+    let pp = map_pp(pp);
     end_timer(timer);
 
     let proof = sample(system, k, || {
@@ -83,13 +87,21 @@ where
 fn bench_hyperplonk<C: CircuitExt<Fr>>(k: usize) {
     type GeminiKzg = multilinear::Gemini<univariate::UnivariateKzg<Bn256>>;
     type HyperPlonk = backend::hyperplonk::HyperPlonk<GeminiKzg>;
-    bench_plonkish_backend::<HyperPlonk, C>(System::HyperPlonk, k)
+    bench_plonkish_backend::<HyperPlonk, C>(System::HyperPlonk, k, |pp| pp)
+}
+
+fn bench_hyperplonk_dense<C: CircuitExt<Fr>>(k: usize) {
+    type GeminiKzg = multilinear::Gemini<univariate::UnivariateKzg<Bn256>>;
+    type HyperPlonk = backend::hyperplonk::HyperPlonk<GeminiKzg>;
+    bench_plonkish_backend::<HyperPlonk, C>(System::HyperPlonkDense, k, |pp| {
+        pp.without_sparse_polys()
+    })
 }
 
 fn bench_unihyperplonk<C: CircuitExt<Fr>>(k: usize) {
     type UnivariateKzg = univariate::UnivariateKzg<Bn256>;
     type UniHyperPlonk = backend::unihyperplonk::UniHyperPlonk<UnivariateKzg, true>;
-    bench_plonkish_backend::<UniHyperPlonk, C>(System::UniHyperPlonk, k)
+    bench_plonkish_backend::<UniHyperPlonk, C>(System::UniHyperPlonk, k, |pp| pp)
 }
 
 fn bench_halo2<C: CircuitExt<Fr>>(k: usize) {
@@ -168,6 +180,7 @@ fn bench_espresso_hyperplonk(circuit: MockCircuit<ark_bn254::Fr>) {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum System {
     HyperPlonk,
+    HyperPlonkDense,
     UniHyperPlonk,
     Halo2,
     EspressoHyperPlonk,
@@ -196,7 +209,10 @@ impl System {
 
     fn support(&self, circuit: Circuit) -> bool {
         match self {
-            System::HyperPlonk | System::UniHyperPlonk | System::Halo2 => match circuit {
+            System::HyperPlonk
+            | System::HyperPlonkDense
+            | System::UniHyperPlonk
+            | System::Halo2 => match circuit {
                 Circuit::VanillaPlonk
                 | Circuit::Aggregation
                 | Circuit::Sha256
@@ -224,6 +240,12 @@ impl System {
                 Circuit::Sha256 => bench_hyperplonk::<Sha256Circuit>(k),
                 Circuit::Keccak256 => bench_hyperplonk::<Keccak256Circuit>(k),
             },
+            System::HyperPlonkDense => match circuit {
+                Circuit::VanillaPlonk => bench_hyperplonk_dense::<VanillaPlonk<Fr>>(k),
+                Circuit::Aggregation => bench_hyperplonk_dense::<AggregationCircuit<Bn256>>(k),
+                Circuit::Sha256 => bench_hyperplonk_dense::<Sha256Circuit>(k),
+                Circuit::Keccak256 => bench_hyperplonk_dense::<Keccak256Circuit>(k),
+            },
             System::UniHyperPlonk => match circuit {
                 Circuit::VanillaPlonk => bench_unihyperplonk::<VanillaPlonk<Fr>>(k),
                 Circuit::Aggregation => bench_unihyperplonk::<AggregationCircuit<Bn256>>(k),
@@ -248,6 +270,7 @@ impl Display for System {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             System::HyperPlonk => write!(f, "hyperplonk"),
+            System::HyperPlonkDense => write!(f, "hyperplonk_dense"),
             System::UniHyperPlonk => write!(f, "unihyperplonk"),
             System::Halo2 => write!(f, "halo2"),
             System::EspressoHyperPlonk => write!(f, "espresso_hyperplonk"),
@@ -293,11 +316,12 @@ fn parse_args() -> (Vec<System>, Circuit, Range<usize>) {
                 "--system" => match value.as_str() {
                     "all" => systems = System::all(),
                     "hyperplonk" => systems.push(System::HyperPlonk),
+                    "hyperplonk_dense" => systems.push(System::HyperPlonkDense),
                     "unihyperplonk" => systems.push(System::UniHyperPlonk),
                     "halo2" => systems.push(System::Halo2),
                     "espresso_hyperplonk" => systems.push(System::EspressoHyperPlonk),
                     _ => panic!(
-                        "system should be one of {{all,hyperplonk,unihyperplonk,halo2,espresso_hyperplonk}}"
+                        "system should be one of {{all,hyperplonk,hyperplonk_dense,unihyperplonk,halo2,espresso_hyperplonk}}"
                     ),
                 },
                 "--circuit" => match value.as_str() {
@@ -0,0 +1,87 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use halo2_curves::bn256::{Bn256, Fr};
+use plonkish_backend::{
+    backend::{
+        hyperplonk::{
+            util::rand_vanilla_plonk_w_lookup_circuit,
+            HyperPlonk,
+            LookupStrategy::{self, LogUp, LogUpGkr},
+        },
+        PlonkishBackend, PlonkishCircuit,
+    },
+    pcs::multilinear::MultilinearKzg,
+    util::{
+        expression::rotate::BinaryField,
+        test::seeded_std_rng,
+        transcript::{InMemoryTranscript, Keccak256Transcript},
+    },
+};
+use pprof::criterion::{Output, PProfProfiler};
+use std::{io::Cursor, ops::Range};
+
+type Pcs = MultilinearKzg<Bn256>;
+
+const NUM_VARS_RANGE: Range<usize> = 16..21;
+
+fn bench_lookup<const LOOKUP_STRATEGY: usize>(c: &mut Criterion) {
+    type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+    let strategy = LookupStrategy::from(LOOKUP_STRATEGY);
+    let mut group = c.benchmark_group(format!("hyperplonk_lookup_{strategy:?}"));
+    group.sample_size(10);
+    for num_vars in NUM_VARS_RANGE {
+        let (circuit_info, circuit) = rand_vanilla_plonk_w_lookup_circuit::<Fr, BinaryField>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param =
+            HyperPlonk::<Pcs, LOOKUP_STRATEGY>::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) =
+            HyperPlonk::<Pcs, LOOKUP_STRATEGY>::preprocess(&param, &circuit_info).unwrap();
+        let prove = || {
+            let mut transcript = T::new(());
+            HyperPlonk::<Pcs, LOOKUP_STRATEGY>::prove(
+                &pp,
+                &circuit,
+                &mut transcript,
+                seeded_std_rng(),
+            )
+            .unwrap();
+            transcript.into_proof()
+        };
+
+        let id = BenchmarkId::new("prove", num_vars);
+        group.bench_with_input(id, &num_vars, |b, _| b.iter(prove));
+
+        let proof = prove();
+        let id = BenchmarkId::new("verify", num_vars);
+        group.bench_with_input(id, &num_vars, |b, _| {
+            b.iter(|| {
+                let mut transcript = T::from_proof((), proof.as_slice());
+                HyperPlonk::<Pcs, LOOKUP_STRATEGY>::verify(
+                    &vp,
+                    circuit.instances(),
+                    &mut transcript,
+                    seeded_std_rng(),
+                )
+                .unwrap();
+            });
+        });
+    }
+}
+
+fn bench_log_up(c: &mut Criterion) {
+    bench_lookup::<{ LogUp as usize }>(c);
+}
+
+fn bench_log_up_gkr(c: &mut Criterion) {
+    bench_lookup::<{ LogUpGkr as usize }>(c);
+}
+
+criterion_group! {
+    name = bench;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = bench_log_up, bench_log_up_gkr
+}
+criterion_main!(bench);
@@ -6,11 +6,11 @@ use halo2_curves::bn256::{Bn256, Fr, G1Affine};
 use plonkish_backend::{
     pcs::{
         multilinear::{
-            Gemini, MultilinearBrakedown, MultilinearHyrax, MultilinearIpa, MultilinearKzg,
-            Zeromorph,
+            Gemini, MultilinearBasefold, MultilinearBrakedown, MultilinearHyrax, MultilinearIpa,
+            MultilinearKzg, MultilinearLigero, Zeromorph,
         },
-        univariate::UnivariateKzg,
-        Point, PolynomialCommitmentScheme,
+        univariate::{UnivariateFri, UnivariateHyrax, UnivariateKzg},
+        Evaluation, Point, PolynomialCommitmentScheme,
     },
     poly::Polynomial,
     util::{
@@ -18,13 +18,17 @@ use plonkish_backend::{
         code::BrakedownSpec6,
         hash::Keccak256,
         test::std_rng,
-        transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptWrite},
+        transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptRead, TranscriptWrite},
+        Itertools,
     },
+    Error,
 };
-use std::{any::type_name, io::Cursor, ops::Range};
+use std::{any::type_name, io::Cursor, iter, ops::Range};
 
 const NUM_VARS_RANGE: Range<usize> = 16..21;
 
+const BATCH_SIZE: usize = 8;
+
 fn pcs_name<Pcs>() -> &'static str {
     type_name::<Pcs>()
         .split("::")
@@ -99,16 +103,75 @@ where
     }
 }
 
+fn batch_verify<F, Pcs>(
+    group: &mut BenchmarkGroup<impl Measurement>,
+    name: &str,
+    batch_verify: impl Fn(
+        &Pcs::VerifierParam,
+        Vec<Pcs::Commitment>,
+        &[Point<F, Pcs::Polynomial>],
+        &[Evaluation<F>],
+        &mut Keccak256Transcript<Cursor<Vec<u8>>>,
+    ) -> Result<(), Error>,
+) where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+    Keccak256Transcript<Cursor<Vec<u8>>>:
+        TranscriptRead<Pcs::CommitmentChunk, F> + TranscriptWrite<Pcs::CommitmentChunk, F>,
+{
+    for k in NUM_VARS_RANGE {
+        let n = 1 << k;
+        let mut rng = std_rng();
+        let param = Pcs::setup(n, BATCH_SIZE, &mut rng).unwrap();
+        let (pp, vp) = Pcs::trim(&param, n, BATCH_SIZE).unwrap();
+        let polys = iter::repeat_with(|| Pcs::Polynomial::rand(n, &mut rng))
+            .take(BATCH_SIZE)
+            .collect_vec();
+        let points = iter::repeat_with(|| Pcs::Polynomial::rand_point(k, &mut rng))
+            .take(2)
+            .collect_vec();
+        let evals = polys
+            .iter()
+            .enumerate()
+            .map(|(idx, poly)| Evaluation::new(idx, idx % 2, poly.evaluate(&points[idx % 2])))
+            .collect_vec();
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            let comms = Pcs::batch_commit_and_write(&pp, &polys, &mut transcript).unwrap();
+            Pcs::batch_open(&pp, &polys, &comms, &points, &evals, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        group.bench_with_input(BenchmarkId::new(name, k), &k, |b, _| {
+            b.iter(|| {
+                let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+                let comms = Pcs::read_commitments(&vp, BATCH_SIZE, &mut transcript).unwrap();
+                batch_verify(
+                    black_box(&vp),
+                    black_box(comms),
+                    black_box(&points),
+                    black_box(&evals),
+                    black_box(&mut transcript),
+                )
+                .unwrap()
+            })
+        });
+    }
+}
+
 fn bench_commit(c: &mut Criterion) {
     let mut group = c.benchmark_group("commit");
     group.sample_size(10);
 
     commit::<_, MultilinearBrakedown<Fr, Keccak256, BrakedownSpec6>>(&mut group);
+    commit::<_, MultilinearLigero<Fr, Keccak256>>(&mut group);
+    commit::<_, MultilinearBasefold<Fr, Keccak256>>(&mut group);
     commit::<_, MultilinearKzg<Bn256>>(&mut group);
     commit::<_, MultilinearIpa<G1Affine>>(&mut group);
     commit::<_, MultilinearHyrax<G1Affine>>(&mut group);
     commit::<_, Gemini<UnivariateKzg<Bn256>>>(&mut group);
     commit::<_, Zeromorph<UnivariateKzg<Bn256>>>(&mut group);
+    commit::<_, Gemini<UnivariateFri<Fr, Keccak256>>>(&mut group);
+    commit::<_, Zeromorph<UnivariateFri<Fr, Keccak256>>>(&mut group);
 }
 
 fn bench_open(c: &mut Criterion) {
@@ -116,12 +179,54 @@ fn bench_open(c: &mut Criterion) {
     group.sample_size(10);
 
     open::<_, MultilinearBrakedown<Fr, Keccak256, BrakedownSpec6>>(&mut group);
+    open::<_, MultilinearLigero<Fr, Keccak256>>(&mut group);
+    open::<_, MultilinearBasefold<Fr, Keccak256>>(&mut group);
     open::<_, MultilinearKzg<Bn256>>(&mut group);
     open::<_, MultilinearIpa<G1Affine>>(&mut group);
     open::<_, MultilinearHyrax<G1Affine>>(&mut group);
     open::<_, Gemini<UnivariateKzg<Bn256>>>(&mut group);
     open::<_, Zeromorph<UnivariateKzg<Bn256>>>(&mut group);
+    open::<_, Gemini<UnivariateFri<Fr, Keccak256>>>(&mut group);
+    open::<_, Zeromorph<UnivariateFri<Fr, Keccak256>>>(&mut group);
+}
+
+/// Benchmarks Hyrax verifiers merging all MSMs into the IPA one against the
+/// ones combining commitments per chunk by separate MSMs.
+fn bench_batch_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_verify");
+    group.sample_size(10);
+
+    type MlHyrax = MultilinearHyrax<G1Affine>;
+    type UniHyrax = UnivariateHyrax<G1Affine>;
+    batch_verify::<_, MlHyrax>(
+        &mut group,
+        "MultilinearHyrax",
+        |vp, comms, points, evals, transcript| {
+            MlHyrax::batch_verify(vp, &comms, points, evals, transcript)
+        },
+    );
+    batch_verify::<_, MlHyrax>(
+        &mut group,
+        "MultilinearHyraxPerChunk",
+        |vp, comms, points, evals, transcript| {
+            MlHyrax::batch_verify_per_chunk(vp, &comms, points, evals, transcript)
+        },
+    );
+    batch_verify::<_, UniHyrax>(
+        &mut group,
+        "UnivariateHyrax",
+        |vp, comms, points, evals, transcript| {
+            UniHyrax::batch_verify(vp, &comms, points, evals, transcript)
+        },
+    );
+    batch_verify::<_, UniHyrax>(
+        &mut group,
+        "UnivariateHyraxPerChunk",
+        |vp, comms, points, evals, transcript| {
+            UniHyrax::batch_verify_per_chunk(vp, &comms, points, evals, transcript)
+        },
+    );
 }
 
-criterion_group!(benches, bench_commit, bench_open);
+criterion_group!(benches, bench_commit, bench_open, bench_batch_verify);
 criterion_main!(benches);
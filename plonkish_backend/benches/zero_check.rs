@@ -22,6 +22,11 @@ fn run(num_vars: usize, virtual_poly: VirtualPolynomial<Fr>) {
     ZeroCheck::prove(&(), num_vars, virtual_poly, Fr::ZERO, &mut transcript).unwrap();
 }
 
+fn run_gruen(num_vars: usize, virtual_poly: VirtualPolynomial<Fr>) {
+    let mut transcript = Keccak256Transcript::<Vec<u8>>::default();
+    ZeroCheck::prove_zero_check(num_vars, virtual_poly, &mut transcript).unwrap();
+}
+
 fn zero_check(c: &mut Criterion) {
     let setup = |num_vars: usize| {
         let expression = vanilla_plonk_expression(num_vars);
@@ -34,15 +39,20 @@ fn zero_check(c: &mut Criterion) {
         (expression, polys, challenges, ys)
     };
 
-    let mut group = c.benchmark_group("zero_check");
-    group.sample_size(10);
-    for num_vars in 20..24 {
-        let (expression, polys, challenges, ys) = setup(num_vars);
-        let virtual_poly = VirtualPolynomial::new(&expression, &polys, &challenges, &ys);
-        let id = BenchmarkId::from_parameter(num_vars);
-        group.bench_with_input(id, &num_vars, |b, &num_vars| {
-            b.iter(|| run(num_vars, virtual_poly.clone()));
-        });
+    for (name, run) in [
+        ("zero_check", run as fn(usize, VirtualPolynomial<Fr>)),
+        ("zero_check_gruen", run_gruen),
+    ] {
+        let mut group = c.benchmark_group(name);
+        group.sample_size(10);
+        for num_vars in 20..24 {
+            let (expression, polys, challenges, ys) = setup(num_vars);
+            let virtual_poly = VirtualPolynomial::new(&expression, &polys, &challenges, &ys);
+            let id = BenchmarkId::from_parameter(num_vars);
+            group.bench_with_input(id, &num_vars, |b, &num_vars| {
+                b.iter(|| run(num_vars, virtual_poly.clone()));
+            });
+        }
     }
 }
 
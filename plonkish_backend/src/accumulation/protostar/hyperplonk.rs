@@ -6,6 +6,7 @@ use crate::{
                 prover::{
                     evaluate_compressed_cross_term_sums, evaluate_cross_term_polys,
                     evaluate_zeta_cross_term_poly, lookup_h_polys, powers_of_zeta_poly,
+                    shuffle_h_polys,
                 },
             },
             Protostar, ProtostarAccumulator, ProtostarAccumulatorInstance, ProtostarProverParam,
@@ -22,6 +23,7 @@ use crate::{
             },
             verifier::verify_sum_check,
             HyperPlonk,
+            SumCheckStrategy::Evaluations,
         },
         PlonkishCircuit, PlonkishCircuitInfo, WitnessEncoding,
     },
@@ -41,8 +43,11 @@ use rand::RngCore;
 use std::{borrow::BorrowMut, hash::Hash, iter};
 
 mod preprocessor;
+mod proof;
 mod prover;
 
+pub use proof::ProtostarDeciderProof;
+
 impl<F, Pcs, const STRATEGY: usize> AccumulationScheme<F> for Protostar<HyperPlonk<Pcs>, STRATEGY>
 where
     F: PrimeField + Hash + Serialize + DeserializeOwned,
@@ -59,7 +64,8 @@ where
         circuit_info: &PlonkishCircuitInfo<F>,
         rng: impl RngCore,
     ) -> Result<Pcs::Param, Error> {
-        assert!(circuit_info.is_well_formed());
+        circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
+        circuit_info.ensure_only_vector_lookups()?;
 
         let num_vars = circuit_info.k;
         let poly_size = 1 << num_vars;
@@ -71,7 +77,8 @@ where
         param: &Pcs::Param,
         circuit_info: &PlonkishCircuitInfo<F>,
     ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
-        assert!(circuit_info.is_well_formed());
+        circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
+        circuit_info.ensure_only_vector_lookups()?;
 
         preprocess(param, circuit_info, STRATEGY.into())
     }
@@ -106,6 +113,8 @@ where
             ..
         } = pp;
 
+        transcript.common_field_element(&pp.vp_digest)?;
+
         let instances = circuit.instances();
         for (num_instances, instances) in pp.num_instances.iter().zip_eq(instances) {
             assert_eq!(instances.len(), *num_instances);
@@ -146,12 +155,17 @@ where
             .take(*num_theta_primes)
             .collect_vec();
 
-        let timer = start_timer(|| format!("lookup_compressed_polys-{}", pp.lookups.len()));
-        let lookup_compressed_polys = {
+        let timer = start_timer(|| {
+            let num_lookups = pp.lookups.len() + pp.shuffles.len();
+            format!("lookup_compressed_polys-{num_lookups}")
+        });
+        let [lookup_compressed_polys, shuffle_compressed_polys] = {
             let instance_polys = instance_polys::<_, BinaryField>(pp.num_vars, instances);
             let polys = chain![&instance_polys, &pp.preprocess_polys, &witness_polys].collect_vec();
             let thetas = chain![[F::ONE], theta_primes.iter().cloned()].collect_vec();
-            lookup_compressed_polys::<_, BinaryField>(&pp.lookups, &polys, &challenges, &thetas)
+            [&pp.lookups, &pp.shuffles].map(|lookups| {
+                lookup_compressed_polys::<_, BinaryField>(lookups, &polys, &challenges, &thetas)
+            })
         };
         end_timer(timer);
 
@@ -169,8 +183,12 @@ where
         let lookup_h_polys = lookup_h_polys(&lookup_compressed_polys, &lookup_m_polys, &beta_prime);
         end_timer(timer);
 
+        let timer = start_timer(|| format!("shuffle_h_polys-{}", pp.shuffles.len()));
+        let shuffle_h_polys = shuffle_h_polys(&shuffle_compressed_polys, &beta_prime);
+        end_timer(timer);
+
         let lookup_h_comms = {
-            let polys = lookup_h_polys.iter().flatten();
+            let polys = chain![&lookup_h_polys, &shuffle_h_polys].flatten();
             Pcs::batch_commit_and_write(&pp.pcs, polys, transcript)?
         };
 
@@ -217,6 +235,7 @@ where
                 witness_polys,
                 lookup_m_polys,
                 lookup_h_polys.into_iter().flatten(),
+                shuffle_h_polys.into_iter().flatten(),
                 powers_of_zeta_poly,
             ]
             .collect(),
@@ -332,6 +351,8 @@ where
         } = vp;
         let accumulator = accumulator.borrow_mut();
 
+        transcript.common_field_element(&vp.vp_digest)?;
+
         for (num_instances, instances) in vp.num_instances.iter().zip_eq(instances) {
             assert_eq!(instances.len(), *num_instances);
             for instance in instances.iter() {
@@ -357,13 +378,17 @@ where
             .take(*num_theta_primes)
             .collect_vec();
 
-        let lookup_m_comms = Pcs::read_commitments(&vp.pcs, vp.num_lookups, transcript)?;
+        let lookup_m_comms = Pcs::read_commitments(&vp.pcs, vp.lookups.len(), transcript)?;
 
         // Round n+1
 
         let beta_prime = transcript.squeeze_challenge();
 
-        let lookup_h_comms = Pcs::read_commitments(&vp.pcs, 2 * vp.num_lookups, transcript)?;
+        let lookup_h_comms = Pcs::read_commitments(
+            &vp.pcs,
+            2 * (vp.lookups.len() + vp.num_shuffles),
+            transcript,
+        )?;
 
         // Round n+2
 
@@ -439,6 +464,7 @@ where
     ) -> Result<(), Error> {
         let ProtostarProverParam { pp, .. } = pp;
 
+        transcript.common_field_element(&pp.vp_digest)?;
         accumulator.instance.absorb_into(transcript)?;
 
         // Round 0
@@ -489,10 +515,12 @@ where
         .collect();
         let (points, evals) = {
             prove_sum_check(
+                Evaluations,
                 pp.num_instances.len(),
                 &pp.expression,
                 accumulator.instance.claimed_sum(),
                 &polys,
+                &Default::default(),
                 challenges,
                 y,
                 transcript,
@@ -527,6 +555,7 @@ where
     ) -> Result<(), Error> {
         let ProtostarVerifierParam { vp, .. } = vp;
 
+        transcript.common_field_element(&vp.vp_digest)?;
         accumulator.absorb_into(transcript)?;
 
         // Round 0
@@ -550,6 +579,7 @@ where
         .collect_vec();
         let (points, evals) = {
             verify_sum_check(
+                Evaluations,
                 vp.num_vars,
                 &vp.expression,
                 accumulator.claimed_sum(),
@@ -589,9 +619,16 @@ impl<Pcs, const STRATEGY: usize> WitnessEncoding for Protostar<HyperPlonk<Pcs>,
 #[cfg(test)]
 pub(crate) mod test {
     use crate::{
-        accumulation::{protostar::Protostar, test::run_accumulation_scheme},
+        accumulation::{
+            protostar::{hyperplonk::ProtostarDeciderProof, Protostar},
+            test::run_accumulation_scheme,
+            AccumulationScheme,
+        },
         backend::hyperplonk::{
-            util::{rand_vanilla_plonk_circuit, rand_vanilla_plonk_w_lookup_circuit},
+            util::{
+                rand_vanilla_plonk_circuit, rand_vanilla_plonk_w_dynamic_lookup_circuit,
+                rand_vanilla_plonk_w_lookup_circuit, rand_vanilla_plonk_w_shuffle_circuit,
+            },
             HyperPlonk,
         },
         pcs::{
@@ -601,12 +638,15 @@ pub(crate) mod test {
         util::{
             expression::rotate::BinaryField,
             test::{seeded_std_rng, std_rng},
-            transcript::Keccak256Transcript,
+            transcript::{InMemoryTranscript, Keccak256Transcript, PoseidonTranscript},
             Itertools,
         },
     };
-    use halo2_curves::{bn256::Bn256, grumpkin};
-    use std::iter;
+    use halo2_curves::{
+        bn256::{self, Bn256},
+        grumpkin,
+    };
+    use std::{io::Cursor, iter};
 
     macro_rules! tests {
         ($suffix:ident, $pcs:ty, $num_vars_range:expr) => {
@@ -623,6 +663,18 @@ pub(crate) mod test {
                     });
                 }
 
+                #[test]
+                fn [<vanilla_plonk_w_ $suffix _w_poseidon_transcript>]() {
+                    run_accumulation_scheme::<_, Protostar<HyperPlonk<$pcs>>, PoseidonTranscript<_, _>, _>($num_vars_range, |num_vars| {
+                        let (circuit_info, _) = rand_vanilla_plonk_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                        let circuits = iter::repeat_with(|| {
+                            let (_, circuit) = rand_vanilla_plonk_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                            circuit
+                        }).take(3).collect_vec();
+                        (circuit_info, circuits)
+                    });
+                }
+
                 #[test]
                 fn [<vanilla_plonk_w_lookup_w_ $suffix>]() {
                     run_accumulation_scheme::<_, Protostar<HyperPlonk<$pcs>>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
@@ -634,6 +686,30 @@ pub(crate) mod test {
                         (circuit_info, circuits)
                     });
                 }
+
+                #[test]
+                fn [<vanilla_plonk_w_dynamic_lookup_w_ $suffix>]() {
+                    run_accumulation_scheme::<_, Protostar<HyperPlonk<$pcs>>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        let (circuit_info, _) = rand_vanilla_plonk_w_dynamic_lookup_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                        let circuits = iter::repeat_with(|| {
+                            let (_, circuit) = rand_vanilla_plonk_w_dynamic_lookup_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                            circuit
+                        }).take(3).collect_vec();
+                        (circuit_info, circuits)
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_shuffle_w_ $suffix>]() {
+                    run_accumulation_scheme::<_, Protostar<HyperPlonk<$pcs>>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        let (circuit_info, _) = rand_vanilla_plonk_w_shuffle_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                        let circuits = iter::repeat_with(|| {
+                            let (_, circuit) = rand_vanilla_plonk_w_shuffle_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                            circuit
+                        }).take(3).collect_vec();
+                        (circuit_info, circuits)
+                    });
+                }
             }
         };
         ($suffix:ident, $pcs:ty) => {
@@ -645,4 +721,41 @@ pub(crate) mod test {
     tests!(kzg, MultilinearKzg<Bn256>);
     tests!(gemini_kzg, Gemini<UnivariateKzg<Bn256>>);
     tests!(zeromorph_kzg, Zeromorph<UnivariateKzg<Bn256>>);
+
+    #[test]
+    fn decider_proof_bytes_roundtrip() {
+        type Fs = Protostar<HyperPlonk<MultilinearKzg<Bn256>>>;
+        type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_w_lookup_circuit::<bn256::Fr, BinaryField>(
+            num_vars,
+            std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Fs::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Fs::preprocess(&param, &circuit_info).unwrap();
+        let mut accumulator = Fs::init_accumulator(&pp).unwrap();
+        Fs::prove_accumulation_from_nark(
+            &pp,
+            &mut accumulator,
+            &circuit,
+            &mut T::new(()),
+            seeded_std_rng(),
+        )
+        .unwrap();
+        let proof = {
+            let mut transcript = T::new(());
+            Fs::prove_decider(&pp, &accumulator, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+
+        let typed = ProtostarDeciderProof::from_bytes::<T>(&vp, (), &proof).unwrap();
+        assert_eq!(typed.sum_check_msgs().len(), num_vars);
+        assert!(!typed.pcs_proof().is_empty());
+
+        let typed: ProtostarDeciderProof<_, MultilinearKzg<Bn256>> =
+            bincode::deserialize(&bincode::serialize(&typed).unwrap()).unwrap();
+        assert_eq!(typed.to_bytes::<T>(()).unwrap(), proof);
+    }
 }
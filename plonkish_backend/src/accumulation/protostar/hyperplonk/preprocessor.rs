@@ -5,7 +5,10 @@ use crate::{
         ProtostarVerifierParam,
     },
     backend::{
-        hyperplonk::{preprocessor::permutation_constraints, HyperPlonk},
+        hyperplonk::{
+            preprocessor::{permutation_constraints, vp_digest},
+            HyperPlonk,
+        },
         PlonkishBackend, PlonkishCircuitInfo,
     },
     pcs::PolynomialCommitmentScheme,
@@ -27,7 +30,8 @@ pub(crate) fn batch_size<F: PrimeField>(
     circuit_info: &PlonkishCircuitInfo<F>,
     strategy: ProtostarStrategy,
 ) -> usize {
-    let num_lookups = circuit_info.lookups.len();
+    let num_lookups = circuit_info.vector_lookups().len();
+    let num_shuffles = circuit_info.shuffles.len();
     let num_permutation_polys = circuit_info.permutation_polys().len();
     chain![
         [circuit_info.preprocess_polys.len() + circuit_info.permutation_polys().len()],
@@ -41,7 +45,8 @@ pub(crate) fn batch_size<F: PrimeField>(
                 vec![1]
             }
         },
-        [2 * num_lookups + div_ceil(num_permutation_polys, max_degree(circuit_info, None) - 1)],
+        [2 * (num_lookups + num_shuffles)
+            + div_ceil(num_permutation_polys, max_degree(circuit_info, None) - 1)],
         [1],
     ]
     .sum()
@@ -64,7 +69,10 @@ where
     Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
 {
     let challenge_offset = circuit_info.num_challenges.iter().sum::<usize>();
-    let max_lookup_width = circuit_info.lookups.iter().map(Vec::len).max().unwrap_or(0);
+    let max_lookup_width = chain![&circuit_info.vector_lookups(), &circuit_info.shuffles]
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0);
     let num_theta_primes = max_lookup_width.checked_sub(1).unwrap_or_default();
     let theta_primes = (challenge_offset..)
         .take(num_theta_primes)
@@ -84,6 +92,8 @@ where
         circuit_info.num_instances.len() + circuit_info.preprocess_polys.len();
     let num_witness_polys = circuit_info.num_witness_polys.iter().sum::<usize>();
     let num_permutation_z_polys = div_ceil(circuit_info.permutation_polys().len(), max_degree - 1);
+    let num_lookup_polys =
+        3 * circuit_info.vector_lookups().len() + 2 * circuit_info.shuffles.len();
 
     let (
         num_builtin_witness_polys,
@@ -94,7 +104,7 @@ where
     ) = match strategy {
         NoCompressing => {
             let alpha_prime_offset = challenge_offset + num_theta_primes + 1;
-            let num_builtin_witness_polys = 3 * circuit_info.lookups.len();
+            let num_builtin_witness_polys = num_lookup_polys;
             let builtin_witness_poly_offset =
                 witness_poly_offset + num_witness_polys + circuit_info.permutation_polys().len();
 
@@ -144,9 +154,7 @@ where
 
             let u = num_folding_challenges;
             let relexed_constraint = {
-                let e = builtin_witness_poly_offset
-                    + circuit_info.lookups.len() * 3
-                    + num_permutation_z_polys;
+                let e = builtin_witness_poly_offset + num_lookup_polys + num_permutation_z_polys;
                 relaxed_expression(&products, u)
                     - Expression::Polynomial(Query::new(e, Rotation::cur()))
             };
@@ -162,7 +170,7 @@ where
         Compressing => {
             let zeta = challenge_offset + num_theta_primes + 1;
             let alpha_prime_offset = zeta + 1;
-            let num_builtin_witness_polys = 3 * circuit_info.lookups.len() + 1;
+            let num_builtin_witness_polys = num_lookup_polys + 1;
             let builtin_witness_poly_offset =
                 witness_poly_offset + num_witness_polys + circuit_info.permutation_polys().len();
 
@@ -178,7 +186,7 @@ where
                 .collect(),
             };
 
-            let powers_of_zeta = builtin_witness_poly_offset + circuit_info.lookups.len() * 3;
+            let powers_of_zeta = builtin_witness_poly_offset + num_lookup_polys;
             let compressed_products = {
                 let mut constraints =
                     chain![circuit_info.constraints.iter(), lookup_constraints.iter()]
@@ -261,8 +269,8 @@ where
         )
     };
 
-    let (pp, vp) = {
-        let (mut pp, mut vp) = HyperPlonk::preprocess(param, circuit_info)?;
+    let (mut pp, vp) = {
+        let (mut pp, mut vp) = HyperPlonk::<Pcs>::preprocess(param, circuit_info)?;
         let batch_size = batch_size(circuit_info, strategy);
         let (pcs_pp, pcs_vp) = Pcs::trim(param, 1 << circuit_info.k, batch_size)?;
         pp.pcs = pcs_pp;
@@ -271,11 +279,24 @@ where
         vp.num_permutation_z_polys = num_permutation_z_polys;
         pp.expression = expression.clone();
         vp.expression = expression;
+        vp.vp_digest = F::ZERO;
         (pp, vp)
     };
 
     let num_cross_terms = cross_term_expressions.len();
 
+    let mut vp = ProtostarVerifierParam {
+        vp,
+        strategy,
+        num_theta_primes,
+        num_alpha_primes,
+        num_folding_witness_polys,
+        num_folding_challenges,
+        num_cross_terms,
+    };
+    vp.vp.vp_digest = vp_digest(&vp);
+    pp.vp_digest = vp.vp.vp_digest;
+
     Ok((
         ProtostarProverParam {
             pp,
@@ -286,15 +307,7 @@ where
             num_folding_challenges,
             cross_term_expressions,
         },
-        ProtostarVerifierParam {
-            vp,
-            strategy,
-            num_theta_primes,
-            num_alpha_primes,
-            num_folding_witness_polys,
-            num_folding_challenges,
-            num_cross_terms,
-        },
+        vp,
     ))
 }
 
@@ -303,7 +316,10 @@ pub(crate) fn max_degree<F: PrimeField>(
     lookup_constraints: Option<&[Expression<F>]>,
 ) -> usize {
     let lookup_constraints = lookup_constraints.map(Cow::Borrowed).unwrap_or_else(|| {
-        let n = circuit_info.lookups.iter().map(Vec::len).max().unwrap_or(1);
+        let n = chain![&circuit_info.vector_lookups(), &circuit_info.shuffles]
+            .map(Vec::len)
+            .max()
+            .unwrap_or(1);
         let dummy_challenges = vec![Expression::zero(); n];
         Cow::Owned(
             self::lookup_constraints(circuit_info, &dummy_challenges, &dummy_challenges[0]).0,
@@ -335,47 +351,70 @@ pub(crate) fn folding_degree<F: PrimeField>(
     )
 }
 
+/// Returns constraints and polynomials to be sum-checked to zero of lookups
+/// and shuffles, where a shuffle is a lookup with multiplicities fixed to one,
+/// so it only has the `h` polynomials but no `m` polynomial.
 pub(crate) fn lookup_constraints<F: PrimeField>(
     circuit_info: &PlonkishCircuitInfo<F>,
     theta_primes: &[Expression<F>],
     beta_prime: &Expression<F>,
 ) -> (Vec<Expression<F>>, Vec<Expression<F>>) {
+    let lookups = circuit_info.vector_lookups();
     let one = &Expression::one();
     let m_offset = circuit_info.num_poly() + circuit_info.permutation_polys().len();
-    let h_offset = m_offset + circuit_info.lookups.len();
-    let constraints = circuit_info
-        .lookups
-        .iter()
-        .zip(m_offset..)
-        .zip((h_offset..).step_by(2))
-        .flat_map(|((lookup, m), h)| {
-            let [m, h_input, h_table] = &[m, h, h + 1]
-                .map(|poly| Query::new(poly, Rotation::cur()))
-                .map(Expression::<F>::Polynomial);
-            let (inputs, tables) = lookup
-                .iter()
-                .map(|(input, table)| (input, table))
-                .unzip::<_, _, Vec<_>, Vec<_>>();
-            let [input, table] = &[inputs, tables].map(|exprs| {
-                chain![
-                    exprs.first().cloned().cloned(),
-                    exprs
-                        .into_iter()
-                        .skip(1)
-                        .zip(theta_primes)
-                        .map(|(expr, theta_prime)| expr * theta_prime),
-                ]
-                .sum::<Expression<_>>()
-            });
-            [
-                h_input * (input + beta_prime) - one,
-                h_table * (table + beta_prime) - m,
+    let h_offset = m_offset + lookups.len();
+    let shuffle_h_offset = h_offset + 2 * lookups.len();
+    let compress = |lookup: &[(Expression<F>, Expression<F>)]| {
+        let (inputs, tables) = lookup
+            .iter()
+            .map(|(input, table)| (input, table))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        [inputs, tables].map(|exprs| {
+            chain![
+                exprs.first().cloned().cloned(),
+                exprs
+                    .into_iter()
+                    .skip(1)
+                    .zip(theta_primes)
+                    .map(|(expr, theta_prime)| expr * theta_prime),
             ]
+            .sum::<Expression<_>>()
         })
-        .collect_vec();
+    };
+    let constraints = chain![
+        lookups
+            .iter()
+            .zip(m_offset..)
+            .zip((h_offset..).step_by(2))
+            .flat_map(|((lookup, m), h)| {
+                let [m, h_input, h_table] = &[m, h, h + 1]
+                    .map(|poly| Query::new(poly, Rotation::cur()))
+                    .map(Expression::<F>::Polynomial);
+                let [input, table] = &compress(lookup);
+                [
+                    h_input * (input + beta_prime) - one,
+                    h_table * (table + beta_prime) - m,
+                ]
+            }),
+        circuit_info
+            .shuffles
+            .iter()
+            .zip((shuffle_h_offset..).step_by(2))
+            .flat_map(|(shuffle, h)| {
+                let [h_input, h_shuffle] = &[h, h + 1]
+                    .map(|poly| Query::new(poly, Rotation::cur()))
+                    .map(Expression::<F>::Polynomial);
+                let [input, shuffle] = &compress(shuffle);
+                [
+                    h_input * (input + beta_prime) - one,
+                    h_shuffle * (shuffle + beta_prime) - one,
+                ]
+            }),
+    ]
+    .collect_vec();
     let sum_check = (h_offset..)
         .step_by(2)
-        .take(circuit_info.lookups.len())
+        .take(lookups.len() + circuit_info.shuffles.len())
         .map(|h| {
             let [h_input, h_table] = &[h, h + 1]
                 .map(|poly| Query::new(poly, Rotation::cur()))
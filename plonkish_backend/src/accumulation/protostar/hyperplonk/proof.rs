@@ -0,0 +1,115 @@
+use crate::{
+    accumulation::protostar::ProtostarVerifierParam,
+    backend::hyperplonk::{
+        proof::{proof_suffix, read_evals, read_sum_check_msgs, write_nested_field_elements},
+        HyperPlonk,
+    },
+    pcs::PolynomialCommitmentScheme,
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::PrimeField,
+        chain,
+        transcript::{InMemoryTranscript, TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Serialize,
+    },
+    Error,
+};
+use std::hash::Hash;
+
+/// Decider proof of [`Protostar`] on [`HyperPlonk`] split into named
+/// components, which converts losslessly from and to the bytes of an
+/// [`InMemoryTranscript`].
+///
+/// [`Protostar`]: crate::accumulation::protostar::Protostar
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct ProtostarDeciderProof<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub(crate) permutation_z_comms: Vec<Pcs::Commitment>,
+    pub(crate) sum_check_msgs: Vec<Vec<F>>,
+    pub(crate) evals: Vec<Vec<F>>,
+    pub(crate) pcs_proof: Vec<u8>,
+}
+
+impl<F, Pcs> ProtostarDeciderProof<F, Pcs>
+where
+    F: PrimeField + Hash + Serialize + DeserializeOwned,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+{
+    pub fn permutation_z_comms(&self) -> &[Pcs::Commitment] {
+        &self.permutation_z_comms
+    }
+
+    /// Returns evaluations of each round polynomial of the sum-check on
+    /// `0, 1, ..., degree`.
+    pub fn sum_check_msgs(&self) -> &[Vec<F>] {
+        &self.sum_check_msgs
+    }
+
+    /// Returns evaluations of each committed query, one for each point of its
+    /// rotation.
+    pub fn evals(&self) -> &[Vec<F>] {
+        &self.evals
+    }
+
+    /// Returns the opening proof of PCS, which is kept as bytes since its
+    /// format is specific to the PCS.
+    pub fn pcs_proof(&self) -> &[u8] {
+        &self.pcs_proof
+    }
+
+    pub fn from_bytes<T>(
+        vp: &ProtostarVerifierParam<F, HyperPlonk<Pcs>>,
+        param: T::Param,
+        proof: &[u8],
+    ) -> Result<Self, Error>
+    where
+        T: TranscriptRead<Pcs::CommitmentChunk, F>
+            + TranscriptWrite<Pcs::CommitmentChunk, F>
+            + InMemoryTranscript,
+    {
+        let vp = &vp.vp;
+        let mut transcript = T::from_proof(param.clone(), proof);
+        let mut parsed = Self {
+            permutation_z_comms: Pcs::read_commitments(
+                &vp.pcs,
+                vp.num_permutation_z_polys,
+                &mut transcript,
+            )?,
+            sum_check_msgs: read_sum_check_msgs(
+                vp.num_vars,
+                vp.expression.degree(),
+                &mut transcript,
+            )?,
+            evals: read_evals(&vp.expression, vp.num_instances.len(), &mut transcript)?,
+            pcs_proof: Vec::new(),
+        };
+        parsed.pcs_proof = proof_suffix::<T>(param, proof, |transcript| {
+            parsed.write_without_pcs_proof(transcript)
+        })?;
+        Ok(parsed)
+    }
+
+    pub fn to_bytes<T>(&self, param: T::Param) -> Result<Vec<u8>, Error>
+    where
+        T: TranscriptWrite<Pcs::CommitmentChunk, F> + InMemoryTranscript,
+    {
+        let mut transcript = T::new(param);
+        self.write_without_pcs_proof(&mut transcript)?;
+        Ok(chain![transcript.into_proof(), self.pcs_proof.iter().copied()].collect())
+    }
+
+    fn write_without_pcs_proof(
+        &self,
+        transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        for comm in &self.permutation_z_comms {
+            transcript.write_commitments(comm.as_ref())?;
+        }
+        write_nested_field_elements(&self.sum_check_msgs, transcript)?;
+        write_nested_field_elements(&self.evals, transcript)
+    }
+}
@@ -35,6 +35,47 @@ fn lookup_h_poly<F: PrimeField + Hash>(
     m_poly: &MultilinearPolynomial<F>,
     beta: &F,
 ) -> [MultilinearPolynomial<F>; 2] {
+    let [h_input, mut h_table] = inverse_polys(compressed_polys, beta);
+
+    parallelize(&mut h_table, |(h_table, start)| {
+        for (h_table, m) in h_table.iter_mut().zip(m_poly[start..].iter()) {
+            *h_table *= m;
+        }
+    });
+
+    if cfg!(feature = "sanity-check") {
+        assert_eq!(sum::<F>(&h_input), sum::<F>(&h_table));
+    }
+
+    [
+        MultilinearPolynomial::new(h_input),
+        MultilinearPolynomial::new(h_table),
+    ]
+}
+
+pub(crate) fn shuffle_h_polys<F: PrimeField + Hash>(
+    compressed_polys: &[[MultilinearPolynomial<F>; 2]],
+    beta: &F,
+) -> Vec<[MultilinearPolynomial<F>; 2]> {
+    compressed_polys
+        .iter()
+        .map(|compressed_polys| {
+            let [h_input, h_shuffle] = inverse_polys(compressed_polys, beta);
+
+            if cfg!(feature = "sanity-check") {
+                assert_eq!(sum::<F>(&h_input), sum::<F>(&h_shuffle));
+            }
+
+            [h_input, h_shuffle].map(MultilinearPolynomial::new)
+        })
+        .collect()
+}
+
+/// Returns evaluations of `1 / (beta + input)` and `1 / (beta + table)`.
+fn inverse_polys<F: PrimeField>(
+    compressed_polys: &[MultilinearPolynomial<F>; 2],
+    beta: &F,
+) -> [Vec<F>; 2] {
     let [input, table] = compressed_polys;
     let mut h_input = vec![F::ZERO; 1 << input.num_vars()];
     let mut h_table = vec![F::ZERO; 1 << input.num_vars()];
@@ -61,20 +102,7 @@ fn lookup_h_poly<F: PrimeField + Hash>(
         },
     );
 
-    parallelize(&mut h_table, |(h_table, start)| {
-        for (h_table, m) in h_table.iter_mut().zip(m_poly[start..].iter()) {
-            *h_table *= m;
-        }
-    });
-
-    if cfg!(feature = "sanity-check") {
-        assert_eq!(sum::<F>(&h_input), sum::<F>(&h_table));
-    }
-
-    [
-        MultilinearPolynomial::new(h_input),
-        MultilinearPolynomial::new(h_table),
-    ]
+    [h_input, h_table]
 }
 
 pub(super) fn powers_of_zeta_poly<F: PrimeField>(
@@ -13,7 +13,7 @@ pub(crate) mod test {
         util::{
             expression::rotate::BinaryField,
             test::{seeded_std_rng, std_rng},
-            transcript::Keccak256Transcript,
+            transcript::{Keccak256Transcript, PoseidonTranscript},
             Itertools,
         },
     };
@@ -35,6 +35,18 @@ pub(crate) mod test {
                     });
                 }
 
+                #[test]
+                fn [<vanilla_plonk_w_ $suffix _w_poseidon_transcript>]() {
+                    run_accumulation_scheme::<_, Sangria<HyperPlonk<$pcs>>, PoseidonTranscript<_, _>, _>($num_vars_range, |num_vars| {
+                        let (circuit_info, _) = rand_vanilla_plonk_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                        let circuits = iter::repeat_with(|| {
+                            let (_, circuit) = rand_vanilla_plonk_circuit::<_, BinaryField>(num_vars, std_rng(), seeded_std_rng());
+                            circuit
+                        }).take(3).collect_vec();
+                        (circuit_info, circuits)
+                    });
+                }
+
                 #[test]
                 fn [<vanilla_plonk_w_lookup_w_ $suffix>]() {
                     run_accumulation_scheme::<_, Sangria<HyperPlonk<$pcs>>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
@@ -3,7 +3,7 @@ use crate::{
     util::{
         arithmetic::Field,
         chain,
-        expression::Expression,
+        expression::{Expression, Rotation},
         transcript::{TranscriptRead, TranscriptWrite},
         Deserialize, DeserializeOwned, Itertools, Serialize,
     },
@@ -13,7 +13,12 @@ use rand::RngCore;
 use std::{collections::BTreeSet, fmt::Debug};
 
 pub mod hyperplonk;
+mod lookup;
+pub mod mock_prover;
 pub mod unihyperplonk;
+pub mod zkhyperplonk;
+
+pub use lookup::{DecomposableLookup, Lookup, Subtable};
 
 pub trait PlonkishBackend<F: Field>: Clone + Debug {
     type Pcs: PolynomialCommitmentScheme<F>;
@@ -62,10 +67,13 @@ pub struct PlonkishCircuitInfo<F> {
     pub num_challenges: Vec<usize>,
     /// Constraints.
     pub constraints: Vec<Expression<F>>,
-    /// Each item inside outer vector repesents an independent vector lookup,
-    /// which contains vector of tuples representing the input and table
-    /// respectively.
-    pub lookups: Vec<Vec<(Expression<F>, Expression<F>)>>,
+    /// Each item repesents an independent lookup, see [`Lookup`].
+    pub lookups: Vec<Lookup<F>>,
+    /// Each item inside outer vector repesents an independent vector shuffle,
+    /// which contains vector of tuples representing the input and shuffle
+    /// respectively, and requires tuples of input over all rows to be a
+    /// permutation of tuples of shuffle.
+    pub shuffles: Vec<Vec<(Expression<F>, Expression<F>)>>,
     /// Each item inside outer vector repesents an closed permutation cycle,
     /// which contains vetor of tuples representing the polynomial index and
     /// row respectively.
@@ -74,37 +82,269 @@ pub struct PlonkishCircuitInfo<F> {
     pub max_degree: Option<usize>,
 }
 
+/// Problem found by [`PlonkishCircuitInfo::validate`], where `poly` is index
+/// of polynomial counted from instance polynomials.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CircuitInfoError {
+    UnmatchedNumPhases {
+        num_witness_polys: usize,
+        num_challenges: usize,
+    },
+    EmptyPhase {
+        phase: usize,
+    },
+    NoChallengeAfterPhase {
+        phase: usize,
+    },
+    PolyOutOfRange {
+        poly: usize,
+        num_poly: usize,
+    },
+    ChallengeOutOfRange {
+        challenge: usize,
+        num_challenges: usize,
+    },
+    DegreeTooHigh {
+        constraint: usize,
+        degree: usize,
+        max_degree: usize,
+    },
+    InvalidPreprocessPolyLength {
+        poly: usize,
+        len: usize,
+        expected: usize,
+    },
+    PermutationRowOutOfRange {
+        cycle: usize,
+        poly: usize,
+        row: usize,
+        num_rows: usize,
+    },
+    InvalidSubtableNumVars {
+        lookup: usize,
+        chunk: usize,
+        num_vars: usize,
+        max_num_vars: usize,
+    },
+    RotationInChunk {
+        lookup: usize,
+        chunk: usize,
+        rotation: Rotation,
+    },
+    ChunkOutOfRange {
+        lookup: usize,
+        chunk: usize,
+        num_chunks: usize,
+    },
+    UnsupportedLookup {
+        lookup: usize,
+    },
+    TooFewUsableRows {
+        num_usable_rows: usize,
+        num_reserved_rows: usize,
+    },
+    PermutationOnBlindingRow {
+        cycle: usize,
+        poly: usize,
+        row: usize,
+    },
+    TooManyEvaluations {
+        poly: usize,
+        num_evals: usize,
+        max_num_evals: usize,
+    },
+    TooFewVarsForMask {
+        num_vars: usize,
+        mask_num_vars: usize,
+    },
+}
+
 impl<F: Clone> PlonkishCircuitInfo<F> {
     pub fn is_well_formed(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Returns all problems found in the circuit info, or `Ok(())` if it's
+    /// well-formed.
+    pub fn validate(&self) -> Result<(), Vec<CircuitInfoError>> {
+        let mut errors = Vec::new();
+
+        // Same amount of phases
+        if self.num_witness_polys.len() != self.num_challenges.len() {
+            errors.push(CircuitInfoError::UnmatchedNumPhases {
+                num_witness_polys: self.num_witness_polys.len(),
+                num_challenges: self.num_challenges.len(),
+            });
+        }
+        // Every phase has some witness polys
+        errors.extend(
+            self.num_witness_polys
+                .iter()
+                .positions(|n| *n == 0)
+                .map(|phase| CircuitInfoError::EmptyPhase { phase }),
+        );
+        // Every phase except the last one has some challenges after the witness polys are committed
+        if let Some((_, num_challenges)) = self.num_challenges.split_last() {
+            errors.extend(
+                num_challenges
+                    .iter()
+                    .positions(|n| *n == 0)
+                    .map(|phase| CircuitInfoError::NoChallengeAfterPhase { phase }),
+            );
+        }
+        // Polynomial indices are in range
         let num_poly = self.num_poly();
+        errors.extend(
+            chain![
+                self.expressions().flat_map(Expression::used_poly),
+                self.permutation_polys(),
+            ]
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|poly| *poly >= num_poly)
+            .map(|poly| CircuitInfoError::PolyOutOfRange { poly, num_poly }),
+        );
+        // Challenge indices are in range
         let num_challenges = self.num_challenges.iter().sum::<usize>();
-        let polys = chain![
-            self.expressions().flat_map(Expression::used_poly),
-            self.permutation_polys(),
-        ]
-        .collect::<BTreeSet<_>>();
-        let challenges = chain![self.expressions().flat_map(Expression::used_challenge)]
-            .collect::<BTreeSet<_>>();
-        // Same amount of phases
-        self.num_witness_polys.len() == self.num_challenges.len()
-            // Every phase has some witness polys
-            && !self.num_witness_polys.iter().any(|n| *n == 0)
-            // Every phase except the last one has some challenges after the witness polys are committed
-            && !self.num_challenges[..self.num_challenges.len() - 1].iter().any(|n| *n == 0)
-            // Polynomial indices are in range
-            && (polys.is_empty() || *polys.last().unwrap() < num_poly)
-            // Challenge indices are in range
-            && (challenges.is_empty() || *challenges.last().unwrap() < num_challenges)
-            // Every constraint has degree less equal than `max_degree`
-            && self
-                .max_degree
-                .map(|max_degree| {
-                    !self
-                        .constraints
+        errors.extend(
+            chain![self.expressions().flat_map(Expression::used_challenge)]
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .filter(|challenge| *challenge >= num_challenges)
+                .map(|challenge| CircuitInfoError::ChallengeOutOfRange {
+                    challenge,
+                    num_challenges,
+                }),
+        );
+        // Every constraint has degree less equal than `max_degree`
+        if let Some(max_degree) = self.max_degree {
+            errors.extend(
+                self.constraints
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, constraint)| constraint.degree() > max_degree)
+                    .map(|(constraint, expression)| CircuitInfoError::DegreeTooHigh {
+                        constraint,
+                        degree: expression.degree(),
+                        max_degree,
+                    }),
+            );
+        }
+        // Every preprocessed polynomial has `2^k` evaluations
+        let n = 1 << self.k;
+        errors.extend(
+            self.preprocess_polys
+                .iter()
+                .enumerate()
+                .filter(|(_, poly)| poly.len() != n)
+                .map(
+                    |(idx, poly)| CircuitInfoError::InvalidPreprocessPolyLength {
+                        poly: self.num_instances.len() + idx,
+                        len: poly.len(),
+                        expected: n,
+                    },
+                ),
+        );
+        // Every copied cell is in range
+        errors.extend(
+            self.permutations
+                .iter()
+                .enumerate()
+                .flat_map(|(cycle, cells)| {
+                    cells
                         .iter()
-                        .any(|constraint| constraint.degree() > max_degree)
-                })
-                .unwrap_or(true)
+                        .filter(|(_, row)| *row >= n)
+                        .map(
+                            move |(poly, row)| CircuitInfoError::PermutationRowOutOfRange {
+                                cycle,
+                                poly: *poly,
+                                row: *row,
+                                num_rows: n,
+                            },
+                        )
+                }),
+        );
+        // Every decomposable lookup has subtables fitting in circuit, chunks only on current
+        // rotation and combining function only on chunks
+        for (lookup, decomposable) in self
+            .lookups
+            .iter()
+            .enumerate()
+            .filter_map(|(lookup, decomposable)| Some((lookup, decomposable.as_decomposable()?)))
+        {
+            let num_chunks = decomposable.chunks.len();
+            for (chunk, (index, value, subtable)) in decomposable.chunks.iter().enumerate() {
+                if subtable.num_vars() == 0 || subtable.num_vars() > self.k {
+                    errors.push(CircuitInfoError::InvalidSubtableNumVars {
+                        lookup,
+                        chunk,
+                        num_vars: subtable.num_vars(),
+                        max_num_vars: self.k,
+                    });
+                }
+                errors.extend(
+                    chain![index.used_rotation(), value.used_rotation()]
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .filter(|rotation| *rotation != Rotation::cur())
+                        .map(|rotation| CircuitInfoError::RotationInChunk {
+                            lookup,
+                            chunk,
+                            rotation,
+                        }),
+                );
+            }
+            errors.extend(
+                decomposable
+                    .combine
+                    .used_poly()
+                    .into_iter()
+                    .filter(|chunk| *chunk >= num_chunks)
+                    .map(|chunk| CircuitInfoError::ChunkOutOfRange {
+                        lookup,
+                        chunk,
+                        num_chunks,
+                    }),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns error for each decomposable lookup, for backend which only
+    /// supports vector lookups.
+    pub(crate) fn ensure_only_vector_lookups(&self) -> Result<(), Error> {
+        let errors = self
+            .lookups
+            .iter()
+            .positions(|lookup| lookup.as_decomposable().is_some())
+            .map(|lookup| CircuitInfoError::UnsupportedLookup { lookup })
+            .collect_vec();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidCircuitInfo(errors))
+        }
+    }
+
+    pub(crate) fn vector_lookups(&self) -> Vec<Vec<(Expression<F>, Expression<F>)>> {
+        self.lookups
+            .iter()
+            .filter_map(Lookup::as_vector)
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn decomposable_lookups(&self) -> Vec<DecomposableLookup<F>> {
+        self.lookups
+            .iter()
+            .filter_map(Lookup::as_decomposable)
+            .cloned()
+            .collect()
     }
 
     pub fn num_poly(&self) -> usize {
@@ -125,8 +365,10 @@ impl<F: Clone> PlonkishCircuitInfo<F> {
     pub fn expressions(&self) -> impl Iterator<Item = &Expression<F>> {
         chain![
             &self.constraints,
-            chain![&self.lookups]
-                .flat_map(|lookup| lookup.iter().flat_map(|(input, table)| [input, table])),
+            self.lookups.iter().flat_map(Lookup::expressions),
+            self.shuffles
+                .iter()
+                .flat_map(|shuffle| shuffle.iter().flat_map(|(input, shuffle)| [input, shuffle])),
         ]
     }
 }
@@ -154,11 +396,17 @@ mod mock {
 
     pub(crate) struct MockCircuit<F> {
         instances: Vec<Vec<F>>,
-        witnesses: Vec<Vec<F>>,
+        witnesses: Vec<Vec<Vec<F>>>,
     }
 
     impl<F> MockCircuit<F> {
         pub(crate) fn new(instances: Vec<Vec<F>>, witnesses: Vec<Vec<F>>) -> Self {
+            Self::new_w_phases(instances, vec![witnesses])
+        }
+
+        /// Returns circuit with witnesses of each phase, which don't depend on
+        /// challenges.
+        pub(crate) fn new_w_phases(instances: Vec<Vec<F>>, witnesses: Vec<Vec<Vec<F>>>) -> Self {
             Self {
                 instances,
                 witnesses,
@@ -179,9 +427,8 @@ mod mock {
             &self.instances
         }
 
-        fn synthesize(&self, round: usize, challenges: &[F]) -> Result<Vec<Vec<F>>, Error> {
-            assert!(round == 0 && challenges.is_empty());
-            Ok(self.witnesses.clone())
+        fn synthesize(&self, round: usize, _: &[F]) -> Result<Vec<Vec<F>>, Error> {
+            Ok(self.witnesses[round].clone())
         }
     }
 }
@@ -189,17 +436,30 @@ mod mock {
 #[cfg(test)]
 pub(crate) mod test {
     use crate::{
-        backend::{PlonkishBackend, PlonkishCircuit, PlonkishCircuitInfo},
-        pcs::PolynomialCommitmentScheme,
+        backend::{
+            hyperplonk::{
+                util::{
+                    vanilla_plonk_circuit_info, vanilla_plonk_w_decomposable_lookup_circuit_info,
+                },
+                HyperPlonk,
+            },
+            CircuitInfoError, Lookup, PlonkishBackend, PlonkishCircuit, PlonkishCircuitInfo,
+            Subtable,
+        },
+        pcs::{multilinear::MultilinearKzg, PolynomialCommitmentScheme},
         util::{
-            arithmetic::PrimeField,
-            end_timer, start_timer,
+            arithmetic::{Field, PrimeField},
+            end_timer,
+            expression::{Expression, Query, Rotation},
+            start_timer,
             test::seeded_std_rng,
             transcript::{InMemoryTranscript, TranscriptRead, TranscriptWrite},
             DeserializeOwned, Serialize,
         },
+        Error,
     };
-    use std::{hash::Hash, ops::Range};
+    use halo2_curves::bn256::{Bn256, Fr};
+    use std::{array, hash::Hash, ops::Range};
 
     pub fn run_plonkish_backend<F, Pb, T, C>(
         num_vars_range: Range<usize>,
@@ -241,4 +501,92 @@ pub(crate) mod test {
             end_timer(timer);
         }
     }
+
+    #[test]
+    fn validate_circuit_info() {
+        let mut circuit_info = vanilla_plonk_circuit_info(
+            3,
+            1,
+            array::from_fn(|_| vec![Fr::ZERO; 8]),
+            vec![vec![(6, 1), (7, 7)]],
+        );
+        assert_eq!(circuit_info.validate(), Ok(()));
+
+        circuit_info.num_witness_polys.push(0);
+        circuit_info.num_challenges.push(0);
+        circuit_info.max_degree = Some(2);
+        circuit_info.preprocess_polys[1].pop();
+        circuit_info.permutations[0].push((8, 8));
+        let errors = vec![
+            CircuitInfoError::EmptyPhase { phase: 1 },
+            CircuitInfoError::NoChallengeAfterPhase { phase: 0 },
+            CircuitInfoError::DegreeTooHigh {
+                constraint: 0,
+                degree: 3,
+                max_degree: 2,
+            },
+            CircuitInfoError::InvalidPreprocessPolyLength {
+                poly: 2,
+                len: 7,
+                expected: 8,
+            },
+            CircuitInfoError::PermutationRowOutOfRange {
+                cycle: 0,
+                poly: 8,
+                row: 8,
+                num_rows: 8,
+            },
+        ];
+        assert_eq!(circuit_info.validate(), Err(errors.clone()));
+        assert_eq!(
+            HyperPlonk::<MultilinearKzg<Bn256>>::setup(&circuit_info, seeded_std_rng())
+                .unwrap_err(),
+            Error::InvalidCircuitInfo(errors)
+        );
+    }
+
+    #[test]
+    fn validate_decomposable_lookup() {
+        let mut circuit_info = vanilla_plonk_w_decomposable_lookup_circuit_info(
+            2,
+            1,
+            array::from_fn(|_| vec![Fr::ZERO; 4]),
+            Vec::new(),
+        );
+        assert_eq!(circuit_info.validate(), Ok(()));
+        assert_eq!(
+            circuit_info.ensure_only_vector_lookups(),
+            Err(Error::InvalidCircuitInfo(vec![
+                CircuitInfoError::UnsupportedLookup { lookup: 0 }
+            ]))
+        );
+
+        let Lookup::Decomposable(lookup) = &mut circuit_info.lookups[0] else {
+            unreachable!()
+        };
+        lookup.chunks[0].2 = Subtable::Xor { num_bits: 2 };
+        lookup.chunks[1].1 = Expression::Polynomial(Query::new(12, Rotation::next()));
+        lookup.combine = &lookup.combine + Expression::Polynomial(Query::new(2, Rotation::cur()));
+        assert_eq!(
+            circuit_info.validate(),
+            Err(vec![
+                CircuitInfoError::InvalidSubtableNumVars {
+                    lookup: 0,
+                    chunk: 0,
+                    num_vars: 4,
+                    max_num_vars: 2,
+                },
+                CircuitInfoError::RotationInChunk {
+                    lookup: 0,
+                    chunk: 1,
+                    rotation: Rotation::next(),
+                },
+                CircuitInfoError::ChunkOutOfRange {
+                    lookup: 0,
+                    chunk: 2,
+                    num_chunks: 2,
+                },
+            ])
+        );
+    }
 }
@@ -1,42 +1,126 @@
 use crate::{
     backend::{
         hyperplonk::{
-            preprocessor::{batch_size, preprocess},
+            preprocessor::{batch_size, lasso_num_chunks, preprocess},
             prover::{
-                instance_polys, lookup_compressed_polys, lookup_h_polys, lookup_m_polys,
-                permutation_z_polys, prove_zero_check,
+                instance_polys, lasso_m_polys, lookup_compressed_polys, lookup_h_polys,
+                lookup_m_polys, permutation_z_polys, prove_lasso, prove_logup_gkr,
+                prove_permutation_gkr, prove_zero_check, shuffle_h_polys,
             },
-            verifier::verify_zero_check,
+            verifier::{
+                lasso_m_openings, verify_lasso, verify_logup_gkr, verify_permutation_gkr,
+                verify_zero_check,
+            },
+            LookupStrategy::{LogUp, LogUpGkr},
+            PermutationStrategy::{Gkr, Plonk},
+            SumCheckStrategy::{Coefficients, Evaluations},
         },
-        PlonkishBackend, PlonkishCircuit, PlonkishCircuitInfo, WitnessEncoding,
+        PlonkishBackend, PlonkishCircuit, PlonkishCircuitInfo, Subtable, WitnessEncoding,
     },
-    pcs::PolynomialCommitmentScheme,
+    pcs::{DeferredKzg, Evaluation, KzgAccumulator, PolynomialCommitmentScheme},
     poly::multilinear::MultilinearPolynomial,
     util::{
-        arithmetic::{powers, PrimeField},
+        arithmetic::{powers, MultiMillerLoop, PrimeCurveAffine, PrimeField},
         chain, end_timer,
         expression::{
             rotate::{BinaryField, Rotatable},
             Expression,
         },
-        start_timer,
+        izip, start_timer,
         transcript::{TranscriptRead, TranscriptWrite},
         Deserialize, DeserializeOwned, Itertools, Serialize,
     },
     Error,
 };
 use rand::RngCore;
-use std::{fmt::Debug, hash::Hash, iter, marker::PhantomData};
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash, iter, marker::PhantomData};
 
 pub(crate) mod preprocessor;
+pub(crate) mod proof;
 pub(crate) mod prover;
 pub(crate) mod verifier;
 
 #[cfg(any(test, feature = "benchmark"))]
 pub mod util;
 
+pub use proof::HyperPlonkProof;
+
 #[derive(Clone, Debug)]
-pub struct HyperPlonk<Pcs>(PhantomData<Pcs>);
+pub struct HyperPlonk<
+    Pcs,
+    const LOOKUP_STRATEGY: usize = { LogUp as usize },
+    const PERMUTATION_STRATEGY: usize = { Plonk as usize },
+    const SUM_CHECK_STRATEGY: usize = { Evaluations as usize },
+>(PhantomData<Pcs>);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LookupStrategy {
+    /// LogUp with committed helper polynomials `h` checked in zero-check as
+    /// described in 2022/1530.
+    #[default]
+    LogUp = 0,
+    /// LogUp proven by fractional sum-check via GKR as described in 2023/1284
+    /// without committing `h`.
+    LogUpGkr = 1,
+}
+
+impl From<usize> for LookupStrategy {
+    fn from(strategy: usize) -> Self {
+        match strategy {
+            0 => LogUp,
+            1 => LogUpGkr,
+            _ => panic!("Invalid lookup strategy {strategy}, expected 0 (LogUp) or 1 (LogUpGkr)"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PermutationStrategy {
+    /// Grand product with committed polynomials `z` checked in zero-check as
+    /// in PLONK.
+    #[default]
+    Plonk = 0,
+    /// Grand product proven by layered product circuit via GKR without
+    /// committing `z`.
+    Gkr = 1,
+}
+
+impl From<usize> for PermutationStrategy {
+    fn from(strategy: usize) -> Self {
+        match strategy {
+            0 => Plonk,
+            1 => Gkr,
+            _ => panic!("Invalid permutation strategy {strategy}, expected 0 (Plonk) or 1 (Gkr)"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SumCheckStrategy {
+    /// Sum-check prover [`EvaluationsProver`] sending evaluations of round
+    /// polynomial, with Gruen's optimization on zero-check.
+    ///
+    /// [`EvaluationsProver`]: crate::piop::sum_check::classic::EvaluationsProver
+    #[default]
+    Evaluations = 0,
+    /// Sum-check prover [`CoefficientsProver`] sending coefficients of round
+    /// polynomial.
+    ///
+    /// [`CoefficientsProver`]: crate::piop::sum_check::classic::CoefficientsProver
+    Coefficients = 1,
+}
+
+impl From<usize> for SumCheckStrategy {
+    fn from(strategy: usize) -> Self {
+        match strategy {
+            0 => Evaluations,
+            1 => Coefficients,
+            _ => panic!(
+                "Invalid sum-check strategy {strategy}, expected 0 (Evaluations) or 1 (Coefficients)"
+            ),
+        }
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HyperPlonkProverParam<F, Pcs>
@@ -48,14 +132,22 @@ where
     pub(crate) num_instances: Vec<usize>,
     pub(crate) num_witness_polys: Vec<usize>,
     pub(crate) num_challenges: Vec<usize>,
+    pub(crate) lookup_strategy: LookupStrategy,
+    pub(crate) permutation_strategy: PermutationStrategy,
+    pub(crate) sum_check_strategy: SumCheckStrategy,
     pub(crate) lookups: Vec<Vec<(Expression<F>, Expression<F>)>>,
+    pub(crate) shuffles: Vec<Vec<(Expression<F>, Expression<F>)>>,
+    pub(crate) lasso_chunks: Vec<(Expression<F>, Expression<F>, Subtable)>,
+    pub(crate) lasso_pcs: BTreeMap<usize, (usize, Pcs::ProverParam)>,
     pub(crate) num_permutation_z_polys: usize,
     pub(crate) num_vars: usize,
     pub(crate) expression: Expression<F>,
     pub(crate) preprocess_polys: Vec<MultilinearPolynomial<F>>,
     pub(crate) preprocess_comms: Vec<Pcs::Commitment>,
+    pub(crate) sparse_polys: BTreeMap<usize, Vec<usize>>,
     pub(crate) permutation_polys: Vec<(usize, MultilinearPolynomial<F>)>,
     pub(crate) permutation_comms: Vec<Pcs::Commitment>,
+    pub(crate) vp_digest: F,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -68,15 +160,73 @@ where
     pub(crate) num_instances: Vec<usize>,
     pub(crate) num_witness_polys: Vec<usize>,
     pub(crate) num_challenges: Vec<usize>,
-    pub(crate) num_lookups: usize,
+    pub(crate) lookup_strategy: LookupStrategy,
+    pub(crate) permutation_strategy: PermutationStrategy,
+    pub(crate) sum_check_strategy: SumCheckStrategy,
+    pub(crate) lookups: Vec<Vec<(Expression<F>, Expression<F>)>>,
+    pub(crate) num_shuffles: usize,
+    pub(crate) lasso_chunks: Vec<(Expression<F>, Expression<F>, Subtable)>,
+    /// PCS param and number of variables of multiplicities of subtables,
+    /// keyed by number of variables of subtables.
+    pub(crate) lasso_pcs: BTreeMap<usize, (usize, Pcs::VerifierParam)>,
     pub(crate) num_permutation_z_polys: usize,
     pub(crate) num_vars: usize,
     pub(crate) expression: Expression<F>,
     pub(crate) preprocess_comms: Vec<Pcs::Commitment>,
     pub(crate) permutation_comms: Vec<(usize, Pcs::Commitment)>,
+    /// Digest of all other fields, absorbed into transcript before anything
+    /// else to bind the proof to the circuit.
+    pub(crate) vp_digest: F,
 }
 
-impl<F, Pcs> PlonkishBackend<F> for HyperPlonk<Pcs>
+#[cfg(any(test, feature = "benchmark"))]
+impl<F, Pcs> HyperPlonkProverParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    /// Drops sparse preprocess polynomials so sum-check iterates over the
+    /// whole hypercube, which is only for benchmark comparison.
+    pub fn without_sparse_polys(mut self) -> Self {
+        self.sparse_polys.clear();
+        self
+    }
+}
+
+impl<F, Pcs> HyperPlonkVerifierParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub(crate) fn num_lookup_h_polys(&self) -> usize {
+        match self.lookup_strategy {
+            LogUp => self.lookups.len(),
+            LogUpGkr => 0,
+        }
+    }
+
+    pub(crate) fn read_lasso_m_comms(
+        &self,
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+    ) -> Result<Vec<Pcs::Commitment>, Error> {
+        self.lasso_chunks
+            .iter()
+            .map(|(_, _, subtable)| {
+                let (_, pcs_vp) = &self.lasso_pcs[&subtable.num_vars()];
+                Pcs::read_commitment(pcs_vp, transcript)
+            })
+            .try_collect()
+    }
+}
+
+impl<
+        F,
+        Pcs,
+        const LOOKUP_STRATEGY: usize,
+        const PERMUTATION_STRATEGY: usize,
+        const SUM_CHECK_STRATEGY: usize,
+    > PlonkishBackend<F>
+    for HyperPlonk<Pcs, LOOKUP_STRATEGY, PERMUTATION_STRATEGY, SUM_CHECK_STRATEGY>
 where
     F: PrimeField + Hash + Serialize + DeserializeOwned,
     Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
@@ -89,11 +239,17 @@ where
         circuit_info: &PlonkishCircuitInfo<F>,
         rng: impl RngCore,
     ) -> Result<Pcs::Param, Error> {
-        assert!(circuit_info.is_well_formed());
+        circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
 
-        let num_vars = circuit_info.k;
+        let num_vars = chain![[circuit_info.k], lasso_num_chunks(circuit_info).into_keys()]
+            .max()
+            .unwrap();
         let poly_size = 1 << num_vars;
-        let batch_size = batch_size(circuit_info);
+        let batch_size = batch_size(
+            circuit_info,
+            LOOKUP_STRATEGY.into(),
+            PERMUTATION_STRATEGY.into(),
+        );
         Pcs::setup(poly_size, batch_size, rng)
     }
 
@@ -101,10 +257,17 @@ where
         param: &Pcs::Param,
         circuit_info: &PlonkishCircuitInfo<F>,
     ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
-        preprocess(param, circuit_info, |pp, polys| {
-            let comms = Pcs::batch_commit(pp, &polys)?;
-            Ok((polys, comms))
-        })
+        preprocess(
+            param,
+            circuit_info,
+            LOOKUP_STRATEGY.into(),
+            PERMUTATION_STRATEGY.into(),
+            SUM_CHECK_STRATEGY.into(),
+            |pp, polys| {
+                let comms = Pcs::batch_commit(pp, &polys)?;
+                Ok((polys, comms))
+            },
+        )
     }
 
     fn prove(
@@ -113,6 +276,8 @@ where
         transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
         _: impl RngCore,
     ) -> Result<(), Error> {
+        transcript.common_field_element(&pp.vp_digest)?;
+
         let instance_polys = {
             let instances = circuit.instances();
             for (num_instances, instances) in pp.num_instances.iter().zip_eq(instances) {
@@ -154,12 +319,19 @@ where
 
         let beta = transcript.squeeze_challenge();
 
-        let timer = start_timer(|| format!("lookup_compressed_polys-{}", pp.lookups.len()));
-        let lookup_compressed_polys = {
-            let max_lookup_width = pp.lookups.iter().map(Vec::len).max().unwrap_or_default();
-            let betas = powers(beta).take(max_lookup_width).collect_vec();
-            lookup_compressed_polys::<_, BinaryField>(&pp.lookups, &polys, &challenges, &betas)
-        };
+        let timer = start_timer(|| {
+            let num_lookups = pp.lookups.len() + pp.shuffles.len();
+            format!("lookup_compressed_polys-{num_lookups}")
+        });
+        let max_lookup_width = chain![&pp.lookups, &pp.shuffles]
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        let betas = powers(beta).take(max_lookup_width).collect_vec();
+        let [lookup_compressed_polys, shuffle_compressed_polys] =
+            [&pp.lookups, &pp.shuffles].map(|lookups| {
+                lookup_compressed_polys::<_, BinaryField>(lookups, &polys, &challenges, &betas)
+            });
         end_timer(timer);
 
         let timer = start_timer(|| format!("lookup_m_polys-{}", pp.lookups.len()));
@@ -168,26 +340,52 @@ where
 
         let lookup_m_comms = Pcs::batch_commit_and_write(&pp.pcs, &lookup_m_polys, transcript)?;
 
+        let timer = start_timer(|| format!("lasso_m_polys-{}", pp.lasso_chunks.len()));
+        let (lasso_chunk_polys, lasso_m_polys) =
+            lasso_m_polys::<_, BinaryField>(&pp.lasso_chunks, &pp.lasso_pcs, &polys, &challenges)?;
+        end_timer(timer);
+
+        let lasso_m_comms = izip!(&pp.lasso_chunks, &lasso_m_polys)
+            .map(|((_, _, subtable), lasso_m_poly)| {
+                let (_, pcs_pp) = &pp.lasso_pcs[&subtable.num_vars()];
+                Pcs::commit_and_write(pcs_pp, lasso_m_poly, transcript)
+            })
+            .try_collect::<_, Vec<_>, _>()?;
+
         // Round n+1
 
         let gamma = transcript.squeeze_challenge();
 
         let timer = start_timer(|| format!("lookup_h_polys-{}", pp.lookups.len()));
-        let lookup_h_polys = lookup_h_polys(&lookup_compressed_polys, &lookup_m_polys, &gamma);
+        let lookup_h_polys = match pp.lookup_strategy {
+            LogUp => lookup_h_polys(&lookup_compressed_polys, &lookup_m_polys, &gamma),
+            LogUpGkr => Vec::new(),
+        };
+        end_timer(timer);
+
+        let timer = start_timer(|| format!("shuffle_h_polys-{}", pp.shuffles.len()));
+        let shuffle_h_polys = shuffle_h_polys(&shuffle_compressed_polys, &gamma);
         end_timer(timer);
 
         let timer = start_timer(|| format!("permutation_z_polys-{}", pp.permutation_polys.len()));
-        let permutation_z_polys = permutation_z_polys::<_, BinaryField>(
-            pp.num_permutation_z_polys,
-            &pp.permutation_polys,
-            &polys,
-            &beta,
-            &gamma,
-        );
+        let permutation_z_polys = match pp.permutation_strategy {
+            Plonk => permutation_z_polys::<_, BinaryField>(
+                pp.num_permutation_z_polys,
+                &pp.permutation_polys,
+                &polys,
+                &beta,
+                &gamma,
+            ),
+            Gkr => Vec::new(),
+        };
         end_timer(timer);
 
-        let lookup_h_permutation_z_polys =
-            chain![lookup_h_polys.iter(), permutation_z_polys.iter()].collect_vec();
+        let lookup_h_permutation_z_polys = chain![
+            lookup_h_polys.iter(),
+            shuffle_h_polys.iter(),
+            permutation_z_polys.iter(),
+        ]
+        .collect_vec();
         let lookup_h_permutation_z_comms =
             Pcs::batch_commit_and_write(&pp.pcs, lookup_h_permutation_z_polys.clone(), transcript)?;
 
@@ -204,15 +402,78 @@ where
         ]
         .collect_vec();
         challenges.extend([beta, gamma, alpha]);
-        let (points, evals) = prove_zero_check(
+        let (mut points, mut evals) = prove_zero_check(
+            pp.sum_check_strategy,
             pp.num_instances.len(),
             &pp.expression,
             &polys,
+            &pp.sparse_polys,
             challenges,
             y,
             transcript,
         )?;
 
+        // Lookups by LogUp-GKR
+
+        if pp.lookup_strategy == LogUpGkr {
+            let timer = start_timer(|| format!("prove_logup_gkr-{}", pp.lookups.len()));
+            let (logup_gkr_points, logup_gkr_evals) = prove_logup_gkr(
+                pp.num_instances.len(),
+                &pp.lookups,
+                &lookup_compressed_polys,
+                &lookup_m_polys,
+                &polys,
+                &gamma,
+                pp.num_instances.len()
+                    + pp.preprocess_polys.len()
+                    + witness_polys.len()
+                    + pp.permutation_polys.len(),
+                points.len(),
+                transcript,
+            )?;
+            points.extend(logup_gkr_points);
+            evals.extend(logup_gkr_evals);
+            end_timer(timer);
+        }
+
+        // Permutation by GKR
+
+        if pp.permutation_strategy == Gkr {
+            let timer =
+                start_timer(|| format!("prove_permutation_gkr-{}", pp.permutation_polys.len()));
+            let (permutation_gkr_points, permutation_gkr_evals) = prove_permutation_gkr(
+                pp.num_instances.len(),
+                &pp.permutation_polys,
+                &polys,
+                &beta,
+                &gamma,
+                pp.num_instances.len() + pp.preprocess_polys.len() + witness_polys.len(),
+                points.len(),
+                transcript,
+            )?;
+            points.extend(permutation_gkr_points);
+            evals.extend(permutation_gkr_evals);
+            end_timer(timer);
+        }
+
+        // Decomposable lookups
+
+        let timer = start_timer(|| format!("prove_lasso-{}", pp.lasso_chunks.len()));
+        let (lasso_points, lasso_evals, lasso_m_evals) = prove_lasso(
+            pp.num_instances.len(),
+            &pp.lasso_chunks,
+            &lasso_chunk_polys,
+            &lasso_m_polys,
+            &polys,
+            &beta,
+            &gamma,
+            points.len(),
+            transcript,
+        )?;
+        points.extend(lasso_points);
+        evals.extend(lasso_evals);
+        end_timer(timer);
+
         // PCS open
 
         let dummy_comm = Pcs::Commitment::default();
@@ -229,6 +490,16 @@ where
         Pcs::batch_open(&pp.pcs, polys, comms, &points, &evals, transcript)?;
         end_timer(timer);
 
+        let timer = start_timer(|| format!("lasso_pcs_batch_open-{}", lasso_m_polys.len()));
+        let lasso_m_openings = lasso_m_openings(&pp.lasso_chunks, &pp.lasso_pcs, lasso_m_evals);
+        for (subtable_num_vars, (idxs, points, evals)) in lasso_m_openings {
+            let (_, pcs_pp) = &pp.lasso_pcs[&subtable_num_vars];
+            let polys = idxs.iter().map(|idx| &lasso_m_polys[*idx]);
+            let comms = idxs.iter().map(|idx| &lasso_m_comms[*idx]);
+            Pcs::batch_open(pcs_pp, polys, comms, &points, &evals, transcript)?;
+        }
+        end_timer(timer);
+
         Ok(())
     }
 
@@ -238,6 +509,92 @@ where
         transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
         _: impl RngCore,
     ) -> Result<(), Error> {
+        Self::verify_with(
+            vp,
+            instances,
+            transcript,
+            |pcs_vp, comms, points, evals, transcript| {
+                Pcs::batch_verify(pcs_vp, comms, points, evals, transcript)
+            },
+        )?;
+        Ok(())
+    }
+}
+
+impl<
+        Pcs,
+        const LOOKUP_STRATEGY: usize,
+        const PERMUTATION_STRATEGY: usize,
+        const SUM_CHECK_STRATEGY: usize,
+    > HyperPlonk<Pcs, LOOKUP_STRATEGY, PERMUTATION_STRATEGY, SUM_CHECK_STRATEGY>
+{
+    /// Same as [`PlonkishBackend::verify`] but returns accumulator of the
+    /// pairing check of PCS, which can be folded with others by
+    /// [`KzgAccumulator::fold`] and finally checked by [`DeferredKzg::decide`].
+    pub fn verify_deferred<M>(
+        vp: &HyperPlonkVerifierParam<M::Scalar, Pcs>,
+        instances: &[Vec<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error>
+    where
+        M: MultiMillerLoop,
+        M::Scalar: Hash + Serialize + DeserializeOwned,
+        Pcs: DeferredKzg<M, Polynomial = MultilinearPolynomial<M::Scalar>>,
+    {
+        let g2s = Pcs::accumulator_g2s(&vp.pcs);
+        let accumulators = Self::verify_with(
+            vp,
+            instances,
+            transcript,
+            |pcs_vp, comms, points, evals, transcript| {
+                let accumulator =
+                    Pcs::batch_verify_deferred(pcs_vp, comms, points, evals, transcript)?;
+                // Accumulators of multiplicities of subtables are padded by
+                // identity to be folded with the one of circuit, which only
+                // works when their `h_i` are prefix of the circuit's.
+                if !g2s.starts_with(&Pcs::accumulator_g2s(pcs_vp)) {
+                    return Err(Error::InvalidPcsParam(
+                        "Unfoldable accumulator of subtable multiplicities".to_string(),
+                    ));
+                }
+                let rhs = chain![
+                    accumulator.rhs().iter().copied(),
+                    iter::repeat(M::G1Affine::identity()),
+                ]
+                .take(g2s.len() - 1)
+                .collect();
+                Ok(KzgAccumulator::new(*accumulator.lhs(), rhs))
+            },
+        )?;
+        Ok(match accumulators.as_slice() {
+            [accumulator] => accumulator.clone(),
+            _ => KzgAccumulator::fold(&accumulators, &transcript.squeeze_challenge()),
+        })
+    }
+
+    /// Verifies everything but the PCS batch openings, which are delegated to
+    /// `pcs_batch_verify`, then returns its outputs of the circuit's opening
+    /// followed by ones of each group of subtable multiplicities.
+    #[allow(clippy::type_complexity)]
+    fn verify_with<F, Tr, T>(
+        vp: &HyperPlonkVerifierParam<F, Pcs>,
+        instances: &[Vec<F>],
+        transcript: &mut Tr,
+        mut pcs_batch_verify: impl FnMut(
+            &Pcs::VerifierParam,
+            Vec<&Pcs::Commitment>,
+            &[Vec<F>],
+            &[Evaluation<F>],
+            &mut Tr,
+        ) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error>
+    where
+        F: PrimeField + Hash + Serialize + DeserializeOwned,
+        Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+        Tr: TranscriptRead<Pcs::CommitmentChunk, F>,
+    {
+        transcript.common_field_element(&vp.vp_digest)?;
+
         for (num_instances, instances) in vp.num_instances.iter().zip_eq(instances) {
             assert_eq!(instances.len(), *num_instances);
             for instance in instances.iter() {
@@ -260,7 +617,9 @@ where
 
         let beta = transcript.squeeze_challenge();
 
-        let lookup_m_comms = Pcs::read_commitments(&vp.pcs, vp.num_lookups, transcript)?;
+        let lookup_m_comms = Pcs::read_commitments(&vp.pcs, vp.lookups.len(), transcript)?;
+
+        let lasso_m_comms = vp.read_lasso_m_comms(transcript)?;
 
         // Round n+1
 
@@ -268,7 +627,7 @@ where
 
         let lookup_h_permutation_z_comms = Pcs::read_commitments(
             &vp.pcs,
-            vp.num_lookups + vp.num_permutation_z_polys,
+            vp.num_lookup_h_polys() + vp.num_shuffles + vp.num_permutation_z_polys,
             transcript,
         )?;
 
@@ -278,7 +637,8 @@ where
         let y = transcript.squeeze_challenges(vp.num_vars);
 
         challenges.extend([beta, gamma, alpha]);
-        let (points, evals) = verify_zero_check(
+        let (mut points, mut evals) = verify_zero_check(
+            vp.sum_check_strategy,
             vp.num_vars,
             &vp.expression,
             instances,
@@ -287,8 +647,6 @@ where
             transcript,
         )?;
 
-        // PCS verify
-
         let dummy_comm = Pcs::Commitment::default();
         let comms = chain![
             iter::repeat(&dummy_comm).take(vp.num_instances.len()),
@@ -299,13 +657,90 @@ where
             &lookup_h_permutation_z_comms,
         ]
         .collect_vec();
-        Pcs::batch_verify(&vp.pcs, comms, &points, &evals, transcript)?;
 
-        Ok(())
+        // Lookups by LogUp-GKR
+
+        if vp.lookup_strategy == LogUpGkr {
+            let (logup_gkr_points, logup_gkr_evals) = verify_logup_gkr(
+                vp.num_vars,
+                &vp.lookups,
+                instances,
+                &challenges,
+                &beta,
+                &gamma,
+                vp.num_instances.len()
+                    + vp.preprocess_comms.len()
+                    + witness_comms.len()
+                    + vp.permutation_comms.len(),
+                points.len(),
+                transcript,
+            )?;
+            points.extend(logup_gkr_points);
+            evals.extend(logup_gkr_evals);
+        }
+
+        // Permutation by GKR
+
+        if vp.permutation_strategy == Gkr {
+            let permutation_polys = vp
+                .permutation_comms
+                .iter()
+                .map(|(poly, _)| *poly)
+                .collect_vec();
+            let (permutation_gkr_points, permutation_gkr_evals) = verify_permutation_gkr(
+                vp.num_vars,
+                &permutation_polys,
+                instances,
+                &beta,
+                &gamma,
+                vp.num_instances.len() + vp.preprocess_comms.len() + witness_comms.len(),
+                points.len(),
+                transcript,
+            )?;
+            points.extend(permutation_gkr_points);
+            evals.extend(permutation_gkr_evals);
+        }
+
+        // Decomposable lookups
+
+        let (lasso_points, lasso_evals, lasso_m_evals) = verify_lasso(
+            vp.num_vars,
+            &vp.lasso_chunks,
+            instances,
+            &challenges,
+            &beta,
+            &gamma,
+            points.len(),
+            transcript,
+        )?;
+        points.extend(lasso_points);
+        evals.extend(lasso_evals);
+
+        // PCS verify
+
+        let mut outputs = vec![pcs_batch_verify(
+            &vp.pcs, comms, &points, &evals, transcript,
+        )?];
+        let lasso_m_openings = lasso_m_openings(&vp.lasso_chunks, &vp.lasso_pcs, lasso_m_evals);
+        for (subtable_num_vars, (idxs, points, evals)) in lasso_m_openings {
+            let (_, pcs_vp) = &vp.lasso_pcs[&subtable_num_vars];
+            let comms = idxs.iter().map(|idx| &lasso_m_comms[*idx]).collect();
+            outputs.push(pcs_batch_verify(
+                pcs_vp, comms, &points, &evals, transcript,
+            )?);
+        }
+        Ok(outputs)
     }
 }
 
-impl<Pcs> WitnessEncoding for HyperPlonk<Pcs> {
+impl<
+        Pcs,
+        const LOOKUP_STRATEGY: usize,
+        const PERMUTATION_STRATEGY: usize,
+        const SUM_CHECK_STRATEGY: usize,
+    > WitnessEncoding
+    for HyperPlonk<Pcs, LOOKUP_STRATEGY, PERMUTATION_STRATEGY, SUM_CHECK_STRATEGY>
+{
     fn row_mapping(k: usize) -> Vec<usize> {
         BinaryField::new(k).usable_indices()
     }
@@ -316,27 +751,44 @@ mod test {
     use crate::{
         backend::{
             hyperplonk::{
-                util::{rand_vanilla_plonk_circuit, rand_vanilla_plonk_w_lookup_circuit},
-                HyperPlonk,
+                util::{
+                    rand_vanilla_plonk_circuit, rand_vanilla_plonk_w_decomposable_lookup_circuit,
+                    rand_vanilla_plonk_w_dynamic_lookup_circuit,
+                    rand_vanilla_plonk_w_lookup_circuit, rand_vanilla_plonk_w_shuffle_circuit,
+                },
+                HyperPlonk, HyperPlonkProof,
+                LookupStrategy::{LogUp, LogUpGkr},
+                PermutationStrategy::{Gkr, Plonk},
+                SumCheckStrategy::Coefficients,
             },
             test::run_plonkish_backend,
+            PlonkishBackend, PlonkishCircuit,
         },
         pcs::{
             multilinear::{
-                Gemini, MultilinearBrakedown, MultilinearHyrax, MultilinearIpa, MultilinearKzg,
-                Zeromorph,
+                Gemini, MultilinearBasefold, MultilinearBrakedown, MultilinearHyrax,
+                MultilinearIpa, MultilinearKzg, MultilinearLigero, Zeromorph,
             },
-            univariate::UnivariateKzg,
+            univariate::{ShplonkBatch, UnivariateFri, UnivariateKzg},
+            DeferredKzg, KzgAccumulator,
         },
+        poly::multilinear::MultilinearPolynomial,
         util::{
-            code::BrakedownSpec6, expression::rotate::BinaryField, hash::Keccak256,
-            test::seeded_std_rng, transcript::Keccak256Transcript,
+            arithmetic::Field,
+            code::BrakedownSpec6,
+            expression::rotate::BinaryField,
+            hash::{Keccak256, PoseidonHash},
+            test::seeded_std_rng,
+            transcript::{InMemoryTranscript, Keccak256Transcript, PoseidonTranscript},
+            Itertools,
         },
     };
     use halo2_curves::{
         bn256::{self, Bn256},
         grumpkin,
     };
+    use rand::rngs::OsRng;
+    use std::{io::Cursor, iter};
 
     macro_rules! tests {
         ($suffix:ident, $pcs:ty, $num_vars_range:expr) => {
@@ -348,12 +800,75 @@ mod test {
                     });
                 }
 
+                #[test]
+                fn [<vanilla_plonk_w_ $suffix _w_poseidon_transcript>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs>, PoseidonTranscript<_, _>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_permutation_gkr_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs, { LogUp as usize }, { Gkr as usize }>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
                 #[test]
                 fn [<vanilla_plonk_w_lookup_w_ $suffix>]() {
                     run_plonkish_backend::<_, HyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
                         rand_vanilla_plonk_w_lookup_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
                     });
                 }
+
+                #[test]
+                fn [<vanilla_plonk_w_lookup_w_logup_gkr_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs, { LogUpGkr as usize }>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_lookup_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_lookup_w_logup_gkr_w_permutation_gkr_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs, { LogUpGkr as usize }, { Gkr as usize }>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_lookup_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_lookup_w_coefficients_sum_check_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs, { LogUp as usize }, { Plonk as usize }, { Coefficients as usize }>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_lookup_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_dynamic_lookup_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_dynamic_lookup_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_dynamic_lookup_w_logup_gkr_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs, { LogUpGkr as usize }>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_dynamic_lookup_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_decomposable_lookup_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_decomposable_lookup_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_shuffle_w_ $suffix>]() {
+                    run_plonkish_backend::<_, HyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_shuffle_circuit::<_, BinaryField>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
             }
         };
         ($suffix:ident, $pcs:ty) => {
@@ -362,9 +877,255 @@ mod test {
     }
 
     tests!(brakedown, MultilinearBrakedown<bn256::Fr, Keccak256, BrakedownSpec6>);
+    tests!(
+        brakedown_poseidon,
+        MultilinearBrakedown<bn256::Fr, PoseidonHash<bn256::Fr>, BrakedownSpec6>
+    );
+    tests!(ligero, MultilinearLigero<bn256::Fr, Keccak256>);
+    tests!(basefold, MultilinearBasefold<bn256::Fr, Keccak256>);
     tests!(hyrax, MultilinearHyrax<grumpkin::G1Affine>, 5..16);
     tests!(ipa, MultilinearIpa<grumpkin::G1Affine>);
     tests!(kzg, MultilinearKzg<Bn256>);
     tests!(gemini_kzg, Gemini<UnivariateKzg<Bn256>>);
+    tests!(
+        gemini_kzg_shplonk,
+        Gemini<UnivariateKzg<Bn256, ShplonkBatch>>
+    );
     tests!(zeromorph_kzg, Zeromorph<UnivariateKzg<Bn256>>);
+    tests!(gemini_fri, Gemini<UnivariateFri<bn256::Fr, Keccak256>>);
+    tests!(
+        zeromorph_fri,
+        Zeromorph<UnivariateFri<bn256::Fr, Keccak256>>
+    );
+
+    #[test]
+    fn vp_digest_binding() {
+        type Pb = HyperPlonk<MultilinearKzg<Bn256>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_circuit::<bn256::Fr, BinaryField>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+        let other_vp = {
+            let mut vp = vp.clone();
+            vp.vp_digest += bn256::Fr::ONE;
+            vp
+        };
+
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+        for (vp, expected) in [(&vp, true), (&other_vp, false)] {
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            let result = Pb::verify(vp, circuit.instances(), &mut transcript, seeded_std_rng());
+            assert_eq!(result.is_ok(), expected);
+        }
+    }
+
+    fn run_verify_deferred<Pcs>()
+    where
+        Pcs: DeferredKzg<
+            Bn256,
+            Polynomial = MultilinearPolynomial<bn256::Fr>,
+            CommitmentChunk = bn256::G1Affine,
+        >,
+    {
+        let num_vars = 5;
+        let (circuit_info, _) = rand_vanilla_plonk_circuit::<bn256::Fr, BinaryField>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = HyperPlonk::<Pcs>::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = HyperPlonk::<Pcs>::preprocess(&param, &circuit_info).unwrap();
+
+        let accumulators = iter::repeat_with(|| {
+            let (_, circuit) = rand_vanilla_plonk_circuit::<bn256::Fr, BinaryField>(
+                num_vars,
+                seeded_std_rng(),
+                OsRng,
+            );
+            let proof = {
+                let mut transcript = Keccak256Transcript::new(());
+                HyperPlonk::<Pcs>::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+                transcript.into_proof()
+            };
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            HyperPlonk::<Pcs>::verify_deferred::<Bn256>(&vp, circuit.instances(), &mut transcript)
+                .unwrap()
+        })
+        .take(3)
+        .collect_vec();
+        for accumulator in accumulators.iter() {
+            assert_eq!(Pcs::decide(&vp.pcs, accumulator), Ok(()));
+        }
+
+        let accumulator = KzgAccumulator::fold(&accumulators, &bn256::Fr::random(OsRng));
+        assert_eq!(Pcs::decide(&vp.pcs, &accumulator), Ok(()));
+    }
+
+    #[test]
+    fn verify_deferred_w_kzg() {
+        run_verify_deferred::<MultilinearKzg<Bn256>>();
+    }
+
+    #[test]
+    fn verify_deferred_w_gemini_kzg() {
+        run_verify_deferred::<Gemini<UnivariateKzg<Bn256>>>();
+    }
+
+    #[test]
+    fn verify_deferred_w_zeromorph_kzg() {
+        run_verify_deferred::<Zeromorph<UnivariateKzg<Bn256>>>();
+    }
+
+    #[test]
+    fn proof_bytes_roundtrip() {
+        type Pb = HyperPlonk<MultilinearKzg<Bn256>>;
+        type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_w_lookup_circuit::<bn256::Fr, BinaryField>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+        let proof = {
+            let mut transcript = T::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+
+        let typed = HyperPlonkProof::from_bytes::<T>(&vp, (), &proof).unwrap();
+        assert_eq!(typed.witness_comms().len(), 3);
+        assert_eq!(typed.lookup_m_comms().len(), 1);
+        assert_eq!(typed.lookup_h_comms().len(), 1);
+        assert_eq!(typed.sum_check_msgs().len(), num_vars);
+        assert!(!typed.pcs_proof().is_empty());
+
+        let typed: HyperPlonkProof<_, MultilinearKzg<Bn256>> =
+            bincode::deserialize(&bincode::serialize(&typed).unwrap()).unwrap();
+        assert_eq!(typed.to_bytes::<T>(()).unwrap(), proof);
+    }
+
+    #[test]
+    fn proof_bytes_roundtrip_w_logup_gkr() {
+        type Pb = HyperPlonk<MultilinearKzg<Bn256>, { LogUpGkr as usize }>;
+        type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_w_lookup_circuit::<bn256::Fr, BinaryField>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+        let proof = {
+            let mut transcript = T::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+
+        let typed = HyperPlonkProof::from_bytes::<T>(&vp, (), &proof).unwrap();
+        assert_eq!(typed.lookup_m_comms().len(), 1);
+        assert!(typed.lookup_h_comms().is_empty());
+        assert!(!typed.logup_gkr_msgs().is_empty());
+        assert!(!typed.pcs_proof().is_empty());
+        assert_eq!(typed.to_bytes::<T>(()).unwrap(), proof);
+    }
+
+    #[test]
+    fn proof_bytes_roundtrip_w_permutation_gkr() {
+        type Pb = HyperPlonk<MultilinearKzg<Bn256>, { LogUp as usize }, { Gkr as usize }>;
+        type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_circuit::<bn256::Fr, BinaryField>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+        let proof = {
+            let mut transcript = T::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+
+        let typed = HyperPlonkProof::from_bytes::<T>(&vp, (), &proof).unwrap();
+        assert!(typed.permutation_z_comms().is_empty());
+        assert!(!typed.permutation_gkr_msgs().is_empty());
+        assert!(!typed.pcs_proof().is_empty());
+        assert_eq!(typed.to_bytes::<T>(()).unwrap(), proof);
+    }
+
+    #[test]
+    fn proof_bytes_roundtrip_w_coefficients_sum_check() {
+        type Pb = HyperPlonk<
+            MultilinearKzg<Bn256>,
+            { LogUp as usize },
+            { Plonk as usize },
+            { Coefficients as usize },
+        >;
+        type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_w_lookup_circuit::<bn256::Fr, BinaryField>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+        let proof = {
+            let mut transcript = T::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+
+        let typed = HyperPlonkProof::from_bytes::<T>(&vp, (), &proof).unwrap();
+        assert_eq!(typed.sum_check_msgs().len(), num_vars);
+        assert!(typed
+            .sum_check_msgs()
+            .iter()
+            .all(|msg| msg.len() == vp.expression.degree() + 1));
+        assert!(!typed.pcs_proof().is_empty());
+        assert_eq!(typed.to_bytes::<T>(()).unwrap(), proof);
+    }
+
+    #[test]
+    fn proof_bytes_roundtrip_w_decomposable_lookup() {
+        type Pb = HyperPlonk<MultilinearKzg<Bn256>>;
+        type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_w_decomposable_lookup_circuit::<
+            bn256::Fr,
+            BinaryField,
+        >(num_vars, seeded_std_rng(), seeded_std_rng());
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+        let proof = {
+            let mut transcript = T::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+
+        let typed = HyperPlonkProof::from_bytes::<T>(&vp, (), &proof).unwrap();
+        assert_eq!(typed.witness_comms().len(), 10);
+        assert_eq!(typed.lasso_m_comms().len(), 2);
+        assert!(!typed.lasso_msgs().is_empty());
+        assert!(!typed.pcs_proof().is_empty());
+        assert_eq!(typed.to_bytes::<T>(()).unwrap(), proof);
+    }
 }
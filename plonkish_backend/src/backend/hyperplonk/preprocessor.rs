@@ -1,36 +1,63 @@
 use crate::{
     backend::{
-        hyperplonk::{HyperPlonkProverParam, HyperPlonkVerifierParam},
-        PlonkishCircuitInfo,
+        hyperplonk::{
+            HyperPlonkProverParam, HyperPlonkVerifierParam, LookupStrategy,
+            LookupStrategy::{LogUp, LogUpGkr},
+            PermutationStrategy,
+            PermutationStrategy::{Gkr, Plonk},
+            SumCheckStrategy,
+        },
+        DecomposableLookup, PlonkishCircuitInfo, Subtable,
     },
     pcs::PolynomialCommitmentScheme,
     poly::multilinear::MultilinearPolynomial,
     util::{
-        arithmetic::{div_ceil, steps, PrimeField},
+        arithmetic::{div_ceil, fe_mod_from_le_bytes, steps, PrimeField},
         chain,
         expression::{Expression, Query, Rotation},
-        Itertools,
+        hash::{Hash, Keccak256},
+        izip, Itertools, Serialize,
     },
     Error,
 };
-use std::{array, borrow::Cow, mem};
+use std::{array, borrow::Cow, collections::BTreeMap, mem};
 
-pub(crate) fn batch_size<F: PrimeField>(circuit_info: &PlonkishCircuitInfo<F>) -> usize {
-    let num_lookups = circuit_info.lookups.len();
+pub(crate) fn batch_size<F: PrimeField>(
+    circuit_info: &PlonkishCircuitInfo<F>,
+    lookup_strategy: LookupStrategy,
+    permutation_strategy: PermutationStrategy,
+) -> usize {
+    let num_lookups = circuit_info.vector_lookups().len();
+    let num_lookup_h_polys = match lookup_strategy {
+        LogUp => num_lookups,
+        LogUpGkr => 0,
+    };
+    let num_shuffles = circuit_info.shuffles.len();
     let num_permutation_polys = circuit_info.permutation_polys().len();
+    let num_permutation_z_polys = match permutation_strategy {
+        Plonk => div_ceil(
+            num_permutation_polys,
+            max_degree(circuit_info, lookup_strategy, None) - 1,
+        ),
+        Gkr => 0,
+    };
+    let num_lasso_chunks = lasso_chunks(circuit_info).len();
     chain![
         [circuit_info.preprocess_polys.len() + circuit_info.permutation_polys().len()],
         circuit_info.num_witness_polys.clone(),
-        [num_lookups],
-        [num_lookups + div_ceil(num_permutation_polys, max_degree(circuit_info, None) - 1)],
+        [num_lookups + num_lasso_chunks],
+        [num_lookup_h_polys + num_shuffles + num_permutation_z_polys],
     ]
     .sum()
 }
 
 #[allow(clippy::type_complexity)]
-pub(crate) fn preprocess<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
+pub(crate) fn preprocess<F: PrimeField + Serialize, Pcs: PolynomialCommitmentScheme<F>>(
     param: &Pcs::Param,
     circuit_info: &PlonkishCircuitInfo<F>,
+    lookup_strategy: LookupStrategy,
+    permutation_strategy: PermutationStrategy,
+    sum_check_strategy: SumCheckStrategy,
     batch_commit: impl Fn(
         &Pcs::ProverParam,
         Vec<MultilinearPolynomial<F>>,
@@ -42,13 +69,35 @@ pub(crate) fn preprocess<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
     ),
     Error,
 > {
-    assert!(circuit_info.is_well_formed());
+    circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
 
     let num_vars = circuit_info.k;
     let poly_size = 1 << num_vars;
-    let batch_size = batch_size(circuit_info);
+    let batch_size = batch_size(circuit_info, lookup_strategy, permutation_strategy);
     let (pcs_pp, pcs_vp) = Pcs::trim(param, poly_size, batch_size)?;
 
+    // Trim PCS param for multiplicities of each size of subtables, or fallback
+    // to pad them to circuit size if PCS param can't be trimmed to subtable size
+    let (lasso_pp, lasso_vp) = lasso_num_chunks(circuit_info)
+        .into_iter()
+        .map(|(subtable_num_vars, num_chunks)| {
+            let (m_num_vars, (pp, vp)) = match Pcs::trim(param, 1 << subtable_num_vars, num_chunks)
+            {
+                Ok(params) => (subtable_num_vars, params),
+                Err(_) if subtable_num_vars < num_vars => {
+                    (num_vars, Pcs::trim(param, poly_size, num_chunks)?)
+                }
+                Err(err) => return Err(err),
+            };
+            Ok((
+                (subtable_num_vars, (m_num_vars, pp)),
+                (subtable_num_vars, (m_num_vars, vp)),
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?
+        .into_iter()
+        .unzip::<_, _, BTreeMap<_, _>, BTreeMap<_, _>>();
+
     // Compute preprocesses comms
     let preprocess_polys = circuit_info
         .preprocess_polys
@@ -57,6 +106,7 @@ pub(crate) fn preprocess<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
         .map(MultilinearPolynomial::new)
         .collect_vec();
     let (preprocess_polys, preprocess_comms) = batch_commit(&pcs_pp, preprocess_polys)?;
+    let sparse_polys = sparse_polys(circuit_info.num_instances.len(), &preprocess_polys);
 
     // Compute permutation polys and comms
     let permutation_polys = permutation_polys(
@@ -67,13 +117,20 @@ pub(crate) fn preprocess<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
     let (permutation_polys, permutation_comms) = batch_commit(&pcs_pp, permutation_polys)?;
 
     // Compose expression
-    let (num_permutation_z_polys, expression) = compose(circuit_info);
-    let vp = HyperPlonkVerifierParam {
+    let (num_permutation_z_polys, expression) =
+        compose(circuit_info, lookup_strategy, permutation_strategy);
+    let mut vp = HyperPlonkVerifierParam {
         pcs: pcs_vp,
         num_instances: circuit_info.num_instances.clone(),
         num_witness_polys: circuit_info.num_witness_polys.clone(),
         num_challenges: circuit_info.num_challenges.clone(),
-        num_lookups: circuit_info.lookups.len(),
+        lookup_strategy,
+        permutation_strategy,
+        sum_check_strategy,
+        lookups: circuit_info.vector_lookups(),
+        num_shuffles: circuit_info.shuffles.len(),
+        lasso_chunks: lasso_chunks(circuit_info),
+        lasso_pcs: lasso_vp,
         num_permutation_z_polys,
         num_vars,
         expression: expression.clone(),
@@ -83,45 +140,85 @@ pub(crate) fn preprocess<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
             .into_iter()
             .zip(permutation_comms.clone())
             .collect(),
+        vp_digest: F::ZERO,
     };
+    vp.vp_digest = vp_digest(&vp);
     let pp = HyperPlonkProverParam {
         pcs: pcs_pp,
         num_instances: circuit_info.num_instances.clone(),
         num_witness_polys: circuit_info.num_witness_polys.clone(),
         num_challenges: circuit_info.num_challenges.clone(),
-        lookups: circuit_info.lookups.clone(),
+        lookup_strategy,
+        permutation_strategy,
+        sum_check_strategy,
+        lookups: circuit_info.vector_lookups(),
+        shuffles: circuit_info.shuffles.clone(),
+        lasso_chunks: lasso_chunks(circuit_info),
+        lasso_pcs: lasso_pp,
         num_permutation_z_polys,
         num_vars,
         expression,
         preprocess_polys,
         preprocess_comms,
+        sparse_polys,
         permutation_polys: circuit_info
             .permutation_polys()
             .into_iter()
             .zip(permutation_polys)
             .collect(),
         permutation_comms,
+        vp_digest: vp.vp_digest,
     };
     Ok((pp, vp))
 }
 
+/// Returns indices of non-zero evaluations of preprocess polynomials which
+/// are non-zero on at most a quarter of the hypercube, keyed by polynomial
+/// index.
+pub(crate) fn sparse_polys<F: PrimeField>(
+    preprocess_offset: usize,
+    preprocess_polys: &[MultilinearPolynomial<F>],
+) -> BTreeMap<usize, Vec<usize>> {
+    izip!(preprocess_offset.., preprocess_polys)
+        .filter_map(|(idx, poly)| {
+            let bs = (0..poly.evals().len())
+                .filter(|b| poly[*b] != F::ZERO)
+                .collect_vec();
+            (bs.len() <= poly.evals().len() >> 2).then_some((idx, bs))
+        })
+        .collect()
+}
+
+/// Returns digest of serialized verifier param, which should be computed
+/// with the digest field itself set to zero.
+pub(crate) fn vp_digest<F: PrimeField>(vp: &impl Serialize) -> F {
+    let bytes = bincode::serialize(vp).unwrap();
+    fe_mod_from_le_bytes(Keccak256::digest(bytes))
+}
+
 pub(crate) fn compose<F: PrimeField>(
     circuit_info: &PlonkishCircuitInfo<F>,
+    lookup_strategy: LookupStrategy,
+    permutation_strategy: PermutationStrategy,
 ) -> (usize, Expression<F>) {
     let challenge_offset = circuit_info.num_challenges.iter().sum::<usize>();
     let [beta, gamma, alpha] =
         &array::from_fn(|idx| Expression::<F>::Challenge(challenge_offset + idx));
 
-    let (lookup_constraints, lookup_zero_checks) = lookup_constraints(circuit_info, beta, gamma);
+    let (lookup_constraints, lookup_zero_checks) =
+        lookup_constraints(circuit_info, lookup_strategy, beta, gamma);
 
-    let max_degree = max_degree(circuit_info, Some(&lookup_constraints));
-    let (num_permutation_z_polys, permutation_constraints) = permutation_constraints(
-        circuit_info,
-        max_degree,
-        beta,
-        gamma,
-        2 * circuit_info.lookups.len(),
-    );
+    let max_degree = max_degree(circuit_info, lookup_strategy, Some(&lookup_constraints));
+    let (num_permutation_z_polys, permutation_constraints) = match permutation_strategy {
+        Plonk => permutation_constraints(
+            circuit_info,
+            max_degree,
+            beta,
+            gamma,
+            num_builtin_witness_polys(circuit_info, lookup_strategy),
+        ),
+        Gkr => (0, Vec::new()),
+    };
 
     let expression = {
         let constraints = chain![
@@ -141,13 +238,22 @@ pub(crate) fn compose<F: PrimeField>(
     (num_permutation_z_polys, expression)
 }
 
-pub(super) fn max_degree<F: PrimeField>(
+pub(crate) fn max_degree<F: PrimeField>(
     circuit_info: &PlonkishCircuitInfo<F>,
+    lookup_strategy: LookupStrategy,
     lookup_constraints: Option<&[Expression<F>]>,
 ) -> usize {
     let lookup_constraints = lookup_constraints.map(Cow::Borrowed).unwrap_or_else(|| {
         let dummy_challenge = Expression::zero();
-        Cow::Owned(self::lookup_constraints(circuit_info, &dummy_challenge, &dummy_challenge).0)
+        Cow::Owned(
+            self::lookup_constraints(
+                circuit_info,
+                lookup_strategy,
+                &dummy_challenge,
+                &dummy_challenge,
+            )
+            .0,
+        )
     });
     chain![
         circuit_info.constraints.iter().map(Expression::degree),
@@ -159,38 +265,104 @@ pub(super) fn max_degree<F: PrimeField>(
     .unwrap()
 }
 
-pub(super) fn lookup_constraints<F: PrimeField>(
+/// Returns chunks of all decomposable lookups, which are proven by
+/// fractional sum-checks separately from the zero-check.
+pub(crate) fn lasso_chunks<F: Clone>(
     circuit_info: &PlonkishCircuitInfo<F>,
+) -> Vec<(Expression<F>, Expression<F>, Subtable)> {
+    circuit_info
+        .decomposable_lookups()
+        .into_iter()
+        .flat_map(|lookup| lookup.chunks)
+        .collect()
+}
+
+/// Returns number of chunks of decomposable lookups keyed by number of
+/// variables of their subtables.
+pub(crate) fn lasso_num_chunks<F: Clone>(
+    circuit_info: &PlonkishCircuitInfo<F>,
+) -> BTreeMap<usize, usize> {
+    lasso_chunks(circuit_info)
+        .iter()
+        .map(|(_, _, subtable)| subtable.num_vars())
+        .counts()
+        .into_iter()
+        .collect()
+}
+
+/// Returns constraints and polynomials to be sum-checked to zero of lookups
+/// and shuffles, where a shuffle is a lookup with multiplicities fixed to one,
+/// so it only has the `h` polynomial but no `m` polynomial. Decomposable
+/// lookups only contribute the constraint of combining function. With
+/// [`LogUpGkr`], lookups are proven by fractional sum-check instead, so they
+/// have neither constraint nor `h` polynomial.
+pub(crate) fn lookup_constraints<F: PrimeField>(
+    circuit_info: &PlonkishCircuitInfo<F>,
+    lookup_strategy: LookupStrategy,
     beta: &Expression<F>,
     gamma: &Expression<F>,
 ) -> (Vec<Expression<F>>, Vec<Expression<F>>) {
     let m_offset = circuit_info.num_poly() + circuit_info.permutation_polys().len();
-    let h_offset = m_offset + circuit_info.lookups.len();
-    let constraints = circuit_info
-        .lookups
-        .iter()
-        .zip(m_offset..)
-        .zip(h_offset..)
-        .flat_map(|((lookup, m), h)| {
-            let [m, h] = &[m, h]
-                .map(|poly| Query::new(poly, Rotation::cur()))
-                .map(Expression::<F>::Polynomial);
-            let (inputs, tables) = lookup
-                .iter()
-                .map(|(input, table)| (input, table))
-                .unzip::<_, _, Vec<_>, Vec<_>>();
-            let input = &Expression::distribute_powers(inputs, beta);
-            let table = &Expression::distribute_powers(tables, beta);
-            [h * (input + gamma) * (table + gamma) - (table + gamma) + m * (input + gamma)]
-        })
-        .collect_vec();
+    let h_offset = m_offset + circuit_info.vector_lookups().len();
+    let lookups = match lookup_strategy {
+        LogUp => circuit_info.vector_lookups(),
+        LogUpGkr => Vec::new(),
+    };
+    let compress = |lookup: &[(Expression<F>, Expression<F>)]| {
+        let (inputs, tables) = lookup
+            .iter()
+            .map(|(input, table)| (input, table))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        [inputs, tables].map(|exprs| Expression::distribute_powers(exprs, beta))
+    };
+    let constraints = chain![
+        circuit_info
+            .decomposable_lookups()
+            .iter()
+            .map(DecomposableLookup::constraint),
+        lookups
+            .iter()
+            .zip(m_offset..)
+            .zip(h_offset..)
+            .map(|((lookup, m), h)| {
+                let [m, h] = &[m, h]
+                    .map(|poly| Query::new(poly, Rotation::cur()))
+                    .map(Expression::<F>::Polynomial);
+                let [input, table] = &compress(lookup);
+                h * (input + gamma) * (table + gamma) - (table + gamma) + m * (input + gamma)
+            }),
+        circuit_info
+            .shuffles
+            .iter()
+            .zip(h_offset + lookups.len()..)
+            .map(|(shuffle, h)| {
+                let h = &Expression::<F>::Polynomial(Query::new(h, Rotation::cur()));
+                let [input, shuffle] = &compress(shuffle);
+                h * (input + gamma) * (shuffle + gamma) - (shuffle + gamma) + (input + gamma)
+            }),
+    ]
+    .collect_vec();
     let sum_check = (h_offset..)
-        .take(circuit_info.lookups.len())
+        .take(lookups.len() + circuit_info.shuffles.len())
         .map(|h| Query::new(h, Rotation::cur()).into())
         .collect_vec();
     (constraints, sum_check)
 }
 
+/// Returns number of `m` and `h` polynomials of lookups and shuffles, which
+/// are placed before permutation `z` polynomials.
+pub(crate) fn num_builtin_witness_polys<F>(
+    circuit_info: &PlonkishCircuitInfo<F>,
+    lookup_strategy: LookupStrategy,
+) -> usize {
+    let num_lookups = circuit_info.vector_lookups().len();
+    let num_lookup_h_polys = match lookup_strategy {
+        LogUp => num_lookups,
+        LogUpGkr => 0,
+    };
+    num_lookups + num_lookup_h_polys + circuit_info.shuffles.len()
+}
+
 pub(crate) fn permutation_constraints<F: PrimeField>(
     circuit_info: &PlonkishCircuitInfo<F>,
     max_degree: usize,
@@ -0,0 +1,323 @@
+use crate::{
+    backend::hyperplonk::{
+        verifier::{lasso_expression, lookup_expression, pcs_query, permutation_expression},
+        HyperPlonkVerifierParam,
+        LookupStrategy::LogUpGkr,
+        PermutationStrategy::Gkr,
+        SumCheckStrategy::{self, Coefficients},
+    },
+    pcs::PolynomialCommitmentScheme,
+    piop::sum_check::classic::is_eq_xy_factored,
+    util::{
+        arithmetic::PrimeField,
+        chain,
+        expression::Expression,
+        transcript::{
+            FieldTranscriptRead, FieldTranscriptWrite, InMemoryTranscript, TranscriptRead,
+            TranscriptWrite,
+        },
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+
+/// Proof of [`HyperPlonk`] split into named components, which converts
+/// losslessly from and to the bytes of an [`InMemoryTranscript`].
+///
+/// [`HyperPlonk`]: crate::backend::hyperplonk::HyperPlonk
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct HyperPlonkProof<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub(crate) witness_comms: Vec<Pcs::Commitment>,
+    pub(crate) lookup_m_comms: Vec<Pcs::Commitment>,
+    pub(crate) lasso_m_comms: Vec<Pcs::Commitment>,
+    pub(crate) lookup_h_comms: Vec<Pcs::Commitment>,
+    pub(crate) shuffle_h_comms: Vec<Pcs::Commitment>,
+    pub(crate) permutation_z_comms: Vec<Pcs::Commitment>,
+    pub(crate) sum_check_msgs: Vec<Vec<F>>,
+    pub(crate) evals: Vec<Vec<F>>,
+    pub(crate) logup_gkr_msgs: Vec<F>,
+    pub(crate) permutation_gkr_msgs: Vec<F>,
+    pub(crate) lasso_msgs: Vec<F>,
+    pub(crate) pcs_proof: Vec<u8>,
+}
+
+impl<F, Pcs> HyperPlonkProof<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub fn witness_comms(&self) -> &[Pcs::Commitment] {
+        &self.witness_comms
+    }
+
+    pub fn lookup_m_comms(&self) -> &[Pcs::Commitment] {
+        &self.lookup_m_comms
+    }
+
+    pub fn lasso_m_comms(&self) -> &[Pcs::Commitment] {
+        &self.lasso_m_comms
+    }
+
+    pub fn lookup_h_comms(&self) -> &[Pcs::Commitment] {
+        &self.lookup_h_comms
+    }
+
+    pub fn shuffle_h_comms(&self) -> &[Pcs::Commitment] {
+        &self.shuffle_h_comms
+    }
+
+    pub fn permutation_z_comms(&self) -> &[Pcs::Commitment] {
+        &self.permutation_z_comms
+    }
+
+    /// Returns evaluations of each round polynomial of the sum-check on
+    /// `0, 1, ..., degree`. When expression is in form of `g * eq_xy(0)`, the
+    /// round polynomial is the one with `eq` factored out, so `degree` is of
+    /// `g`.
+    pub fn sum_check_msgs(&self) -> &[Vec<F>] {
+        &self.sum_check_msgs
+    }
+
+    /// Returns evaluations of each committed query, one for each point of its
+    /// rotation.
+    pub fn evals(&self) -> &[Vec<F>] {
+        &self.evals
+    }
+
+    /// Returns messages of the fractional sum-check of lookups proven by
+    /// LogUp-GKR, including claimed roots, sum-check messages and evaluations.
+    pub fn logup_gkr_msgs(&self) -> &[F] {
+        &self.logup_gkr_msgs
+    }
+
+    /// Returns messages of the grand product of permutation proven by GKR,
+    /// including claimed roots, sum-check messages and evaluations.
+    pub fn permutation_gkr_msgs(&self) -> &[F] {
+        &self.permutation_gkr_msgs
+    }
+
+    /// Returns messages of fractional sum-checks of decomposable lookups,
+    /// including claimed roots, sum-check messages and evaluations.
+    pub fn lasso_msgs(&self) -> &[F] {
+        &self.lasso_msgs
+    }
+
+    /// Returns the opening proof of PCS, which is kept as bytes since its
+    /// format is specific to the PCS.
+    pub fn pcs_proof(&self) -> &[u8] {
+        &self.pcs_proof
+    }
+
+    pub fn from_bytes<T>(
+        vp: &HyperPlonkVerifierParam<F, Pcs>,
+        param: T::Param,
+        proof: &[u8],
+    ) -> Result<Self, Error>
+    where
+        T: TranscriptRead<Pcs::CommitmentChunk, F>
+            + TranscriptWrite<Pcs::CommitmentChunk, F>
+            + InMemoryTranscript,
+    {
+        let mut transcript = T::from_proof(param.clone(), proof);
+        let num_witness_polys = vp.num_witness_polys.iter().sum();
+        let mut parsed = Self {
+            witness_comms: Pcs::read_commitments(&vp.pcs, num_witness_polys, &mut transcript)?,
+            lookup_m_comms: Pcs::read_commitments(&vp.pcs, vp.lookups.len(), &mut transcript)?,
+            lasso_m_comms: vp.read_lasso_m_comms(&mut transcript)?,
+            lookup_h_comms: Pcs::read_commitments(
+                &vp.pcs,
+                vp.num_lookup_h_polys(),
+                &mut transcript,
+            )?,
+            shuffle_h_comms: Pcs::read_commitments(&vp.pcs, vp.num_shuffles, &mut transcript)?,
+            permutation_z_comms: Pcs::read_commitments(
+                &vp.pcs,
+                vp.num_permutation_z_polys,
+                &mut transcript,
+            )?,
+            sum_check_msgs: read_sum_check_msgs(
+                vp.num_vars,
+                zero_check_degree(vp.sum_check_strategy, &vp.expression),
+                &mut transcript,
+            )?,
+            evals: read_evals(&vp.expression, vp.num_instances.len(), &mut transcript)?,
+            logup_gkr_msgs: read_logup_gkr_msgs(vp, &mut transcript)?,
+            permutation_gkr_msgs: read_permutation_gkr_msgs(vp, &mut transcript)?,
+            lasso_msgs: read_lasso_msgs(vp, &mut transcript)?,
+            pcs_proof: Vec::new(),
+        };
+        parsed.pcs_proof = proof_suffix::<T>(param, proof, |transcript| {
+            parsed.write_without_pcs_proof(transcript)
+        })?;
+        Ok(parsed)
+    }
+
+    pub fn to_bytes<T>(&self, param: T::Param) -> Result<Vec<u8>, Error>
+    where
+        T: TranscriptWrite<Pcs::CommitmentChunk, F> + InMemoryTranscript,
+    {
+        let mut transcript = T::new(param);
+        self.write_without_pcs_proof(&mut transcript)?;
+        Ok(chain![transcript.into_proof(), self.pcs_proof.iter().copied()].collect())
+    }
+
+    fn write_without_pcs_proof(
+        &self,
+        transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        for comm in chain![
+            &self.witness_comms,
+            &self.lookup_m_comms,
+            &self.lasso_m_comms,
+            &self.lookup_h_comms,
+            &self.shuffle_h_comms,
+            &self.permutation_z_comms,
+        ] {
+            transcript.write_commitments(comm.as_ref())?;
+        }
+        write_nested_field_elements(&self.sum_check_msgs, transcript)?;
+        write_nested_field_elements(&self.evals, transcript)?;
+        transcript.write_field_elements(&self.logup_gkr_msgs)?;
+        transcript.write_field_elements(&self.permutation_gkr_msgs)?;
+        transcript.write_field_elements(&self.lasso_msgs)
+    }
+}
+
+pub(crate) fn read_sum_check_msgs<F: PrimeField>(
+    num_vars: usize,
+    degree: usize,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<Vec<Vec<F>>, Error> {
+    (0..num_vars)
+        .map(|_| transcript.read_field_elements(degree + 1))
+        .try_collect()
+}
+
+/// Returns degree of zero-check round messages, which is lowered by 1 only
+/// when the Gruen zero-check is used as in `prove_zero_check`.
+fn zero_check_degree<F: PrimeField>(
+    sum_check_strategy: SumCheckStrategy,
+    expression: &Expression<F>,
+) -> usize {
+    if sum_check_strategy == Coefficients || !is_eq_xy_factored(expression) {
+        expression.degree()
+    } else {
+        expression.degree() - 1
+    }
+}
+
+pub(crate) fn read_evals<F: PrimeField>(
+    expression: &Expression<F>,
+    num_instance_poly: usize,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<Vec<Vec<F>>, Error> {
+    pcs_query(expression, num_instance_poly)
+        .iter()
+        .map(|query| transcript.read_field_elements(1 << query.rotation().distance()))
+        .try_collect()
+}
+
+fn read_logup_gkr_msgs<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
+    vp: &HyperPlonkVerifierParam<F, Pcs>,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<Vec<F>, Error> {
+    if vp.lookup_strategy != LogUpGkr || vp.lookups.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let num_fractions = 2 * vp.lookups.len();
+    let num_evals = pcs_query(&lookup_expression(&vp.lookups), vp.num_instances.len())
+        .iter()
+        .map(|query| 1 << query.rotation().distance())
+        .sum::<usize>();
+    let len = 2 * num_fractions + fractional_sum_check_len(vp.num_vars, num_fractions) + num_evals;
+    transcript.read_field_elements(len)
+}
+
+fn read_permutation_gkr_msgs<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
+    vp: &HyperPlonkVerifierParam<F, Pcs>,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<Vec<F>, Error> {
+    if vp.permutation_strategy != Gkr || vp.permutation_comms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let num_products = 2 * vp.permutation_comms.len();
+    let permutation_polys = vp
+        .permutation_comms
+        .iter()
+        .map(|(poly, _)| *poly)
+        .collect_vec();
+    let permutation_offset = vp.num_instances.len()
+        + vp.preprocess_comms.len()
+        + vp.num_witness_polys.iter().sum::<usize>();
+    let expression = permutation_expression(&permutation_polys, permutation_offset);
+    let num_evals = pcs_query(&expression, vp.num_instances.len()).len();
+    let len = num_products + grand_product_len(vp.num_vars, num_products) + num_evals;
+    transcript.read_field_elements(len)
+}
+
+fn read_lasso_msgs<F: PrimeField, Pcs: PolynomialCommitmentScheme<F>>(
+    vp: &HyperPlonkVerifierParam<F, Pcs>,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<Vec<F>, Error> {
+    if vp.lasso_chunks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let num_chunks = vp.lasso_chunks.len();
+    let num_evals = pcs_query(&lasso_expression(&vp.lasso_chunks), vp.num_instances.len()).len();
+    let len = 2 * num_chunks
+        + fractional_sum_check_len(vp.num_vars, num_chunks)
+        + num_evals
+        + vp.lasso_chunks
+            .iter()
+            .map(|(_, _, subtable)| 2 + fractional_sum_check_len(subtable.num_vars(), 1))
+            .sum::<usize>();
+    transcript.read_field_elements(len)
+}
+
+// Each layer has a sum-check of degree 3 and 4 evaluations for each batch
+fn fractional_sum_check_len(num_vars: usize, num_batching: usize) -> usize {
+    (0..num_vars)
+        .map(|num_vars| 4 * num_vars + 4 * num_batching)
+        .sum()
+}
+
+// Each layer has a sum-check of degree 3 and 2 evaluations for each batch
+fn grand_product_len(num_vars: usize, num_batching: usize) -> usize {
+    (0..num_vars)
+        .map(|num_vars| 4 * num_vars + 2 * num_batching)
+        .sum()
+}
+
+pub(crate) fn write_nested_field_elements<F: PrimeField>(
+    fes: &[Vec<F>],
+    transcript: &mut impl FieldTranscriptWrite<F>,
+) -> Result<(), Error> {
+    fes.iter()
+        .try_for_each(|fes| transcript.write_field_elements(fes))
+}
+
+/// Returns bytes of `proof` after the prefix re-encoded by `write`, which is
+/// expected to write what has been parsed from the beginning of `proof`.
+pub(crate) fn proof_suffix<T: InMemoryTranscript>(
+    param: T::Param,
+    proof: &[u8],
+    write: impl FnOnce(&mut T) -> Result<(), Error>,
+) -> Result<Vec<u8>, Error> {
+    let mut transcript = T::new(param);
+    write(&mut transcript)?;
+    let prefix = transcript.into_proof();
+    if !proof.starts_with(&prefix) {
+        return Err(Error::Serialization(
+            "Non-canonical encoding in proof".to_string(),
+        ));
+    }
+    Ok(proof[prefix.len()..].to_vec())
+}
@@ -1,18 +1,31 @@
 use crate::{
-    backend::hyperplonk::verifier::{pcs_query, point_offset, points},
+    backend::{
+        hyperplonk::{
+            verifier::{
+                lasso_expression, lookup_expression, pcs_query, permutation_expression,
+                point_offset, points,
+            },
+            SumCheckStrategy::{self, Coefficients, Evaluations},
+        },
+        Subtable,
+    },
     pcs::Evaluation,
-    piop::sum_check::{
-        classic::{ClassicSumCheck, EvaluationsProver},
-        SumCheck, VirtualPolynomial,
+    piop::{
+        gkr::{prove_fractional_sum_check, prove_grand_product},
+        sum_check::{
+            classic::{is_eq_xy_factored, ClassicSumCheck, CoefficientsProver, EvaluationsProver},
+            SumCheck, VirtualPolynomial,
+        },
     },
     poly::multilinear::MultilinearPolynomial,
     util::{
-        arithmetic::{div_ceil, steps_by, sum, BatchInvert, PrimeField},
+        arithmetic::{div_ceil, steps, steps_by, sum, BatchInvert, PrimeField},
         chain, end_timer,
         expression::{
             rotate::{BinaryField, Rotatable},
-            CommonPolynomial, Expression, Rotation,
+            CommonPolynomial, Expression, Query, Rotation,
         },
+        izip,
         parallel::{num_threads, par_map_collect, parallelize, parallelize_iter},
         start_timer,
         transcript::FieldTranscriptWrite,
@@ -22,8 +35,9 @@ use crate::{
 };
 use std::{
     borrow::Borrow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     hash::Hash,
+    iter,
 };
 
 pub(crate) fn instance_polys<'a, F: PrimeField, R: Rotatable + From<usize>>(
@@ -248,6 +262,281 @@ pub(super) fn lookup_h_poly<F: PrimeField + Hash>(
     MultilinearPolynomial::new(h_input)
 }
 
+pub(crate) fn shuffle_h_polys<F: PrimeField>(
+    compressed_polys: &[[MultilinearPolynomial<F>; 2]],
+    gamma: &F,
+) -> Vec<MultilinearPolynomial<F>> {
+    compressed_polys
+        .iter()
+        .map(|compressed_polys| shuffle_h_poly(compressed_polys, gamma))
+        .collect()
+}
+
+/// Same as [`lookup_h_poly`] but with multiplicities all being one.
+pub(super) fn shuffle_h_poly<F: PrimeField>(
+    compressed_polys: &[MultilinearPolynomial<F>; 2],
+    gamma: &F,
+) -> MultilinearPolynomial<F> {
+    let [input, shuffle] = compressed_polys;
+    let mut h_input: Vec<_> = par_map_collect(input.evals(), |input| *gamma + input);
+    let mut h_shuffle: Vec<_> = par_map_collect(shuffle.evals(), |shuffle| *gamma + shuffle);
+
+    let chunk_size = div_ceil(2 * h_input.len(), num_threads());
+    parallelize_iter(
+        chain![
+            h_input.chunks_mut(chunk_size),
+            h_shuffle.chunks_mut(chunk_size)
+        ],
+        |h| {
+            h.batch_invert();
+        },
+    );
+
+    parallelize(&mut h_input, |(h_input, start)| {
+        for (h_input, h_shuffle) in h_input.iter_mut().zip(h_shuffle[start..].iter()) {
+            *h_input -= h_shuffle;
+        }
+    });
+
+    if cfg!(feature = "sanity-check") {
+        assert_eq!(sum::<F>(&h_input), F::ZERO);
+    }
+
+    MultilinearPolynomial::new(h_input)
+}
+
+/// Proves lookups by a fractional sum-check of `Σ m/(γ+t) - Σ 1/(γ+f)` as in
+/// LogUp-GKR, where inputs and tables of all lookups are batched into one,
+/// then returns points and evaluations to be opened by PCS.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn prove_logup_gkr<F: PrimeField>(
+    num_instance_poly: usize,
+    lookups: &[Vec<(Expression<F>, Expression<F>)>],
+    compressed_polys: &[[MultilinearPolynomial<F>; 2]],
+    m_polys: &[MultilinearPolynomial<F>],
+    polys: &[&MultilinearPolynomial<F>],
+    gamma: &F,
+    m_offset: usize,
+    point_offset: usize,
+    transcript: &mut impl FieldTranscriptWrite<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
+    if lookups.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let num_vars = polys[0].num_vars();
+
+    let input_p = MultilinearPolynomial::new(vec![F::ONE; 1 << num_vars]);
+    let ps = chain![iter::repeat(&input_p).take(lookups.len()), m_polys].collect_vec();
+    let qs = chain![
+        compressed_polys.iter().map(|[input, _]| input),
+        compressed_polys.iter().map(|[_, table]| table),
+    ]
+    .map(|poly| MultilinearPolynomial::new(par_map_collect(poly.evals(), |eval| *gamma + eval)))
+    .collect_vec();
+    let (p_0s, q_0s) = izip!(&ps, &qs)
+        .map(|(p, q)| fractional_sum_root(p, q))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    transcript.write_field_elements(chain![&p_0s, &q_0s])?;
+
+    let (p_xs, _, x) = prove_fractional_sum_check(
+        p_0s.iter().copied().map(Some),
+        q_0s.iter().copied().map(Some),
+        ps,
+        &qs,
+        transcript,
+    )?;
+
+    let expression = lookup_expression(lookups);
+    let evals = pcs_query(&expression, num_instance_poly)
+        .into_iter()
+        .filter(|query| query.rotation() == Rotation::cur())
+        .map(|query| (query, polys[query.poly()].evaluate(&x)))
+        .collect();
+    let (query_points, query_evals) = prove_evaluations(
+        num_instance_poly,
+        &expression,
+        polys,
+        &x,
+        &evals,
+        transcript,
+    )?;
+
+    let points = chain![[x], query_points].collect_vec();
+    let evals = chain![
+        izip!(m_offset.., &p_xs[lookups.len()..]).map(|(poly, eval)| Evaluation::new(
+            poly,
+            point_offset,
+            *eval
+        )),
+        query_evals.into_iter().map(|eval| {
+            Evaluation::new(eval.poly(), point_offset + 1 + eval.point(), *eval.value())
+        }),
+    ]
+    .collect();
+    Ok((points, evals))
+}
+
+/// Returns polynomials of index and value of each chunk of decomposable
+/// lookups, and multiplicities of subtable entries being looked up, which are
+/// padded with zeros to number of variables given in `lasso_pcs` keyed by
+/// number of variables of subtable.
+#[allow(clippy::type_complexity)]
+pub(crate) fn lasso_m_polys<F: PrimeField + Hash, R: Rotatable + From<usize>, T>(
+    chunks: &[(Expression<F>, Expression<F>, Subtable)],
+    lasso_pcs: &BTreeMap<usize, (usize, T)>,
+    polys: &[impl Borrow<MultilinearPolynomial<F>>],
+    challenges: &[F],
+) -> Result<
+    (
+        Vec<[MultilinearPolynomial<F>; 2]>,
+        Vec<MultilinearPolynomial<F>>,
+    ),
+    Error,
+> {
+    // Index and value are evaluated as input and table of a lookup with width 1
+    let lookups = chunks
+        .iter()
+        .map(|(index, value, _)| vec![(index.clone(), value.clone())])
+        .collect_vec();
+    let chunk_polys = lookup_compressed_polys::<_, R>(&lookups, polys, challenges, &[F::ONE]);
+    let m_polys = izip!(chunks, &chunk_polys)
+        .map(|((_, _, subtable), [index, value])| {
+            let (num_vars, _) = lasso_pcs[&subtable.num_vars()];
+            lasso_m_poly(num_vars, subtable, index, value)
+        })
+        .try_collect()?;
+    Ok((chunk_polys, m_polys))
+}
+
+fn lasso_m_poly<F: PrimeField + Hash>(
+    num_vars: usize,
+    subtable: &Subtable,
+    index: &MultilinearPolynomial<F>,
+    value: &MultilinearPolynomial<F>,
+) -> Result<MultilinearPolynomial<F>, Error> {
+    let indice_map = (0..1 << subtable.num_vars())
+        .map(|idx| (F::from(idx as u64), idx))
+        .collect::<HashMap<_, _>>();
+
+    let mut m = vec![0; 1 << num_vars];
+    for (index, value) in index.iter().zip(value.iter()) {
+        match indice_map.get(index) {
+            Some(&idx) if *value == F::from(subtable.value(idx)) => m[idx] += 1,
+            _ => {
+                return Err(Error::InvalidSnark(
+                    "Invalid lasso lookup input".to_string(),
+                ))
+            }
+        }
+    }
+    let m = par_map_collect(m, |count| match count {
+        0 => F::ZERO,
+        1 => F::ONE,
+        count => F::from(count),
+    });
+    Ok(MultilinearPolynomial::new(m))
+}
+
+/// Proves decomposable lookups by fractional sum-checks, where inputs of all
+/// chunks are batched into one and each subtable is proven separately, then
+/// returns points and evaluations to be opened by PCS, and point of each
+/// subtable with evaluation of its multiplicities.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn prove_lasso<F: PrimeField>(
+    num_instance_poly: usize,
+    chunks: &[(Expression<F>, Expression<F>, Subtable)],
+    chunk_polys: &[[MultilinearPolynomial<F>; 2]],
+    m_polys: &[MultilinearPolynomial<F>],
+    polys: &[&MultilinearPolynomial<F>],
+    beta: &F,
+    gamma: &F,
+    point_offset: usize,
+    transcript: &mut impl FieldTranscriptWrite<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>, Vec<(Vec<F>, F)>), Error> {
+    if chunks.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let num_vars = polys[0].num_vars();
+
+    let input_p = MultilinearPolynomial::new(vec![F::ONE; 1 << num_vars]);
+    let input_qs = chunk_polys
+        .iter()
+        .map(|[index, value]| {
+            let q = izip!(index.iter(), value.iter())
+                .map(|(index, value)| *gamma + index + *beta * value)
+                .collect();
+            MultilinearPolynomial::new(q)
+        })
+        .collect_vec();
+    let (input_p_0s, input_q_0s) = input_qs
+        .iter()
+        .map(|input_q| fractional_sum_root(&input_p, input_q))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    transcript.write_field_elements(chain![&input_p_0s, &input_q_0s])?;
+
+    let (_, _, x) = prove_fractional_sum_check(
+        input_p_0s.iter().copied().map(Some),
+        input_q_0s.iter().copied().map(Some),
+        iter::repeat(&input_p).take(chunks.len()),
+        &input_qs,
+        transcript,
+    )?;
+
+    let expression = lasso_expression(chunks);
+    let pcs_query = pcs_query(&expression, num_instance_poly);
+    let input_evals = pcs_query
+        .iter()
+        .map(|query| {
+            let eval = polys[query.poly()].evaluate(&x);
+            Evaluation::new(query.poly(), point_offset, eval)
+        })
+        .collect_vec();
+    transcript.write_field_elements(input_evals.iter().map(Evaluation::value))?;
+
+    let mut m_openings = Vec::with_capacity(chunks.len());
+    for ((_, _, subtable), m_poly) in izip!(chunks, m_polys) {
+        let table_p = MultilinearPolynomial::new(m_poly[..1 << subtable.num_vars()].to_vec());
+        let table_q = MultilinearPolynomial::new(
+            izip!(steps(F::ZERO), subtable.evals::<F>())
+                .map(|(index, value)| *gamma + index + *beta * value)
+                .collect(),
+        );
+        let (table_p_0, table_q_0) = fractional_sum_root(&table_p, &table_q);
+        transcript.write_field_elements([&table_p_0, &table_q_0])?;
+
+        let (table_p_xs, _, x) = prove_fractional_sum_check(
+            [Some(table_p_0)],
+            [Some(table_q_0)],
+            [&table_p],
+            [&table_q],
+            transcript,
+        )?;
+
+        m_openings.push((x, table_p_xs[0]));
+    }
+
+    Ok((vec![x], input_evals, m_openings))
+}
+
+/// Returns the root of fractional sum of `p/q` computed in the same way as
+/// [`prove_fractional_sum_check`].
+fn fractional_sum_root<F: PrimeField>(
+    p: &MultilinearPolynomial<F>,
+    q: &MultilinearPolynomial<F>,
+) -> (F, F) {
+    let (mut p, mut q) = (p.evals().to_vec(), q.evals().to_vec());
+    while p.len() > 1 {
+        let mid = p.len() >> 1;
+        let ((p_l, p_r), (q_l, q_r)) = (p.split_at(mid), q.split_at(mid));
+        (p, q) = izip!(p_l, p_r, q_l, q_r)
+            .map(|(p_l, p_r, q_l, q_r)| (*p_l * q_r + *p_r * q_l, *q_l * q_r))
+            .unzip();
+    }
+    (p[0], q[0])
+}
+
 pub(crate) fn permutation_z_polys<F: PrimeField, R: Rotatable + From<usize>>(
     num_chunks: usize,
     permutation_polys: &[(usize, MultilinearPolynomial<F>)],
@@ -331,47 +620,171 @@ pub(crate) fn permutation_z_polys<F: PrimeField, R: Rotatable + From<usize>>(
     z.into_iter().map(MultilinearPolynomial::new).collect()
 }
 
+/// Proves permutation by grand products of `w + β·id + γ` and `w + β·σ + γ`
+/// of all permutation polynomials via GKR instead of committing `z`
+/// polynomials, then returns points and evaluations to be opened by PCS.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn prove_permutation_gkr<F: PrimeField>(
+    num_instance_poly: usize,
+    permutation_polys: &[(usize, MultilinearPolynomial<F>)],
+    polys: &[&MultilinearPolynomial<F>],
+    beta: &F,
+    gamma: &F,
+    permutation_offset: usize,
+    point_offset: usize,
+    transcript: &mut impl FieldTranscriptWrite<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
+    if permutation_polys.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let num_vars = polys[0].num_vars();
+
+    let (ids, permutations) = permutation_polys
+        .iter()
+        .enumerate()
+        .map(|(idx, (poly, permutation_poly))| {
+            let id_offset = idx << num_vars;
+            let mut id = vec![F::ZERO; 1 << num_vars];
+            let mut permutation = vec![F::ZERO; 1 << num_vars];
+            parallelize(&mut id, |(id, start)| {
+                for ((id, value), beta_id) in id
+                    .iter_mut()
+                    .zip(polys[*poly][start..].iter())
+                    .zip(steps_by(F::from((id_offset + start) as u64) * beta, *beta))
+                {
+                    *id = beta_id + gamma + value;
+                }
+            });
+            parallelize(&mut permutation, |(permutation, start)| {
+                for ((permutation, value), sigma) in permutation
+                    .iter_mut()
+                    .zip(polys[*poly][start..].iter())
+                    .zip(permutation_poly[start..].iter())
+                {
+                    *permutation = *beta * sigma + gamma + value;
+                }
+            });
+            (
+                MultilinearPolynomial::new(id),
+                MultilinearPolynomial::new(permutation),
+            )
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    let vs = chain![&ids, &permutations].collect_vec();
+    let v_0s = vs.iter().map(|v| v.evals().iter().product()).collect_vec();
+    transcript.write_field_elements(&v_0s)?;
+
+    let (_, x) = prove_grand_product(v_0s.into_iter().map(Some), vs, transcript)?;
+
+    let permutation_polys = permutation_polys
+        .iter()
+        .map(|(poly, _)| *poly)
+        .collect_vec();
+    let expression = permutation_expression(&permutation_polys, permutation_offset);
+    let evals = pcs_query(&expression, num_instance_poly)
+        .into_iter()
+        .map(|query| (query, polys[query.poly()].evaluate(&x)))
+        .collect();
+    let (points, evals) = prove_evaluations(
+        num_instance_poly,
+        &expression,
+        polys,
+        &x,
+        &evals,
+        transcript,
+    )?;
+
+    let evals = evals
+        .into_iter()
+        .map(|eval| Evaluation::new(eval.poly(), point_offset + eval.point(), *eval.value()))
+        .collect();
+    Ok((points, evals))
+}
+
 #[allow(clippy::type_complexity)]
 pub(super) fn prove_zero_check<F: PrimeField>(
+    sum_check_strategy: SumCheckStrategy,
     num_instance_poly: usize,
     expression: &Expression<F>,
     polys: &[&MultilinearPolynomial<F>],
+    sparse_polys: &BTreeMap<usize, Vec<usize>>,
     challenges: Vec<F>,
     y: Vec<F>,
     transcript: &mut impl FieldTranscriptWrite<F>,
 ) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
-    prove_sum_check(
-        num_instance_poly,
-        expression,
-        F::ZERO,
-        polys,
-        challenges,
-        y,
+    if sum_check_strategy == Coefficients || !is_eq_xy_factored(expression) {
+        return prove_sum_check(
+            sum_check_strategy,
+            num_instance_poly,
+            expression,
+            F::ZERO,
+            polys,
+            sparse_polys,
+            challenges,
+            y,
+            transcript,
+        );
+    }
+
+    let num_vars = polys[0].num_vars();
+    let ys = [y];
+    let virtual_poly = VirtualPolynomial::new(expression, polys.to_vec(), &challenges, &ys)
+        .with_sparse_polys(sparse_polys.iter().map(|(poly, bs)| (*poly, bs.as_slice())));
+    let (_, x, evals) = ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::prove_zero_check(
+        num_vars,
+        virtual_poly,
         transcript,
-    )
+    )?;
+
+    prove_evaluations(num_instance_poly, expression, polys, &x, &evals, transcript)
 }
 
 #[allow(clippy::type_complexity)]
 pub(crate) fn prove_sum_check<F: PrimeField>(
+    sum_check_strategy: SumCheckStrategy,
     num_instance_poly: usize,
     expression: &Expression<F>,
     sum: F,
     polys: &[&MultilinearPolynomial<F>],
+    sparse_polys: &BTreeMap<usize, Vec<usize>>,
     challenges: Vec<F>,
     y: Vec<F>,
     transcript: &mut impl FieldTranscriptWrite<F>,
 ) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
     let num_vars = polys[0].num_vars();
     let ys = [y];
-    let virtual_poly = VirtualPolynomial::new(expression, polys.to_vec(), &challenges, &ys);
-    let (_, x, evals) = ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::prove(
-        &(),
-        num_vars,
-        virtual_poly,
-        sum,
-        transcript,
-    )?;
+    let virtual_poly = VirtualPolynomial::new(expression, polys.to_vec(), &challenges, &ys)
+        .with_sparse_polys(sparse_polys.iter().map(|(poly, bs)| (*poly, bs.as_slice())));
+    let (_, x, evals) = match sum_check_strategy {
+        Evaluations => ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::prove(
+            &(),
+            num_vars,
+            virtual_poly,
+            sum,
+            transcript,
+        )?,
+        Coefficients => ClassicSumCheck::<CoefficientsProver<_>, BinaryField>::prove(
+            &(),
+            num_vars,
+            virtual_poly,
+            sum,
+            transcript,
+        )?,
+    };
 
+    prove_evaluations(num_instance_poly, expression, polys, &x, &evals, transcript)
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn prove_evaluations<F: PrimeField>(
+    num_instance_poly: usize,
+    expression: &Expression<F>,
+    polys: &[&MultilinearPolynomial<F>],
+    x: &[F],
+    evals: &BTreeMap<Query, F>,
+    transcript: &mut impl FieldTranscriptWrite<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
     let pcs_query = pcs_query(expression, num_instance_poly);
     let point_offset = point_offset(&pcs_query);
 
@@ -383,7 +796,7 @@ pub(crate) fn prove_sum_check<F: PrimeField>(
                 .zip(if query.rotation() == Rotation::cur() {
                     vec![evals[query]]
                 } else {
-                    polys[query.poly()].evaluate_for_rotation(&x, query.rotation())
+                    polys[query.poly()].evaluate_for_rotation(x, query.rotation())
                 })
                 .map(|(point, eval)| Evaluation::new(query.poly(), point, eval))
         })
@@ -392,5 +805,5 @@ pub(crate) fn prove_sum_check<F: PrimeField>(
 
     transcript.write_field_elements(evals.iter().map(Evaluation::value))?;
 
-    Ok((points(&pcs_query, &x), evals))
+    Ok((points(&pcs_query, x), evals))
 }
@@ -6,13 +6,15 @@ use crate::{
                 instance_polys, lookup_compressed_polys, lookup_h_polys, lookup_m_polys,
                 permutation_z_polys,
             },
+            LookupStrategy::LogUp,
+            PermutationStrategy::Plonk,
         },
         mock::MockCircuit,
-        PlonkishCircuit, PlonkishCircuitInfo,
+        DecomposableLookup, Lookup, PlonkishCircuit, PlonkishCircuitInfo, Subtable,
     },
     poly::multilinear::MultilinearPolynomial,
     util::{
-        arithmetic::{powers, PrimeField},
+        arithmetic::{div_ceil, powers, PrimeField},
         chain,
         expression::{rotate::Rotatable, Expression, Query, Rotation},
         test::{rand_array, rand_idx, rand_vec},
@@ -20,12 +22,13 @@ use crate::{
     },
 };
 use num_integer::Integer;
-use rand::RngCore;
+use rand::{seq::SliceRandom, RngCore};
 use std::{
     array,
     collections::{HashMap, HashSet},
     hash::Hash,
     iter, mem,
+    ops::Range,
 };
 
 pub fn vanilla_plonk_circuit_info<F: PrimeField>(
@@ -44,6 +47,7 @@ pub fn vanilla_plonk_circuit_info<F: PrimeField>(
         num_challenges: vec![0],
         constraints: vec![q_l * w_l + q_r * w_r + q_m * w_l * w_r + q_o * w_o + q_c + pi],
         lookups: Vec::new(),
+        shuffles: Vec::new(),
         permutations,
         max_degree: Some(4),
     }
@@ -56,7 +60,7 @@ pub fn vanilla_plonk_expression<F: PrimeField>(num_vars: usize) -> Expression<F>
         Default::default(),
         vec![vec![(6, 1)], vec![(7, 1)], vec![(8, 1)]],
     );
-    let (num_permutation_z_polys, expression) = compose(&circuit_info);
+    let (num_permutation_z_polys, expression) = compose(&circuit_info, LogUp, Plonk);
     assert_eq!(num_permutation_z_polys, 1);
     expression
 }
@@ -76,16 +80,109 @@ pub fn vanilla_plonk_w_lookup_circuit_info<F: PrimeField>(
         num_witness_polys: vec![3],
         num_challenges: vec![0],
         constraints: vec![q_l * w_l + q_r * w_r + q_m * w_l * w_r + q_o * w_o + q_c + pi],
-        lookups: vec![vec![
+        lookups: vec![Lookup::Vector(vec![
             (q_lookup * w_l, t_l.clone()),
             (q_lookup * w_r, t_r.clone()),
             (q_lookup * w_o, t_o.clone()),
+        ])],
+        shuffles: Vec::new(),
+        permutations,
+        max_degree: Some(4),
+    }
+}
+
+pub fn vanilla_plonk_w_dynamic_lookup_circuit_info<F: PrimeField>(
+    num_vars: usize,
+    num_instances: usize,
+    preprocess_polys: [Vec<F>; 7],
+    permutations: Vec<Vec<(usize, usize)>>,
+) -> PlonkishCircuitInfo<F> {
+    let [pi, q_l, q_r, q_m, q_o, q_c, q_lookup, q_table, t_l, t_r, t_o, w_l, w_r, w_o] =
+        &array::from_fn(|poly| Query::new(poly, Rotation::cur())).map(Expression::Polynomial);
+    PlonkishCircuitInfo {
+        k: num_vars,
+        num_instances: vec![num_instances],
+        preprocess_polys: preprocess_polys.to_vec(),
+        num_witness_polys: vec![3, 3],
+        num_challenges: vec![1, 0],
+        constraints: vec![q_l * w_l + q_r * w_r + q_m * w_l * w_r + q_o * w_o + q_c + pi],
+        lookups: vec![Lookup::Vector(vec![
+            (q_lookup * w_l, q_table * t_l),
+            (q_lookup * w_r, q_table * t_r),
+            (q_lookup * w_o, q_table * t_o),
+        ])],
+        shuffles: Vec::new(),
+        permutations,
+        max_degree: Some(4),
+    }
+}
+
+pub fn vanilla_plonk_w_shuffle_circuit_info<F: PrimeField>(
+    num_vars: usize,
+    num_instances: usize,
+    preprocess_polys: [Vec<F>; 6],
+    permutations: Vec<Vec<(usize, usize)>>,
+) -> PlonkishCircuitInfo<F> {
+    let [pi, q_l, q_r, q_m, q_o, q_c, q_shuffle, w_l, w_r, w_o, s_l, s_r] =
+        &array::from_fn(|poly| Query::new(poly, Rotation::cur())).map(Expression::Polynomial);
+    PlonkishCircuitInfo {
+        k: num_vars,
+        num_instances: vec![num_instances],
+        preprocess_polys: preprocess_polys.to_vec(),
+        num_witness_polys: vec![5],
+        num_challenges: vec![0],
+        constraints: vec![q_l * w_l + q_r * w_r + q_m * w_l * w_r + q_o * w_o + q_c + pi],
+        lookups: Vec::new(),
+        shuffles: vec![vec![
+            (q_shuffle * w_l, q_shuffle * s_l),
+            (q_shuffle * w_r, q_shuffle * s_r),
         ]],
         permutations,
         max_degree: Some(4),
     }
 }
 
+pub fn vanilla_plonk_w_decomposable_lookup_circuit_info<F: PrimeField>(
+    num_vars: usize,
+    num_instances: usize,
+    preprocess_polys: [Vec<F>; 6],
+    permutations: Vec<Vec<(usize, usize)>>,
+) -> PlonkishCircuitInfo<F> {
+    let [pi, q_l, q_r, q_m, q_o, q_c, q_xor, w_l, w_r, w_o, x_0, x_1, y_0, y_1, z_0, z_1, z] =
+        &array::from_fn(|poly| Query::new(poly, Rotation::cur())).map(Expression::Polynomial);
+    let [v_0, v_1] =
+        &array::from_fn(|poly| Query::new(poly, Rotation::cur())).map(Expression::Polynomial);
+    let num_bits = num_vars / 2;
+    let shift = F::from(1 << num_bits);
+    PlonkishCircuitInfo {
+        k: num_vars,
+        num_instances: vec![num_instances],
+        preprocess_polys: preprocess_polys.to_vec(),
+        num_witness_polys: vec![10],
+        num_challenges: vec![0],
+        constraints: vec![q_l * w_l + q_r * w_r + q_m * w_l * w_r + q_o * w_o + q_c + pi],
+        lookups: vec![Lookup::Decomposable(DecomposableLookup {
+            chunks: vec![
+                (
+                    q_xor * (x_0 + y_0 * shift),
+                    q_xor * z_0,
+                    Subtable::Xor { num_bits },
+                ),
+                (
+                    q_xor * (x_1 + y_1 * shift),
+                    q_xor * z_1,
+                    Subtable::Xor { num_bits },
+                ),
+            ],
+            output: q_xor * z,
+            combine: v_0 + v_1 * shift,
+        })],
+        shuffles: Vec::new(),
+        permutations,
+        max_degree: Some(4),
+    }
+}
+
 pub fn vanilla_plonk_w_lookup_expression<F: PrimeField>(num_vars: usize) -> Expression<F> {
     let circuit_info = vanilla_plonk_w_lookup_circuit_info(
         num_vars,
@@ -93,18 +190,30 @@ pub fn vanilla_plonk_w_lookup_expression<F: PrimeField>(num_vars: usize) -> Expr
         Default::default(),
         vec![vec![(10, 1)], vec![(11, 1)], vec![(12, 1)]],
     );
-    let (num_permutation_z_polys, expression) = compose(&circuit_info);
+    let (num_permutation_z_polys, expression) = compose(&circuit_info, LogUp, Plonk);
     assert_eq!(num_permutation_z_polys, 1);
     expression
 }
 
 pub fn rand_vanilla_plonk_circuit<F: PrimeField, R: Rotatable + From<usize>>(
     num_vars: usize,
+    preprocess_rng: impl RngCore,
+    witness_rng: impl RngCore,
+) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
+    rand_vanilla_plonk_circuit_w_blinding_rows::<_, R>(num_vars, 0, preprocess_rng, witness_rng)
+}
+
+/// Same as [`rand_vanilla_plonk_circuit`] but leaves the last
+/// `num_blinding_rows` usable rows unconstrained and out of any copy.
+pub fn rand_vanilla_plonk_circuit_w_blinding_rows<F: PrimeField, R: Rotatable + From<usize>>(
+    num_vars: usize,
+    num_blinding_rows: usize,
     mut preprocess_rng: impl RngCore,
     mut witness_rng: impl RngCore,
 ) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
     let size = 1 << num_vars;
     let mut polys = [(); 9].map(|_| vec![F::ZERO; size]);
+    let blinding_rows = blinding_rows::<R>(num_vars, num_blinding_rows);
 
     let instances = rand_vec(num_vars, &mut witness_rng);
     polys[0] = mem::take(&mut instance_polys::<_, R>(num_vars, [&instances])[0]).into_evals();
@@ -114,11 +223,14 @@ pub fn rand_vanilla_plonk_circuit<F: PrimeField, R: Rotatable + From<usize>>(
         permutation.copy((poly, 1), (poly, 1));
     }
     for idx in 0..size - 1 {
+        if blinding_rows.contains(&idx) {
+            continue;
+        }
         let [w_l, w_r] = if preprocess_rng.next_u32().is_even() && idx > 1 {
             let [l_copy_idx, r_copy_idx] = [(); 2].map(|_| {
                 (
                     rand_idx(6..9, &mut preprocess_rng),
-                    rand_idx(1..idx, &mut preprocess_rng),
+                    rand_row(1..idx, &blinding_rows, &mut preprocess_rng),
                 )
             });
             permutation.copy(l_copy_idx, (6, idx));
@@ -169,6 +281,47 @@ pub fn rand_vanilla_plonk_circuit<F: PrimeField, R: Rotatable + From<usize>>(
     )
 }
 
+/// Returns another random witness of circuit from
+/// [`rand_vanilla_plonk_circuit_w_blinding_rows`] satisfying the same
+/// `instances`, by sampling every cell not copied from an earlier one.
+pub fn rand_vanilla_plonk_witness<F: PrimeField, R: Rotatable + From<usize>>(
+    circuit_info: &PlonkishCircuitInfo<F>,
+    instances: &[Vec<F>],
+    mut witness_rng: impl RngCore,
+) -> Vec<Vec<F>> {
+    let size = 1 << circuit_info.k;
+    let copied_from = circuit_info
+        .permutations
+        .iter()
+        .flat_map(|cycle| {
+            let first = *cycle
+                .iter()
+                .min_by_key(|(poly, row)| (*row, *poly))
+                .unwrap();
+            cycle.iter().map(move |cell| (*cell, first))
+        })
+        .collect::<HashMap<_, _>>();
+    let pi = mem::take(&mut instance_polys::<_, R>(circuit_info.k, instances)[0]).into_evals();
+
+    let mut w = [(); 3].map(|_| vec![F::ZERO; size]);
+    for row in 0..size {
+        let [q_l, q_r, q_m, q_o, q_c] =
+            array::from_fn(|poly| circuit_info.preprocess_polys[poly][row]);
+        if q_o != -F::ONE {
+            continue;
+        }
+        for poly in [0, 1] {
+            w[poly][row] = match copied_from.get(&(6 + poly, row)) {
+                Some(&(from_poly, from_row)) if from_row < row => w[from_poly - 6][from_row],
+                _ => F::random(&mut witness_rng),
+            };
+        }
+        let [w_l, w_r] = [w[0][row], w[1][row]];
+        w[2][row] = q_l * w_l + q_r * w_r + q_m * w_l * w_r + q_c + pi[row];
+    }
+    w.to_vec()
+}
+
 pub fn rand_vanilla_plonk_assignment<F: PrimeField, R: Rotatable + From<usize>>(
     num_vars: usize,
     mut preprocess_rng: impl RngCore,
@@ -208,11 +361,32 @@ pub fn rand_vanilla_plonk_assignment<F: PrimeField, R: Rotatable + From<usize>>(
 
 pub fn rand_vanilla_plonk_w_lookup_circuit<F: PrimeField + Hash, R: Rotatable + From<usize>>(
     num_vars: usize,
+    preprocess_rng: impl RngCore,
+    witness_rng: impl RngCore,
+) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
+    rand_vanilla_plonk_w_lookup_circuit_w_blinding_rows::<_, R>(
+        num_vars,
+        0,
+        preprocess_rng,
+        witness_rng,
+    )
+}
+
+/// Same as [`rand_vanilla_plonk_w_lookup_circuit`] but leaves the last
+/// `num_blinding_rows` usable rows unconstrained, out of any copy and out of
+/// the lookup table.
+pub fn rand_vanilla_plonk_w_lookup_circuit_w_blinding_rows<
+    F: PrimeField + Hash,
+    R: Rotatable + From<usize>,
+>(
+    num_vars: usize,
+    num_blinding_rows: usize,
     mut preprocess_rng: impl RngCore,
     mut witness_rng: impl RngCore,
 ) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
     let size = 1 << num_vars;
     let mut polys = [(); 13].map(|_| vec![F::ZERO; size]);
+    let blinding_rows = blinding_rows::<R>(num_vars, num_blinding_rows);
 
     let [t_l, t_r, t_o] = [(); 3].map(|_| {
         chain![
@@ -241,12 +415,15 @@ pub fn rand_vanilla_plonk_w_lookup_circuit<F: PrimeField + Hash, R: Rotatable +
         permutation.copy((poly, 1), (poly, 1));
     }
     for idx in 0..size - 1 {
+        if blinding_rows.contains(&idx) {
+            continue;
+        }
         let use_copy = preprocess_rng.next_u32().is_even() && idx > 1;
         let [w_l, w_r] = if use_copy {
             let [l_copy_idx, r_copy_idx] = [(); 2].map(|_| {
                 (
                     rand_idx(10..13, &mut preprocess_rng),
-                    rand_idx(1..idx, &mut preprocess_rng),
+                    rand_row(1..idx, &blinding_rows, &mut preprocess_rng),
                 )
             });
             permutation.copy(l_copy_idx, (10, idx));
@@ -285,7 +462,7 @@ pub fn rand_vanilla_plonk_w_lookup_circuit<F: PrimeField + Hash, R: Rotatable +
                 ]
             }
             (false, _) => {
-                let idx = rand_idx(1..size, &mut witness_rng);
+                let idx = rand_row(1..size, &blinding_rows, &mut witness_rng);
                 vec![
                     (6, F::ONE),
                     (10, polys[7][idx]),
@@ -335,8 +512,8 @@ pub fn rand_vanilla_plonk_w_lookup_assignment<F: PrimeField + Hash, R: Rotatable
     let [beta, gamma, _] = challenges;
 
     let (lookup_compressed_polys, lookup_m_polys) = {
-        let PlonkishCircuitInfo { lookups, .. } =
-            vanilla_plonk_w_lookup_circuit_info(0, 0, Default::default(), Vec::new());
+        let lookups = vanilla_plonk_w_lookup_circuit_info(0, 0, Default::default(), Vec::new())
+            .vector_lookups();
         let polys = polys.iter().collect_vec();
         let betas = powers(beta).take(3).collect_vec();
         let lookup_compressed_polys =
@@ -371,6 +548,297 @@ pub fn rand_vanilla_plonk_w_lookup_assignment<F: PrimeField + Hash, R: Rotatable
     )
 }
 
+pub fn rand_vanilla_plonk_w_dynamic_lookup_circuit<
+    F: PrimeField + Hash,
+    R: Rotatable + From<usize>,
+>(
+    num_vars: usize,
+    preprocess_rng: impl RngCore,
+    witness_rng: impl RngCore,
+) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
+    rand_vanilla_plonk_w_dynamic_lookup_circuit_w_blinding_rows::<_, R>(
+        num_vars,
+        0,
+        preprocess_rng,
+        witness_rng,
+    )
+}
+
+/// Same as [`rand_vanilla_plonk_w_lookup_circuit_w_blinding_rows`] but with
+/// table in witness polynomials `(t_l, t_r, t_o)`, which is masked by `q_table`
+/// and has duplicate entries. The table is committed in the first phase, and
+/// `(w_l, w_r, w_o)` looking it up are committed in the second phase after a
+/// challenge.
+pub fn rand_vanilla_plonk_w_dynamic_lookup_circuit_w_blinding_rows<
+    F: PrimeField + Hash,
+    R: Rotatable + From<usize>,
+>(
+    num_vars: usize,
+    num_blinding_rows: usize,
+    mut preprocess_rng: impl RngCore,
+    mut witness_rng: impl RngCore,
+) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
+    let size = 1 << num_vars;
+    let mut polys = [(); 14].map(|_| vec![F::ZERO; size]);
+    let blinding_rows = blinding_rows::<R>(num_vars, num_blinding_rows);
+
+    let table_rows = (1..size)
+        .filter(|row| !blinding_rows.contains(row) && preprocess_rng.next_u32().is_even())
+        .collect_vec();
+    let entries = iter::repeat_with(|| rand_array::<F, 3>(&mut witness_rng))
+        .take(div_ceil(table_rows.len(), 2))
+        .collect_vec();
+    for row in table_rows.iter().copied() {
+        let entry = entries[rand_idx(0..entries.len(), &mut witness_rng)];
+        polys[7][row] = F::ONE;
+        polys[8][row] = entry[0];
+        polys[9][row] = entry[1];
+        polys[10][row] = entry[2];
+    }
+
+    let instances = rand_vec(num_vars, &mut witness_rng);
+    polys[0] = instance_polys::<_, R>(num_vars, [&instances])[0]
+        .evals()
+        .to_vec();
+    let instance_rows = R::from(num_vars)
+        .usable_indices()
+        .into_iter()
+        .take(num_vars + 1)
+        .collect::<HashSet<_>>();
+
+    let mut permutation = Permutation::default();
+    for poly in [11, 12, 13] {
+        permutation.copy((poly, 1), (poly, 1));
+    }
+    for idx in 0..size - 1 {
+        if blinding_rows.contains(&idx) {
+            continue;
+        }
+        let use_copy = preprocess_rng.next_u32().is_even() && idx > 1;
+        let [w_l, w_r] = if use_copy {
+            let [l_copy_idx, r_copy_idx] = [(); 2].map(|_| {
+                (
+                    rand_idx(11..14, &mut preprocess_rng),
+                    rand_row(1..idx, &blinding_rows, &mut preprocess_rng),
+                )
+            });
+            permutation.copy(l_copy_idx, (11, idx));
+            permutation.copy(r_copy_idx, (12, idx));
+            [
+                polys[l_copy_idx.0][l_copy_idx.1],
+                polys[r_copy_idx.0][r_copy_idx.1],
+            ]
+        } else {
+            rand_array(&mut witness_rng)
+        };
+        let q_c = F::random(&mut preprocess_rng);
+        let values = match (
+            use_copy || instance_rows.contains(&idx) || table_rows.is_empty(),
+            preprocess_rng.next_u32().is_even(),
+        ) {
+            (true, true) => {
+                vec![
+                    (1, F::ONE),
+                    (2, F::ONE),
+                    (4, -F::ONE),
+                    (5, q_c),
+                    (11, w_l),
+                    (12, w_r),
+                    (13, w_l + w_r + q_c + polys[0][idx]),
+                ]
+            }
+            (true, false) => {
+                vec![
+                    (3, F::ONE),
+                    (4, -F::ONE),
+                    (5, q_c),
+                    (11, w_l),
+                    (12, w_r),
+                    (13, w_l * w_r + q_c + polys[0][idx]),
+                ]
+            }
+            (false, _) => {
+                let idx = table_rows[rand_idx(0..table_rows.len(), &mut witness_rng)];
+                vec![
+                    (6, F::ONE),
+                    (11, polys[8][idx]),
+                    (12, polys[9][idx]),
+                    (13, polys[10][idx]),
+                ]
+            }
+        };
+        for (poly, value) in values {
+            polys[poly][idx] = value;
+        }
+    }
+
+    let [_, q_l, q_r, q_m, q_o, q_c, q_lookup, q_table, t_l, t_r, t_o, w_l, w_r, w_o] = polys;
+    let circuit_info = vanilla_plonk_w_dynamic_lookup_circuit_info(
+        num_vars,
+        instances.len(),
+        [q_l, q_r, q_m, q_o, q_c, q_lookup, q_table],
+        permutation.into_cycles(),
+    );
+    (
+        circuit_info,
+        MockCircuit::new_w_phases(
+            vec![instances],
+            vec![vec![t_l, t_r, t_o], vec![w_l, w_r, w_o]],
+        ),
+    )
+}
+
+pub fn rand_vanilla_plonk_w_shuffle_circuit<F: PrimeField, R: Rotatable + From<usize>>(
+    num_vars: usize,
+    preprocess_rng: impl RngCore,
+    witness_rng: impl RngCore,
+) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
+    rand_vanilla_plonk_w_shuffle_circuit_w_blinding_rows::<_, R>(
+        num_vars,
+        0,
+        preprocess_rng,
+        witness_rng,
+    )
+}
+
+/// Same as [`rand_vanilla_plonk_circuit_w_blinding_rows`] but with `(w_l, w_r)`
+/// shuffled into `(s_l, s_r)` on all rows except the blinding ones.
+pub fn rand_vanilla_plonk_w_shuffle_circuit_w_blinding_rows<
+    F: PrimeField,
+    R: Rotatable + From<usize>,
+>(
+    num_vars: usize,
+    num_blinding_rows: usize,
+    mut preprocess_rng: impl RngCore,
+    mut witness_rng: impl RngCore,
+) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
+    let size = 1 << num_vars;
+    let (circuit_info, circuit) = rand_vanilla_plonk_circuit_w_blinding_rows::<_, R>(
+        num_vars,
+        num_blinding_rows,
+        &mut preprocess_rng,
+        &mut witness_rng,
+    );
+    let blinding_rows = blinding_rows::<R>(num_vars, num_blinding_rows);
+
+    let [w_l, w_r, w_o] = circuit.synthesize(0, &[]).unwrap().try_into().unwrap();
+    let rows = (0..size)
+        .filter(|row| !blinding_rows.contains(row))
+        .collect_vec();
+    let shuffled_rows = {
+        let mut rows = rows.clone();
+        rows.shuffle(&mut witness_rng);
+        rows
+    };
+    let mut q_shuffle = vec![F::ZERO; size];
+    let mut s_l = vec![F::ZERO; size];
+    let mut s_r = vec![F::ZERO; size];
+    for (row, shuffled_row) in rows.into_iter().zip(shuffled_rows) {
+        q_shuffle[row] = F::ONE;
+        s_l[row] = w_l[shuffled_row];
+        s_r[row] = w_r[shuffled_row];
+    }
+
+    let [q_l, q_r, q_m, q_o, q_c] = circuit_info.preprocess_polys.try_into().unwrap();
+    let permutations = circuit_info
+        .permutations
+        .into_iter()
+        .map(|cycle| {
+            cycle
+                .into_iter()
+                .map(|(poly, row)| (if poly < 6 { poly } else { poly + 1 }, row))
+                .collect()
+        })
+        .collect();
+    let circuit_info = vanilla_plonk_w_shuffle_circuit_info(
+        num_vars,
+        circuit_info.num_instances[0],
+        [q_l, q_r, q_m, q_o, q_c, q_shuffle],
+        permutations,
+    );
+    (
+        circuit_info,
+        MockCircuit::new(circuit.instances().to_vec(), vec![w_l, w_r, w_o, s_l, s_r]),
+    )
+}
+
+/// Same as [`rand_vanilla_plonk_circuit`] but with `z = x ^ y` on random rows,
+/// where `x`, `y` and `z` have `2 * (num_vars / 2)` bits and are decomposed
+/// into 2 chunks to be looked up from XOR subtable.
+pub fn rand_vanilla_plonk_w_decomposable_lookup_circuit<
+    F: PrimeField,
+    R: Rotatable + From<usize>,
+>(
+    num_vars: usize,
+    mut preprocess_rng: impl RngCore,
+    mut witness_rng: impl RngCore,
+) -> (PlonkishCircuitInfo<F>, impl PlonkishCircuit<F>) {
+    let size = 1 << num_vars;
+    let num_bits = num_vars / 2;
+    let (circuit_info, circuit) =
+        rand_vanilla_plonk_circuit::<_, R>(num_vars, &mut preprocess_rng, &mut witness_rng);
+
+    let mut q_xor = vec![F::ZERO; size];
+    let mut xor_polys = [(); 7].map(|_| vec![F::ZERO; size]);
+    for row in R::from(num_vars).usable_indices() {
+        if preprocess_rng.next_u32().is_odd() {
+            continue;
+        }
+        let [x_0, x_1, y_0, y_1] = [(); 4].map(|_| witness_rng.next_u64() % (1 << num_bits));
+        let [z_0, z_1] = [x_0 ^ y_0, x_1 ^ y_1];
+        q_xor[row] = F::ONE;
+        for (poly, value) in
+            xor_polys
+                .iter_mut()
+                .zip([x_0, x_1, y_0, y_1, z_0, z_1, z_0 + (z_1 << num_bits)])
+        {
+            poly[row] = F::from(value);
+        }
+    }
+
+    let [q_l, q_r, q_m, q_o, q_c] = circuit_info.preprocess_polys.try_into().unwrap();
+    let permutations = circuit_info
+        .permutations
+        .into_iter()
+        .map(|cycle| {
+            cycle
+                .into_iter()
+                .map(|(poly, row)| (if poly < 6 { poly } else { poly + 1 }, row))
+                .collect()
+        })
+        .collect();
+    let circuit_info = vanilla_plonk_w_decomposable_lookup_circuit_info(
+        num_vars,
+        circuit_info.num_instances[0],
+        [q_l, q_r, q_m, q_o, q_c, q_xor],
+        permutations,
+    );
+    let witness = chain![circuit.synthesize(0, &[]).unwrap(), xor_polys].collect();
+    (
+        circuit_info,
+        MockCircuit::new(circuit.instances().to_vec(), witness),
+    )
+}
+
+fn blinding_rows<R: Rotatable + From<usize>>(
+    num_vars: usize,
+    num_blinding_rows: usize,
+) -> HashSet<usize> {
+    let rotatable = R::from(num_vars);
+    (1..=num_blinding_rows as i32)
+        .map(|i| rotatable.nth(-i))
+        .collect()
+}
+
+fn rand_row(range: Range<usize>, blinding_rows: &HashSet<usize>, mut rng: impl RngCore) -> usize {
+    loop {
+        let row = rand_idx(range.clone(), &mut rng);
+        if !blinding_rows.contains(&row) {
+            return row;
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Permutation {
     cycles: Vec<HashSet<(usize, usize)>>,
@@ -1,25 +1,38 @@
 use crate::{
+    backend::{
+        hyperplonk::SumCheckStrategy::{self, Coefficients, Evaluations},
+        Subtable,
+    },
     pcs::Evaluation,
-    piop::sum_check::{
-        classic::{ClassicSumCheck, EvaluationsProver},
-        evaluate, lagrange_eval, SumCheck,
+    piop::{
+        gkr::{verify_fractional_sum_check, verify_grand_product},
+        sum_check::{
+            classic::{is_eq_xy_factored, ClassicSumCheck, CoefficientsProver, EvaluationsProver},
+            evaluate, lagrange_eval, SumCheck,
+        },
     },
     poly::multilinear::{rotation_eval, rotation_eval_points},
     util::{
         arithmetic::{inner_product, PrimeField},
+        chain,
         expression::{
             rotate::{BinaryField, Rotatable},
             Expression, Query, Rotation,
         },
+        izip,
         transcript::FieldTranscriptRead,
         Itertools,
     },
     Error,
 };
-use std::collections::{BTreeSet, HashMap};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    iter,
+};
 
 #[allow(clippy::type_complexity)]
 pub(super) fn verify_zero_check<F: PrimeField>(
+    sum_check_strategy: SumCheckStrategy,
     num_vars: usize,
     expression: &Expression<F>,
     instances: &[Vec<F>],
@@ -27,19 +40,34 @@ pub(super) fn verify_zero_check<F: PrimeField>(
     y: &[F],
     transcript: &mut impl FieldTranscriptRead<F>,
 ) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
-    verify_sum_check(
+    if sum_check_strategy == Coefficients || !is_eq_xy_factored(expression) {
+        return verify_sum_check(
+            sum_check_strategy,
+            num_vars,
+            expression,
+            F::ZERO,
+            instances,
+            challenges,
+            y,
+            transcript,
+        );
+    }
+
+    let (x_eval, x) = ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::verify_zero_check(
         num_vars,
-        expression,
-        F::ZERO,
-        instances,
-        challenges,
+        expression.degree() - 1,
         y,
         transcript,
+    )?;
+
+    verify_evaluations(
+        num_vars, expression, instances, challenges, y, x_eval, &x, transcript,
     )
 }
 
 #[allow(clippy::type_complexity)]
 pub(crate) fn verify_sum_check<F: PrimeField>(
+    sum_check_strategy: SumCheckStrategy,
     num_vars: usize,
     expression: &Expression<F>,
     sum: F,
@@ -48,39 +76,81 @@ pub(crate) fn verify_sum_check<F: PrimeField>(
     y: &[F],
     transcript: &mut impl FieldTranscriptRead<F>,
 ) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
-    let (x_eval, x) = ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::verify(
-        &(),
-        num_vars,
-        expression.degree(),
-        sum,
-        transcript,
-    )?;
+    let (x_eval, x) = match sum_check_strategy {
+        Evaluations => ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::verify(
+            &(),
+            num_vars,
+            expression.degree(),
+            sum,
+            transcript,
+        )?,
+        Coefficients => ClassicSumCheck::<CoefficientsProver<_>, BinaryField>::verify(
+            &(),
+            num_vars,
+            expression.degree(),
+            sum,
+            transcript,
+        )?,
+    };
+
+    verify_evaluations(
+        num_vars, expression, instances, challenges, y, x_eval, &x, transcript,
+    )
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn verify_evaluations<F: PrimeField>(
+    num_vars: usize,
+    expression: &Expression<F>,
+    instances: &[Vec<F>],
+    challenges: &[F],
+    y: &[F],
+    x_eval: F,
+    x: &[F],
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
+    let (evals, points, pcs_evals) =
+        read_evaluations(num_vars, expression, instances, x, transcript)?;
+    if evaluate::<_, BinaryField>(expression, num_vars, &evals, challenges, &[y], x) != x_eval {
+        return Err(Error::InvalidSnark(
+            "Unmatched between sum_check output and query evaluation".to_string(),
+        ));
+    }
 
+    Ok((points, pcs_evals))
+}
+
+/// Reads evaluations of queries of `expression` on `x`, then returns
+/// evaluations of all queries including instance ones, and points and
+/// evaluations to be verified by PCS.
+#[allow(clippy::type_complexity)]
+pub(crate) fn read_evaluations<F: PrimeField>(
+    num_vars: usize,
+    expression: &Expression<F>,
+    instances: &[Vec<F>],
+    x: &[F],
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<(BTreeMap<Query, F>, Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
     let pcs_query = pcs_query(expression, instances.len());
     let (evals_for_rotation, evals) = pcs_query
         .iter()
         .map(|query| {
             let evals_for_rotation =
                 transcript.read_field_elements(1 << query.rotation().distance())?;
-            let eval = rotation_eval(&x, query.rotation(), &evals_for_rotation);
+            let eval = rotation_eval(x, query.rotation(), &evals_for_rotation);
             Ok((evals_for_rotation, (*query, eval)))
         })
         .try_collect::<_, Vec<_>, _>()?
         .into_iter()
         .unzip::<_, _, Vec<_>, Vec<_>>();
 
-    let evals = instance_evals::<_, BinaryField>(num_vars, expression, instances, &x)
+    let evals = instance_evals::<_, BinaryField>(num_vars, expression, instances, x)
         .into_iter()
         .chain(evals)
         .collect();
-    if evaluate::<_, BinaryField>(expression, num_vars, &evals, challenges, &[y], &x) != x_eval {
-        return Err(Error::InvalidSnark(
-            "Unmatched between sum_check output and query evaluation".to_string(),
-        ));
-    }
 
     let point_offset = point_offset(&pcs_query);
-    let evals = pcs_query
+    let pcs_evals = pcs_query
         .iter()
         .zip(evals_for_rotation)
         .flat_map(|(query, evals_for_rotation)| {
@@ -89,7 +159,286 @@ pub(crate) fn verify_sum_check<F: PrimeField>(
                 .map(|(point, eval)| Evaluation::new(query.poly(), point, eval))
         })
         .collect();
-    Ok((points(&pcs_query, &x), evals))
+    Ok((evals, points(&pcs_query, x), pcs_evals))
+}
+
+/// Verifies lookups proven by [`prove_logup_gkr`], then returns points and
+/// evaluations to be verified by PCS.
+///
+/// [`prove_logup_gkr`]: crate::backend::hyperplonk::prover::prove_logup_gkr
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn verify_logup_gkr<F: PrimeField>(
+    num_vars: usize,
+    lookups: &[Vec<(Expression<F>, Expression<F>)>],
+    instances: &[Vec<F>],
+    challenges: &[F],
+    beta: &F,
+    gamma: &F,
+    m_offset: usize,
+    point_offset: usize,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
+    if lookups.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let p_0s = transcript.read_field_elements(2 * lookups.len())?;
+    let q_0s = transcript.read_field_elements(2 * lookups.len())?;
+    let ((input_p_0s, table_p_0s), (input_q_0s, table_q_0s)) =
+        (p_0s.split_at(lookups.len()), q_0s.split_at(lookups.len()));
+    for (input_p_0, input_q_0, table_p_0, table_q_0) in
+        izip!(input_p_0s, input_q_0s, table_p_0s, table_q_0s)
+    {
+        if *input_p_0 * table_q_0 != *table_p_0 * input_q_0 {
+            return Err(Error::InvalidSnark(
+                "Unmatched between lookup input and table".to_string(),
+            ));
+        }
+    }
+
+    let (p_xs, q_xs, x) = verify_fractional_sum_check(
+        num_vars,
+        p_0s.iter().copied().map(Some),
+        q_0s.iter().copied().map(Some),
+        transcript,
+    )?;
+
+    let expression = lookup_expression(lookups);
+    let (query_evals, query_points, query_evals_for_pcs) =
+        read_evaluations(num_vars, &expression, instances, &x, transcript)?;
+
+    let beta = Expression::Constant(*beta);
+    let (input_q_xs, table_q_xs) = q_xs.split_at(lookups.len());
+    for (lookup, input_p_x, input_q_x, table_q_x) in izip!(lookups, &p_xs, input_q_xs, table_q_xs) {
+        let (inputs, tables) = lookup
+            .iter()
+            .map(|(input, table)| (input, table))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        let [input, table] = [inputs, tables].map(|expressions| {
+            let expression = Expression::distribute_powers(expressions, &beta);
+            evaluate::<_, BinaryField>(&expression, num_vars, &query_evals, challenges, &[], &x)
+        });
+        if *input_p_x != F::ONE || *input_q_x != *gamma + input || *table_q_x != *gamma + table {
+            return Err(Error::InvalidSnark(
+                "Unmatched between lookup and query evaluation".to_string(),
+            ));
+        }
+    }
+
+    let points = chain![[x], query_points].collect_vec();
+    let evals = chain![
+        izip!(m_offset.., &p_xs[lookups.len()..]).map(|(poly, eval)| Evaluation::new(
+            poly,
+            point_offset,
+            *eval
+        )),
+        query_evals_for_pcs.into_iter().map(|eval| {
+            Evaluation::new(eval.poly(), point_offset + 1 + eval.point(), *eval.value())
+        }),
+    ]
+    .collect();
+    Ok((points, evals))
+}
+
+/// Verifies decomposable lookups proven by [`prove_lasso`], then returns points
+/// and evaluations to be verified by PCS, and point of each subtable with
+/// evaluation of its multiplicities.
+///
+/// [`prove_lasso`]: crate::backend::hyperplonk::prover::prove_lasso
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn verify_lasso<F: PrimeField>(
+    num_vars: usize,
+    chunks: &[(Expression<F>, Expression<F>, Subtable)],
+    instances: &[Vec<F>],
+    challenges: &[F],
+    beta: &F,
+    gamma: &F,
+    point_offset: usize,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>, Vec<(Vec<F>, F)>), Error> {
+    if chunks.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let input_p_0s = transcript.read_field_elements(chunks.len())?;
+    let input_q_0s = transcript.read_field_elements(chunks.len())?;
+
+    let (input_p_xs, input_q_xs, x) = verify_fractional_sum_check(
+        num_vars,
+        input_p_0s.iter().copied().map(Some),
+        input_q_0s.iter().copied().map(Some),
+        transcript,
+    )?;
+
+    let expression = lasso_expression(chunks);
+    let pcs_query = pcs_query(&expression, instances.len());
+    let input_evals = izip!(&pcs_query, transcript.read_field_elements(pcs_query.len())?)
+        .map(|(query, eval)| Evaluation::new(query.poly(), point_offset, eval))
+        .collect_vec();
+
+    let query_evals = chain![
+        instance_evals::<_, BinaryField>(num_vars, &expression, instances, &x),
+        izip!(
+            pcs_query,
+            input_evals.iter().map(Evaluation::value).copied()
+        ),
+    ]
+    .collect::<BTreeMap<_, _>>();
+    for ((index, value, _), input_p_x, input_q_x) in izip!(chunks, &input_p_xs, &input_q_xs) {
+        let [index, value] = [index, value].map(|expression| {
+            evaluate::<_, BinaryField>(expression, num_vars, &query_evals, challenges, &[], &x)
+        });
+        if *input_p_x != F::ONE || *input_q_x != *gamma + index + *beta * value {
+            return Err(Error::InvalidSnark(
+                "Unmatched between lasso input and query evaluation".to_string(),
+            ));
+        }
+    }
+
+    let mut m_openings = Vec::with_capacity(chunks.len());
+    for ((_, _, subtable), input_p_0, input_q_0) in izip!(chunks, &input_p_0s, &input_q_0s) {
+        let table_p_0 = transcript.read_field_element()?;
+        let table_q_0 = transcript.read_field_element()?;
+        if *input_p_0 * table_q_0 != table_p_0 * input_q_0 {
+            return Err(Error::InvalidSnark(
+                "Unmatched between lasso input and subtable".to_string(),
+            ));
+        }
+
+        let (table_p_xs, table_q_xs, x) = verify_fractional_sum_check(
+            subtable.num_vars(),
+            [Some(table_p_0)],
+            [Some(table_q_0)],
+            transcript,
+        )?;
+
+        let index = Subtable::Identity {
+            num_bits: subtable.num_vars(),
+        }
+        .evaluate(&x);
+        if table_q_xs[0] != *gamma + index + *beta * subtable.evaluate(&x) {
+            return Err(Error::InvalidSnark(
+                "Unmatched between lasso subtable and its evaluation".to_string(),
+            ));
+        }
+
+        m_openings.push((x, table_p_xs[0]));
+    }
+
+    Ok((vec![x], input_evals, m_openings))
+}
+
+/// Groups openings of multiplicities of subtables returned by [`verify_lasso`]
+/// by number of variables of subtables, where each group has indices of its
+/// chunks, points padded with zeros to number of variables given in
+/// `lasso_pcs`, and evaluations to be verified by PCS.
+#[allow(clippy::type_complexity)]
+pub(crate) fn lasso_m_openings<F: PrimeField, T>(
+    chunks: &[(Expression<F>, Expression<F>, Subtable)],
+    lasso_pcs: &BTreeMap<usize, (usize, T)>,
+    m_openings: Vec<(Vec<F>, F)>,
+) -> BTreeMap<usize, (Vec<usize>, Vec<Vec<F>>, Vec<Evaluation<F>>)> {
+    let mut groups = BTreeMap::<_, (Vec<_>, Vec<_>, Vec<_>)>::new();
+    for (idx, ((_, _, subtable), (x, eval))) in izip!(chunks, m_openings).enumerate() {
+        let (num_vars, _) = lasso_pcs[&subtable.num_vars()];
+        let (idxs, points, evals) = groups.entry(subtable.num_vars()).or_default();
+        evals.push(Evaluation::new(idxs.len(), points.len(), eval));
+        idxs.push(idx);
+        points.push(chain![x, iter::repeat(F::ZERO)].take(num_vars).collect());
+    }
+    groups
+}
+
+/// Returns sum of index and value expressions of all chunks, which is only
+/// used to collect queries.
+pub(crate) fn lasso_expression<F: PrimeField>(
+    chunks: &[(Expression<F>, Expression<F>, Subtable)],
+) -> Expression<F> {
+    chunks.iter().map(|(index, value, _)| index + value).sum()
+}
+
+/// Verifies permutation proven by [`prove_permutation_gkr`], then returns
+/// points and evaluations to be verified by PCS.
+///
+/// [`prove_permutation_gkr`]: crate::backend::hyperplonk::prover::prove_permutation_gkr
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(crate) fn verify_permutation_gkr<F: PrimeField>(
+    num_vars: usize,
+    permutation_polys: &[usize],
+    instances: &[Vec<F>],
+    beta: &F,
+    gamma: &F,
+    permutation_offset: usize,
+    point_offset: usize,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<(Vec<Vec<F>>, Vec<Evaluation<F>>), Error> {
+    if permutation_polys.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let v_0s = transcript.read_field_elements(2 * permutation_polys.len())?;
+    let (id_0s, permutation_0s) = v_0s.split_at(permutation_polys.len());
+    if id_0s.iter().product::<F>() != permutation_0s.iter().product::<F>() {
+        return Err(Error::InvalidSnark(
+            "Unmatched between permutation grand products".to_string(),
+        ));
+    }
+
+    let (v_xs, x) = verify_grand_product(num_vars, v_0s.iter().copied().map(Some), transcript)?;
+
+    let expression = permutation_expression(permutation_polys, permutation_offset);
+    let (query_evals, points, evals) =
+        read_evaluations(num_vars, &expression, instances, &x, transcript)?;
+
+    let [beta, gamma] = &[beta, gamma].map(|challenge| Expression::Constant(*challenge));
+    let (id_xs, permutation_xs) = v_xs.split_at(permutation_polys.len());
+    for (idx, (poly, id_x, permutation_x)) in
+        izip!(permutation_polys, id_xs, permutation_xs).enumerate()
+    {
+        let value = &Expression::Polynomial(Query::new(*poly, Rotation::cur()));
+        let id = Expression::Constant(F::from((idx << num_vars) as u64)) + Expression::identity();
+        let permutation =
+            Expression::Polynomial(Query::new(permutation_offset + idx, Rotation::cur()));
+        let [id, permutation] = [id, permutation].map(|expression| {
+            let expression = value + beta * expression + gamma;
+            evaluate::<_, BinaryField>(&expression, num_vars, &query_evals, &[], &[], &x)
+        });
+        if *id_x != id || *permutation_x != permutation {
+            return Err(Error::InvalidSnark(
+                "Unmatched between permutation and query evaluation".to_string(),
+            ));
+        }
+    }
+
+    let evals = evals
+        .into_iter()
+        .map(|eval| Evaluation::new(eval.poly(), point_offset + eval.point(), *eval.value()))
+        .collect();
+    Ok((points, evals))
+}
+
+/// Returns sum of queries of all permutation polynomials and their
+/// permutations, which is only used to collect queries.
+pub(crate) fn permutation_expression<F: PrimeField>(
+    permutation_polys: &[usize],
+    permutation_offset: usize,
+) -> Expression<F> {
+    izip!(permutation_polys, permutation_offset..)
+        .flat_map(|(poly, permutation)| [*poly, permutation])
+        .map(|poly| Expression::Polynomial(Query::new(poly, Rotation::cur())))
+        .sum()
+}
+
+/// Returns sum of input and table expressions of all lookups, which is only
+/// used to collect queries.
+pub(crate) fn lookup_expression<F: PrimeField>(
+    lookups: &[Vec<(Expression<F>, Expression<F>)>],
+) -> Expression<F> {
+    lookups
+        .iter()
+        .flatten()
+        .map(|(input, table)| input + table)
+        .sum()
 }
 
 pub(crate) fn instance_evals<F: PrimeField, R: Rotatable + From<usize>>(
@@ -134,7 +483,7 @@ pub(crate) fn pcs_query<F: PrimeField>(
     used_query
 }
 
-pub(super) fn points<F: PrimeField>(pcs_query: &BTreeSet<Query>, x: &[F]) -> Vec<Vec<F>> {
+pub(crate) fn points<F: PrimeField>(pcs_query: &BTreeSet<Query>, x: &[F]) -> Vec<Vec<F>> {
     pcs_query
         .iter()
         .map(Query::rotation)
@@ -0,0 +1,190 @@
+use crate::util::{
+    arithmetic::{powers, Field, PrimeField},
+    chain,
+    expression::Expression,
+    izip, Deserialize, Serialize,
+};
+
+/// Lookup argument in [`PlonkishCircuitInfo`].
+///
+/// [`PlonkishCircuitInfo`]: crate::backend::PlonkishCircuitInfo
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Lookup<F> {
+    /// Vector lookup which contains vector of tuples representing the input
+    /// and table respectively. Table could be any expression including witness
+    /// polynomials, and could have duplicate entries. When input is masked by
+    /// selector, table needs to have the zero tuple on some row as well, which
+    /// can be done by masking table with another selector.
+    Vector(Vec<(Expression<F>, Expression<F>)>),
+    /// Lasso-style lookup into a table too large to be materialized, see
+    /// [`DecomposableLookup`].
+    Decomposable(DecomposableLookup<F>),
+}
+
+impl<F> Lookup<F> {
+    pub fn as_vector(&self) -> Option<&Vec<(Expression<F>, Expression<F>)>> {
+        match self {
+            Self::Vector(lookup) => Some(lookup),
+            Self::Decomposable(_) => None,
+        }
+    }
+
+    pub fn as_decomposable(&self) -> Option<&DecomposableLookup<F>> {
+        match self {
+            Self::Vector(_) => None,
+            Self::Decomposable(lookup) => Some(lookup),
+        }
+    }
+
+    /// Returns all expressions over polynomials of circuit.
+    pub fn expressions(&self) -> Vec<&Expression<F>> {
+        match self {
+            Self::Vector(lookup) => lookup
+                .iter()
+                .flat_map(|(input, table)| [input, table])
+                .collect(),
+            Self::Decomposable(lookup) => chain![
+                lookup
+                    .chunks
+                    .iter()
+                    .flat_map(|(index, value, _)| [index, value]),
+                [&lookup.output],
+            ]
+            .collect(),
+        }
+    }
+}
+
+/// Lookup into table `T` which is decomposed as
+/// `T[i] = g(T_0[i_0], T_1[i_1], ...)`, where each chunk `(i_j, T_j[i_j])` is
+/// looked up from a small [`Subtable`] `T_j`, and `g` is the combining
+/// function.
+///
+/// Expressions of chunks can only query polynomials on current rotation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecomposableLookup<F> {
+    /// Tuples of index, value and subtable of each chunk.
+    pub chunks: Vec<(Expression<F>, Expression<F>, Subtable)>,
+    /// Output of the lookup, which is constrained to be `combine` on every
+    /// row.
+    pub output: Expression<F>,
+    /// Combining function, where value of the `i`-th chunk is represented by
+    /// query of polynomial `i`.
+    pub combine: Expression<F>,
+}
+
+impl<F: Field> DecomposableLookup<F> {
+    /// Returns constraint `output - combine` with values of chunks substituted
+    /// into `combine`.
+    pub fn constraint(&self) -> Expression<F> {
+        let combined = self.combine.evaluate(
+            &|constant| Expression::Constant(constant),
+            &|common_poly| Expression::CommonPolynomial(common_poly),
+            &|query| self.chunks[query.poly()].1.clone(),
+            &|challenge| Expression::Challenge(challenge),
+            &|value| -value,
+            &|lhs, rhs| lhs + rhs,
+            &|lhs, rhs| lhs * rhs,
+            &|value, scalar| value * scalar,
+        );
+        self.output.clone() - combined
+    }
+}
+
+/// Subtable with multilinear extension evaluable in `O(num_vars)`, so the
+/// verifier doesn't need its commitment. Bits of index are ordered from the
+/// least significant one, and binary operation on `(x, y)` takes
+/// `x + (y << num_bits)` as index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Subtable {
+    /// Table of `x` for range check of `num_bits` bits.
+    Identity { num_bits: usize },
+    /// Table of `x & y`.
+    And { num_bits: usize },
+    /// Table of `x | y`.
+    Or { num_bits: usize },
+    /// Table of `x ^ y`.
+    Xor { num_bits: usize },
+}
+
+impl Subtable {
+    pub fn num_vars(&self) -> usize {
+        match self {
+            Self::Identity { num_bits } => *num_bits,
+            Self::And { num_bits } | Self::Or { num_bits } | Self::Xor { num_bits } => 2 * num_bits,
+        }
+    }
+
+    /// Returns value at `idx`.
+    pub fn value(&self, idx: usize) -> u64 {
+        let split = |num_bits: usize| (idx & ((1 << num_bits) - 1), idx >> num_bits);
+        (match *self {
+            Self::Identity { .. } => idx,
+            Self::And { num_bits } => {
+                let (x, y) = split(num_bits);
+                x & y
+            }
+            Self::Or { num_bits } => {
+                let (x, y) = split(num_bits);
+                x | y
+            }
+            Self::Xor { num_bits } => {
+                let (x, y) = split(num_bits);
+                x ^ y
+            }
+        }) as u64
+    }
+
+    pub fn evals<F: PrimeField>(&self) -> Vec<F> {
+        (0..1 << self.num_vars())
+            .map(|idx| F::from(self.value(idx)))
+            .collect()
+    }
+
+    /// Returns evaluation of multilinear extension at `x`.
+    pub fn evaluate<F: PrimeField>(&self, x: &[F]) -> F {
+        assert_eq!(x.len(), self.num_vars());
+
+        let bit_op = |op: fn(&F, &F) -> F| {
+            let (lhs, rhs) = x.split_at(x.len() >> 1);
+            izip!(powers(F::from(2)), lhs, rhs)
+                .map(|(power, lhs, rhs)| power * op(lhs, rhs))
+                .sum::<F>()
+        };
+        match self {
+            Self::Identity { .. } => izip!(powers(F::from(2)), x)
+                .map(|(power, x)| power * x)
+                .sum::<F>(),
+            Self::And { .. } => bit_op(|x, y| *x * y),
+            Self::Or { .. } => bit_op(|x, y| *x + y - *x * y),
+            Self::Xor { .. } => bit_op(|x, y| *x + y - (*x * y).double()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::lookup::Subtable,
+        poly::multilinear::MultilinearPolynomial,
+        util::test::{rand_vec, seeded_std_rng},
+    };
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn subtable_evaluate() {
+        let mut rng = seeded_std_rng();
+        for num_bits in 1..5 {
+            for subtable in [
+                Subtable::Identity { num_bits },
+                Subtable::And { num_bits },
+                Subtable::Or { num_bits },
+                Subtable::Xor { num_bits },
+            ] {
+                let poly = MultilinearPolynomial::new(subtable.evals::<Fr>());
+                let x = rand_vec(subtable.num_vars(), &mut rng);
+                assert_eq!(subtable.evaluate(&x), poly.evaluate(&x));
+            }
+        }
+    }
+}
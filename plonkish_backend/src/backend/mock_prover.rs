@@ -0,0 +1,452 @@
+use crate::{
+    backend::{Lookup, PlonkishCircuit, PlonkishCircuitInfo},
+    util::{
+        arithmetic::PrimeField,
+        chain,
+        expression::{rotate::Rotatable, CommonPolynomial, Expression, Query},
+        izip, Itertools,
+    },
+    Error,
+};
+use rand::RngCore;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    iter,
+};
+
+/// Failure found by [`MockProver::verify`], where `row` is the raw index of
+/// polynomial evaluations and cells are represented by `(poly, row)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerifyFailure<F> {
+    /// Constraint is not zero on `row`, with values of all its queries.
+    Constraint {
+        constraint: usize,
+        row: usize,
+        cells: Vec<(Query, F)>,
+    },
+    /// Input tuple of lookup on `row` doesn't exist in the table. For
+    /// decomposable lookup, input consists of index and value of each chunk
+    /// followed by the output, which fails when any chunk doesn't exist in its
+    /// subtable or the output doesn't match the combining function.
+    Lookup {
+        lookup: usize,
+        row: usize,
+        input: Vec<F>,
+    },
+    /// Input tuple of shuffle on `row` has no remaining occurrence in the
+    /// shuffle tuples.
+    Shuffle {
+        shuffle: usize,
+        row: usize,
+        input: Vec<F>,
+    },
+    /// Cell in permutation cycle has value different from the first cell.
+    Permutation {
+        cycle: usize,
+        cells: [(usize, usize); 2],
+        values: [F; 2],
+    },
+}
+
+/// Checks witness of [`PlonkishCircuit`] against [`PlonkishCircuitInfo`] by
+/// evaluating constraints, lookups, shuffles and permutations row by row in the order of
+/// `R`, so failures can be located without running any backend.
+#[derive(Debug)]
+pub struct MockProver<F, R> {
+    circuit_info: PlonkishCircuitInfo<F>,
+    rotatable: R,
+    polys: Vec<Vec<F>>,
+    challenges: Vec<F>,
+}
+
+impl<F, R> MockProver<F, R>
+where
+    F: PrimeField + Hash,
+    R: Rotatable + From<usize>,
+{
+    /// Synthesizes witness polynomials of `circuit` phase by phase, with
+    /// challenges sampled from `rng`.
+    pub fn run(
+        circuit_info: &PlonkishCircuitInfo<F>,
+        circuit: &impl PlonkishCircuit<F>,
+        mut rng: impl RngCore,
+    ) -> Result<Self, Error> {
+        circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
+
+        let n = 1 << circuit_info.k;
+        let rotatable = R::from(circuit_info.k);
+
+        let instances = circuit.instances();
+        if instances.len() != circuit_info.num_instances.len()
+            || instances
+                .iter()
+                .zip(circuit_info.num_instances.iter())
+                .any(|(instances, num_instances)| instances.len() != *num_instances)
+        {
+            return Err(Error::InvalidSnark(
+                "Unmatched number of instances".to_string(),
+            ));
+        }
+        let usable_indices = rotatable.usable_indices();
+        let instance_polys = instances.iter().map(|instances| {
+            let mut poly = vec![F::ZERO; n];
+            for (b, instance) in usable_indices.iter().zip(instances) {
+                poly[*b] = *instance;
+            }
+            poly
+        });
+
+        let mut witness_polys = Vec::with_capacity(circuit_info.num_witness_polys.iter().sum());
+        let mut challenges = Vec::with_capacity(circuit_info.num_challenges.iter().sum());
+        for (phase, (num_witness_polys, num_challenges)) in circuit_info
+            .num_witness_polys
+            .iter()
+            .zip_eq(circuit_info.num_challenges.iter())
+            .enumerate()
+        {
+            let polys = circuit.synthesize(phase, &challenges)?;
+            if polys.len() != *num_witness_polys || polys.iter().any(|poly| poly.len() != n) {
+                return Err(Error::InvalidSnark(format!(
+                    "Unmatched number or length of witness polys in phase {phase}"
+                )));
+            }
+            witness_polys.extend(polys);
+            challenges.extend(iter::repeat_with(|| F::random(&mut rng)).take(*num_challenges));
+        }
+
+        let polys = chain![
+            instance_polys,
+            circuit_info.preprocess_polys.iter().cloned(),
+            witness_polys,
+        ]
+        .collect();
+
+        Ok(Self {
+            circuit_info: circuit_info.clone(),
+            rotatable,
+            polys,
+            challenges,
+        })
+    }
+
+    pub fn verify(&self) -> Result<(), Vec<VerifyFailure<F>>> {
+        let failures = chain![
+            self.constraint_failures(),
+            self.lookup_failures(),
+            self.shuffle_failures(),
+            self.permutation_failures(),
+        ]
+        .collect_vec();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    fn constraint_failures(&self) -> Vec<VerifyFailure<F>> {
+        let rows = 0..1 << self.circuit_info.k;
+        self.circuit_info
+            .constraints
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, constraint)| {
+                rows.clone()
+                    .filter(move |row| self.evaluate(constraint, *row) != F::ZERO)
+                    .map(move |row| VerifyFailure::Constraint {
+                        constraint: idx,
+                        row,
+                        cells: constraint
+                            .used_query()
+                            .into_iter()
+                            .map(|query| (query, self.query(query, row)))
+                            .collect(),
+                    })
+            })
+            .collect()
+    }
+
+    fn lookup_failures(&self) -> Vec<VerifyFailure<F>> {
+        let rows = 0..1 << self.circuit_info.k;
+        let evaluate = |row, expressions: &[&Expression<F>]| {
+            expressions
+                .iter()
+                .map(|expression| self.evaluate(expression, row))
+                .collect_vec()
+        };
+        self.circuit_info
+            .lookups
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, lookup)| {
+                let failed_rows = match lookup {
+                    Lookup::Vector(vector) => {
+                        let (inputs, tables) = vector
+                            .iter()
+                            .map(|(input, table)| (input, table))
+                            .unzip::<_, _, Vec<_>, Vec<_>>();
+                        let table = rows
+                            .clone()
+                            .map(|row| evaluate(row, &tables))
+                            .collect::<HashSet<_>>();
+                        rows.clone()
+                            .map(|row| (row, evaluate(row, &inputs)))
+                            .filter(|(_, input)| !table.contains(input))
+                            .collect_vec()
+                    }
+                    Lookup::Decomposable(decomposable) => {
+                        let subtables = decomposable
+                            .chunks
+                            .iter()
+                            .map(|(_, _, subtable)| {
+                                (0..1 << subtable.num_vars())
+                                    .map(|idx| (F::from(idx as u64), F::from(subtable.value(idx))))
+                                    .collect::<HashMap<_, _>>()
+                            })
+                            .collect_vec();
+                        let inputs = lookup.expressions();
+                        let constraint = decomposable.constraint();
+                        rows.clone()
+                            .map(|row| (row, evaluate(row, &inputs)))
+                            .filter(|(row, input)| {
+                                izip!(&subtables, input.iter().tuples()).any(
+                                    |(subtable, (index, value))| subtable.get(index) != Some(value),
+                                ) || self.evaluate(&constraint, *row) != F::ZERO
+                            })
+                            .collect_vec()
+                    }
+                };
+                failed_rows
+                    .into_iter()
+                    .map(move |(row, input)| VerifyFailure::Lookup {
+                        lookup: idx,
+                        row,
+                        input,
+                    })
+            })
+            .collect()
+    }
+
+    fn shuffle_failures(&self) -> Vec<VerifyFailure<F>> {
+        let rows = 0..1 << self.circuit_info.k;
+        self.circuit_info
+            .shuffles
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, shuffle)| {
+                let evaluate = |row, expressions: &[&Expression<F>]| {
+                    expressions
+                        .iter()
+                        .map(|expression| self.evaluate(expression, row))
+                        .collect_vec()
+                };
+                let (inputs, shuffles) = shuffle
+                    .iter()
+                    .map(|(input, shuffle)| (input, shuffle))
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+                let mut counts = HashMap::<_, usize>::new();
+                for row in rows.clone() {
+                    *counts.entry(evaluate(row, &shuffles)).or_default() += 1;
+                }
+                rows.clone()
+                    .map(|row| (row, evaluate(row, &inputs)))
+                    .filter(|(_, input)| match counts.get_mut(input) {
+                        Some(count) if *count > 0 => {
+                            *count -= 1;
+                            false
+                        }
+                        _ => true,
+                    })
+                    .map(|(row, input)| VerifyFailure::Shuffle {
+                        shuffle: idx,
+                        row,
+                        input,
+                    })
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    fn permutation_failures(&self) -> Vec<VerifyFailure<F>> {
+        self.circuit_info
+            .permutations
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, cycle)| {
+                let Some((first, cycle)) = cycle.split_first() else {
+                    return Vec::new();
+                };
+                let value = |(poly, row): (usize, usize)| self.polys[poly][row];
+                cycle
+                    .iter()
+                    .filter(|cell| value(**cell) != value(*first))
+                    .map(|cell| VerifyFailure::Permutation {
+                        cycle: idx,
+                        cells: [*first, *cell],
+                        values: [value(*first), value(*cell)],
+                    })
+                    .collect_vec()
+            })
+            .collect()
+    }
+
+    fn query(&self, query: Query, row: usize) -> F {
+        self.polys[query.poly()][self.rotatable.rotate(row, query.rotation())]
+    }
+
+    fn evaluate(&self, expression: &Expression<F>, row: usize) -> F {
+        expression.evaluate(
+            &|constant| constant,
+            &|common_poly| match common_poly {
+                CommonPolynomial::Identity => F::from(row as u64),
+                CommonPolynomial::Lagrange(i) => {
+                    if self.rotatable.nth(i) == row {
+                        F::ONE
+                    } else {
+                        F::ZERO
+                    }
+                }
+                CommonPolynomial::EqXY(_) => unreachable!(),
+            },
+            &|query| self.query(query, row),
+            &|challenge| self.challenges[challenge],
+            &|value| -value,
+            &|lhs, rhs| lhs + &rhs,
+            &|lhs, rhs| lhs * &rhs,
+            &|value, scalar| value * &scalar,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::{
+            hyperplonk::util::{
+                rand_vanilla_plonk_circuit, rand_vanilla_plonk_w_decomposable_lookup_circuit,
+                rand_vanilla_plonk_w_dynamic_lookup_circuit, rand_vanilla_plonk_w_lookup_circuit,
+                rand_vanilla_plonk_w_shuffle_circuit,
+            },
+            mock::MockCircuit,
+            mock_prover::{MockProver, VerifyFailure},
+            PlonkishCircuit,
+        },
+        util::{
+            arithmetic::Field,
+            expression::rotate::{BinaryField, Lexical},
+            test::seeded_std_rng,
+        },
+    };
+    use halo2_curves::bn256::Fr;
+
+    macro_rules! tests {
+        ($suffix:ident, $rotatable:ty) => {
+            paste::paste! {
+                #[test]
+                fn [<vanilla_plonk_w_ $suffix>]() {
+                    for num_vars in 2..10 {
+                        let (circuit_info, circuit) = rand_vanilla_plonk_circuit::<Fr, $rotatable>(num_vars, seeded_std_rng(), seeded_std_rng());
+                        let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                        assert_eq!(prover.verify(), Ok(()));
+                    }
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_lookup_w_ $suffix>]() {
+                    for num_vars in 2..10 {
+                        let (circuit_info, circuit) = rand_vanilla_plonk_w_lookup_circuit::<Fr, $rotatable>(num_vars, seeded_std_rng(), seeded_std_rng());
+                        let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                        assert_eq!(prover.verify(), Ok(()));
+                    }
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_dynamic_lookup_w_ $suffix>]() {
+                    for num_vars in 2..10 {
+                        let (circuit_info, circuit) = rand_vanilla_plonk_w_dynamic_lookup_circuit::<Fr, $rotatable>(num_vars, seeded_std_rng(), seeded_std_rng());
+                        let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                        assert_eq!(prover.verify(), Ok(()));
+                    }
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_bad_dynamic_lookup_w_ $suffix>]() {
+                    let (circuit_info, circuit) = rand_vanilla_plonk_w_dynamic_lookup_circuit::<Fr, $rotatable>(6, seeded_std_rng(), seeded_std_rng());
+                    let mut witnesses = [0, 1].map(|phase| circuit.synthesize(phase, &[]).unwrap());
+                    witnesses[1][0].iter_mut().for_each(|w_l| *w_l += Fr::ONE);
+                    let circuit = MockCircuit::new_w_phases(circuit.instances().to_vec(), witnesses.to_vec());
+                    let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                    let failures = prover.verify().unwrap_err();
+                    assert!(failures.iter().any(|failure| matches!(
+                        failure,
+                        VerifyFailure::Lookup { lookup: 0, .. }
+                    )));
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_decomposable_lookup_w_ $suffix>]() {
+                    for num_vars in 2..10 {
+                        let (circuit_info, circuit) = rand_vanilla_plonk_w_decomposable_lookup_circuit::<Fr, $rotatable>(num_vars, seeded_std_rng(), seeded_std_rng());
+                        let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                        assert_eq!(prover.verify(), Ok(()));
+                    }
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_bad_decomposable_lookup_w_ $suffix>]() {
+                    let (circuit_info, circuit) = rand_vanilla_plonk_w_decomposable_lookup_circuit::<Fr, $rotatable>(4, seeded_std_rng(), seeded_std_rng());
+                    let mut witnesses = circuit.synthesize(0, &[]).unwrap();
+                    witnesses[9].iter_mut().for_each(|z| *z += Fr::ONE);
+                    let circuit = MockCircuit::new(circuit.instances().to_vec(), witnesses);
+                    let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                    let failures = prover.verify().unwrap_err();
+                    assert!(failures.iter().any(|failure| matches!(
+                        failure,
+                        VerifyFailure::Lookup { lookup: 0, .. }
+                    )));
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_shuffle_w_ $suffix>]() {
+                    for num_vars in 2..10 {
+                        let (circuit_info, circuit) = rand_vanilla_plonk_w_shuffle_circuit::<Fr, $rotatable>(num_vars, seeded_std_rng(), seeded_std_rng());
+                        let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                        assert_eq!(prover.verify(), Ok(()));
+                    }
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_bad_shuffle_w_ $suffix>]() {
+                    let (circuit_info, circuit) = rand_vanilla_plonk_w_shuffle_circuit::<Fr, $rotatable>(4, seeded_std_rng(), seeded_std_rng());
+                    let mut witnesses = circuit.synthesize(0, &[]).unwrap();
+                    witnesses[3][1] += Fr::ONE;
+                    let circuit = MockCircuit::new(circuit.instances().to_vec(), witnesses);
+                    let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                    let failures = prover.verify().unwrap_err();
+                    assert!(failures.iter().any(|failure| matches!(
+                        failure,
+                        VerifyFailure::Shuffle { shuffle: 0, .. }
+                    )));
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_bad_witness_w_ $suffix>]() {
+                    let (circuit_info, circuit) = rand_vanilla_plonk_circuit::<Fr, $rotatable>(4, seeded_std_rng(), seeded_std_rng());
+                    let mut witnesses = circuit.synthesize(0, &[]).unwrap();
+                    witnesses[2][1] += Fr::ONE;
+                    let circuit = MockCircuit::new(circuit.instances().to_vec(), witnesses);
+                    let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                    let failures = prover.verify().unwrap_err();
+                    assert!(failures.iter().any(|failure| matches!(
+                        failure,
+                        VerifyFailure::Constraint { constraint: 0, row: 1, .. }
+                    )));
+                }
+            }
+        };
+    }
+
+    tests!(binary_field, BinaryField);
+    tests!(lexical, Lexical);
+}
@@ -1,21 +1,24 @@
 use crate::{
     backend::{
-        hyperplonk::{HyperPlonkProverParam, HyperPlonkVerifierParam},
+        hyperplonk::{
+            HyperPlonkProverParam, HyperPlonkVerifierParam, LookupStrategy::LogUp,
+            PermutationStrategy::Plonk, SumCheckStrategy::Evaluations,
+        },
         unihyperplonk::{
             preprocessor::{batch_size, preprocess},
             prover::{
                 instance_polys, lookup_compressed_polys, lookup_h_polys, lookup_m_polys,
-                permutation_z_polys, prove_zero_check,
+                permutation_z_polys, prove_zero_check, shuffle_h_polys,
             },
             verifier::verify_zero_check,
         },
         PlonkishBackend, PlonkishCircuit, PlonkishCircuitInfo, WitnessEncoding,
     },
-    pcs::{Additive, PolynomialCommitmentScheme},
+    pcs::{Additive, DeferredKzg, Evaluation, KzgAccumulator, PolynomialCommitmentScheme},
     piop::multilinear_eval::ph23::{self, s_polys},
     poly::{multilinear::MultilinearPolynomial, univariate::UnivariatePolynomial},
     util::{
-        arithmetic::{powers, WithSmallOrderMulGroup},
+        arithmetic::{powers, MultiMillerLoop, WithSmallOrderMulGroup},
         chain, end_timer,
         expression::rotate::{Lexical, Rotatable},
         start_timer,
@@ -28,9 +31,12 @@ use rand::RngCore;
 use std::{borrow::Cow, fmt::Debug, hash::Hash, iter, marker::PhantomData, ops::Deref};
 
 pub(crate) mod preprocessor;
+pub(crate) mod proof;
 pub(crate) mod prover;
 pub(crate) mod verifier;
 
+pub use proof::UniHyperPlonkProof;
+
 #[derive(Clone, Debug)]
 pub struct UniHyperPlonk<Pcs, const ADDITIVE_PCS: bool>(PhantomData<Pcs>);
 
@@ -93,11 +99,12 @@ where
         circuit_info: &PlonkishCircuitInfo<F>,
         rng: impl RngCore,
     ) -> Result<Pcs::Param, Error> {
-        assert!(circuit_info.is_well_formed());
+        circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
+        circuit_info.ensure_only_vector_lookups()?;
 
         let num_vars = circuit_info.k;
         let poly_size = 1 << num_vars;
-        let batch_size = batch_size(circuit_info);
+        let batch_size = batch_size(circuit_info, LogUp, Plonk);
         Pcs::setup(poly_size, batch_size, rng)
     }
 
@@ -105,9 +112,16 @@ where
         param: &Pcs::Param,
         circuit_info: &PlonkishCircuitInfo<F>,
     ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
-        let (pp, vp) = preprocess(param, circuit_info, |pp, polys| {
-            batch_commit::<_, Pcs>(pp, polys)
-        })?;
+        circuit_info.ensure_only_vector_lookups()?;
+
+        let (pp, vp) = preprocess(
+            param,
+            circuit_info,
+            LogUp,
+            Plonk,
+            Evaluations,
+            |pp, polys| batch_commit::<_, Pcs>(pp, polys),
+        )?;
         let s_polys = s_polys(circuit_info.k);
         Ok((
             UniHyperPlonkProverParam { pp, s_polys },
@@ -121,6 +135,8 @@ where
         transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
         _: impl RngCore,
     ) -> Result<(), Error> {
+        transcript.common_field_element(&pp.vp_digest)?;
+
         let instance_polys = {
             let instances = circuit.instances();
             for (num_instances, instances) in pp.num_instances.iter().zip_eq(instances) {
@@ -168,12 +184,19 @@ where
 
         let beta = transcript.squeeze_challenge();
 
-        let timer = start_timer(|| format!("lookup_compressed_polys-{}", pp.lookups.len()));
-        let lookup_compressed_polys = {
-            let max_lookup_width = pp.lookups.iter().map(Vec::len).max().unwrap_or_default();
-            let betas = powers(beta).take(max_lookup_width).collect_vec();
-            lookup_compressed_polys::<_, Lexical>(&pp.lookups, &polys, &challenges, &betas)
-        };
+        let timer = start_timer(|| {
+            let num_lookups = pp.lookups.len() + pp.shuffles.len();
+            format!("lookup_compressed_polys-{num_lookups}")
+        });
+        let max_lookup_width = chain![&pp.lookups, &pp.shuffles]
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        let betas = powers(beta).take(max_lookup_width).collect_vec();
+        let [lookup_compressed_polys, shuffle_compressed_polys] =
+            [&pp.lookups, &pp.shuffles].map(|lookups| {
+                lookup_compressed_polys::<_, Lexical>(lookups, &polys, &challenges, &betas)
+            });
         end_timer(timer);
 
         let timer = start_timer(|| format!("lookup_m_polys-{}", pp.lookups.len()));
@@ -191,6 +214,10 @@ where
         let lookup_h_polys = lookup_h_polys(&lookup_compressed_polys, &lookup_m_polys, &gamma);
         end_timer(timer);
 
+        let timer = start_timer(|| format!("shuffle_h_polys-{}", pp.shuffles.len()));
+        let shuffle_h_polys = shuffle_h_polys(&shuffle_compressed_polys, &gamma);
+        end_timer(timer);
+
         let timer = start_timer(|| format!("permutation_z_polys-{}", pp.permutation_polys.len()));
         let permutation_z_polys = permutation_z_polys::<_, Lexical>(
             pp.num_permutation_z_polys,
@@ -202,7 +229,7 @@ where
         end_timer(timer);
 
         let lookup_h_permutation_z_polys =
-            chain![lookup_h_polys, permutation_z_polys].collect_vec();
+            chain![lookup_h_polys, shuffle_h_polys, permutation_z_polys].collect_vec();
         let (lookup_h_permutation_z_polys, lookup_h_permutation_z_comms) =
             batch_commit_and_write::<_, Pcs>(&pp.pcs, lookup_h_permutation_z_polys, transcript)?;
 
@@ -267,6 +294,45 @@ where
         transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
         _: impl RngCore,
     ) -> Result<(), Error> {
+        let (comms, points, evals) = Self::verify_reduction(vp, instances, transcript)?;
+        Pcs::batch_verify(&vp.pcs, &comms, &points, &evals, transcript)
+    }
+}
+
+impl<Pcs> UniHyperPlonk<Pcs, true> {
+    /// Same as [`PlonkishBackend::verify`] but returns accumulator of the
+    /// pairing check of PCS, which can be folded with others by
+    /// [`KzgAccumulator::fold`] and finally checked by [`DeferredKzg::decide`].
+    pub fn verify_deferred<M>(
+        vp: &UniHyperPlonkVerifierParam<M::Scalar, Pcs>,
+        instances: &[Vec<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error>
+    where
+        M: MultiMillerLoop,
+        M::Scalar: WithSmallOrderMulGroup<3> + Hash + Serialize + DeserializeOwned,
+        Pcs: DeferredKzg<M, Polynomial = UnivariatePolynomial<M::Scalar>>,
+        Pcs::Commitment: Additive<M::Scalar>,
+    {
+        let (comms, points, evals) = Self::verify_reduction(vp, instances, transcript)?;
+        Pcs::batch_verify_deferred(&vp.pcs, &comms, &points, &evals, transcript)
+    }
+
+    /// Verifies everything but the final PCS batch opening, and returns
+    /// commitments, points and evaluations to be batch verified.
+    #[allow(clippy::type_complexity)]
+    fn verify_reduction<F>(
+        vp: &UniHyperPlonkVerifierParam<F, Pcs>,
+        instances: &[Vec<F>],
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+    ) -> Result<(Vec<Pcs::Commitment>, Vec<F>, Vec<Evaluation<F>>), Error>
+    where
+        F: WithSmallOrderMulGroup<3> + Hash + Serialize + DeserializeOwned,
+        Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+        Pcs::Commitment: Additive<F>,
+    {
+        transcript.common_field_element(&vp.vp_digest)?;
+
         for (num_instances, instances) in vp.num_instances.iter().zip_eq(instances) {
             assert_eq!(instances.len(), *num_instances);
             for instance in instances.iter() {
@@ -289,7 +355,7 @@ where
 
         let beta = transcript.squeeze_challenge();
 
-        let lookup_m_comms = Pcs::read_commitments(&vp.pcs, vp.num_lookups, transcript)?;
+        let lookup_m_comms = Pcs::read_commitments(&vp.pcs, vp.lookups.len(), transcript)?;
 
         // Round n+1
 
@@ -297,7 +363,7 @@ where
 
         let lookup_h_permutation_z_comms = Pcs::read_commitments(
             &vp.pcs,
-            vp.num_lookups + vp.num_permutation_z_polys,
+            vp.lookups.len() + vp.num_shuffles + vp.num_permutation_z_polys,
             transcript,
         )?;
 
@@ -328,16 +394,14 @@ where
             &lookup_h_permutation_z_comms,
         ]
         .collect_vec();
-        ph23::additive::verify_multilinear_eval::<_, Pcs>(
+        ph23::additive::verify_multilinear_eval_reduction::<_, Pcs>(
             &vp.pcs,
             vp.num_vars,
             comms,
             &point,
             &evals,
             transcript,
-        )?;
-
-        Ok(())
+        )
     }
 }
 
@@ -398,16 +462,29 @@ where
 mod test {
     use crate::{
         backend::{
-            hyperplonk::util::{rand_vanilla_plonk_circuit, rand_vanilla_plonk_w_lookup_circuit},
+            hyperplonk::util::{
+                rand_vanilla_plonk_circuit, rand_vanilla_plonk_w_dynamic_lookup_circuit,
+                rand_vanilla_plonk_w_lookup_circuit, rand_vanilla_plonk_w_shuffle_circuit,
+            },
             test::run_plonkish_backend,
-            unihyperplonk::UniHyperPlonk,
+            unihyperplonk::{UniHyperPlonk, UniHyperPlonkProof},
+            PlonkishBackend, PlonkishCircuit,
+        },
+        pcs::{
+            univariate::{ShplonkBatch, UnivariateKzg},
+            DeferredKzg, KzgAccumulator,
         },
-        pcs::univariate::UnivariateKzg,
         util::{
-            expression::rotate::Lexical, test::seeded_std_rng, transcript::Keccak256Transcript,
+            arithmetic::Field,
+            expression::rotate::Lexical,
+            test::seeded_std_rng,
+            transcript::{InMemoryTranscript, Keccak256Transcript, PoseidonTranscript},
+            Itertools,
         },
     };
-    use halo2_curves::bn256::Bn256;
+    use halo2_curves::bn256::{self, Bn256};
+    use rand::rngs::OsRng;
+    use std::{io::Cursor, iter};
 
     macro_rules! tests {
         ($suffix:ident, $pcs:ty, $additive:literal, $num_vars_range:expr) => {
@@ -419,12 +496,33 @@ mod test {
                     });
                 }
 
+                #[test]
+                fn [<vanilla_plonk_w_ $suffix _w_poseidon_transcript>]() {
+                    run_plonkish_backend::<_, UniHyperPlonk<$pcs, $additive>, PoseidonTranscript<_, _>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_circuit::<_, Lexical>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
                 #[test]
                 fn [<vanilla_plonk_w_lookup_w_ $suffix>]() {
                     run_plonkish_backend::<_, UniHyperPlonk<$pcs, $additive>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
                         rand_vanilla_plonk_w_lookup_circuit::<_, Lexical>(num_vars, seeded_std_rng(), seeded_std_rng())
                     });
                 }
+
+                #[test]
+                fn [<vanilla_plonk_w_dynamic_lookup_w_ $suffix>]() {
+                    run_plonkish_backend::<_, UniHyperPlonk<$pcs, $additive>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_dynamic_lookup_circuit::<_, Lexical>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_shuffle_w_ $suffix>]() {
+                    run_plonkish_backend::<_, UniHyperPlonk<$pcs, $additive>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_shuffle_circuit::<_, Lexical>(num_vars, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
             }
         };
         ($suffix:ident, $pcs:ty, $additive:literal) => {
@@ -433,4 +531,69 @@ mod test {
     }
 
     tests!(kzg, UnivariateKzg<Bn256>, true);
+    tests!(kzg_shplonk, UnivariateKzg<Bn256, ShplonkBatch>, true);
+
+    #[test]
+    fn verify_deferred() {
+        type Pcs = UnivariateKzg<Bn256, ShplonkBatch>;
+        type Pb = UniHyperPlonk<Pcs, true>;
+
+        let num_vars = 5;
+        let (circuit_info, _) = rand_vanilla_plonk_circuit::<bn256::Fr, Lexical>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+
+        let accumulators = iter::repeat_with(|| {
+            let (_, circuit) =
+                rand_vanilla_plonk_circuit::<bn256::Fr, Lexical>(num_vars, seeded_std_rng(), OsRng);
+            let proof = {
+                let mut transcript = Keccak256Transcript::new(());
+                Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+                transcript.into_proof()
+            };
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            Pb::verify_deferred::<Bn256>(&vp, circuit.instances(), &mut transcript).unwrap()
+        })
+        .take(3)
+        .collect_vec();
+        for accumulator in accumulators.iter() {
+            assert_eq!(Pcs::decide(&vp.pcs, accumulator), Ok(()));
+        }
+
+        let accumulator = KzgAccumulator::fold(&accumulators, &bn256::Fr::random(OsRng));
+        assert_eq!(Pcs::decide(&vp.pcs, &accumulator), Ok(()));
+    }
+
+    #[test]
+    fn proof_bytes_roundtrip() {
+        type Pb = UniHyperPlonk<UnivariateKzg<Bn256>, true>;
+        type T = Keccak256Transcript<Cursor<Vec<u8>>>;
+
+        let num_vars = 5;
+        let (circuit_info, circuit) = rand_vanilla_plonk_w_lookup_circuit::<bn256::Fr, Lexical>(
+            num_vars,
+            seeded_std_rng(),
+            seeded_std_rng(),
+        );
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+        let proof = {
+            let mut transcript = T::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+
+        let typed = UniHyperPlonkProof::from_bytes::<T>(&vp, (), &proof).unwrap();
+        assert_eq!(typed.witness_comms().len(), 3);
+        assert_eq!(typed.sum_check_msgs().len(), num_vars);
+        assert!(!typed.pcs_proof().is_empty());
+
+        let typed: UniHyperPlonkProof<_, UnivariateKzg<Bn256>> =
+            bincode::deserialize(&bincode::serialize(&typed).unwrap()).unwrap();
+        assert_eq!(typed.to_bytes::<T>(()).unwrap(), proof);
+    }
 }
@@ -0,0 +1,145 @@
+use crate::{
+    backend::{
+        hyperplonk::{
+            proof::{proof_suffix, read_sum_check_msgs, write_nested_field_elements},
+            verifier::pcs_query,
+        },
+        unihyperplonk::UniHyperPlonkVerifierParam,
+    },
+    pcs::PolynomialCommitmentScheme,
+    util::{
+        arithmetic::WithSmallOrderMulGroup,
+        chain,
+        transcript::{InMemoryTranscript, TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Serialize,
+    },
+    Error,
+};
+
+/// Proof of [`UniHyperPlonk`] split into named components, which converts
+/// losslessly from and to the bytes of an [`InMemoryTranscript`].
+///
+/// [`UniHyperPlonk`]: crate::backend::unihyperplonk::UniHyperPlonk
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct UniHyperPlonkProof<F, Pcs>
+where
+    F: WithSmallOrderMulGroup<3>,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub(crate) witness_comms: Vec<Pcs::Commitment>,
+    pub(crate) lookup_m_comms: Vec<Pcs::Commitment>,
+    pub(crate) lookup_h_comms: Vec<Pcs::Commitment>,
+    pub(crate) shuffle_h_comms: Vec<Pcs::Commitment>,
+    pub(crate) permutation_z_comms: Vec<Pcs::Commitment>,
+    pub(crate) sum_check_msgs: Vec<Vec<F>>,
+    pub(crate) evals: Vec<F>,
+    pub(crate) pcs_proof: Vec<u8>,
+}
+
+impl<F, Pcs> UniHyperPlonkProof<F, Pcs>
+where
+    F: WithSmallOrderMulGroup<3>,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub fn witness_comms(&self) -> &[Pcs::Commitment] {
+        &self.witness_comms
+    }
+
+    pub fn lookup_m_comms(&self) -> &[Pcs::Commitment] {
+        &self.lookup_m_comms
+    }
+
+    pub fn lookup_h_comms(&self) -> &[Pcs::Commitment] {
+        &self.lookup_h_comms
+    }
+
+    pub fn shuffle_h_comms(&self) -> &[Pcs::Commitment] {
+        &self.shuffle_h_comms
+    }
+
+    pub fn permutation_z_comms(&self) -> &[Pcs::Commitment] {
+        &self.permutation_z_comms
+    }
+
+    /// Returns evaluations of each round polynomial of the sum-check on
+    /// `0, 1, ..., degree`.
+    pub fn sum_check_msgs(&self) -> &[Vec<F>] {
+        &self.sum_check_msgs
+    }
+
+    /// Returns multilinear evaluation of each committed query.
+    pub fn evals(&self) -> &[F] {
+        &self.evals
+    }
+
+    /// Returns the PH23 multilinear evaluation proof including the opening
+    /// proof of PCS, which is kept as bytes since its format is specific to
+    /// the PCS.
+    pub fn pcs_proof(&self) -> &[u8] {
+        &self.pcs_proof
+    }
+
+    pub fn from_bytes<T>(
+        vp: &UniHyperPlonkVerifierParam<F, Pcs>,
+        param: T::Param,
+        proof: &[u8],
+    ) -> Result<Self, Error>
+    where
+        T: TranscriptRead<Pcs::CommitmentChunk, F>
+            + TranscriptWrite<Pcs::CommitmentChunk, F>
+            + InMemoryTranscript,
+    {
+        let mut transcript = T::from_proof(param.clone(), proof);
+        let num_witness_polys = vp.num_witness_polys.iter().sum();
+        let num_evals = pcs_query(&vp.expression, vp.num_instances.len()).len();
+        let mut parsed = Self {
+            witness_comms: Pcs::read_commitments(&vp.pcs, num_witness_polys, &mut transcript)?,
+            lookup_m_comms: Pcs::read_commitments(&vp.pcs, vp.lookups.len(), &mut transcript)?,
+            lookup_h_comms: Pcs::read_commitments(&vp.pcs, vp.lookups.len(), &mut transcript)?,
+            shuffle_h_comms: Pcs::read_commitments(&vp.pcs, vp.num_shuffles, &mut transcript)?,
+            permutation_z_comms: Pcs::read_commitments(
+                &vp.pcs,
+                vp.num_permutation_z_polys,
+                &mut transcript,
+            )?,
+            sum_check_msgs: read_sum_check_msgs(
+                vp.num_vars,
+                vp.expression.degree(),
+                &mut transcript,
+            )?,
+            evals: transcript.read_field_elements(num_evals)?,
+            pcs_proof: Vec::new(),
+        };
+        parsed.pcs_proof = proof_suffix::<T>(param, proof, |transcript| {
+            parsed.write_without_pcs_proof(transcript)
+        })?;
+        Ok(parsed)
+    }
+
+    pub fn to_bytes<T>(&self, param: T::Param) -> Result<Vec<u8>, Error>
+    where
+        T: TranscriptWrite<Pcs::CommitmentChunk, F> + InMemoryTranscript,
+    {
+        let mut transcript = T::new(param);
+        self.write_without_pcs_proof(&mut transcript)?;
+        Ok(chain![transcript.into_proof(), self.pcs_proof.iter().copied()].collect())
+    }
+
+    fn write_without_pcs_proof(
+        &self,
+        transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        for comm in chain![
+            &self.witness_comms,
+            &self.lookup_m_comms,
+            &self.lookup_h_comms,
+            &self.shuffle_h_comms,
+            &self.permutation_z_comms,
+        ] {
+            transcript.write_commitments(comm.as_ref())?;
+        }
+        write_nested_field_elements(&self.sum_check_msgs, transcript)?;
+        transcript.write_field_elements(&self.evals)
+    }
+}
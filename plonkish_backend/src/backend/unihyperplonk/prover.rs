@@ -17,6 +17,7 @@ use std::borrow::Borrow;
 
 pub(super) use crate::backend::hyperplonk::prover::{
     instance_polys, lookup_compressed_polys, lookup_h_polys, lookup_m_polys, permutation_z_polys,
+    shuffle_h_polys,
 };
 
 #[allow(clippy::type_complexity)]
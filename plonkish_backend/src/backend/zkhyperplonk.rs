@@ -0,0 +1,654 @@
+use crate::{
+    backend::{
+        hyperplonk::{
+            prover::{
+                instance_polys, lookup_compressed_polys, permutation_z_polys, prove_evaluations,
+            },
+            verifier::verify_evaluations,
+            HyperPlonkProverParam, HyperPlonkVerifierParam,
+        },
+        zkhyperplonk::{
+            preprocessor::{batch_size, blinding_rows, preprocess},
+            prover::{
+                blind_permutation_z_polys, blind_polys, lookup_h_polys, lookup_m_polys, mask_polys,
+                shuffle_h_polys,
+            },
+            verifier::{mask_point, mask_weight_poly},
+        },
+        PlonkishBackend, PlonkishCircuit, PlonkishCircuitInfo, WitnessEncoding,
+    },
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    piop::sum_check::{
+        classic::{ClassicSumCheck, EvaluationsProver, SumCheckMask},
+        SumCheck, VirtualPolynomial,
+    },
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::{powers, PrimeField},
+        chain, end_timer,
+        expression::{
+            rotate::{BinaryField, Rotatable},
+            Expression, Query, Rotation,
+        },
+        start_timer,
+        transcript::{TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+use rand::RngCore;
+use std::{fmt::Debug, hash::Hash, iter, marker::PhantomData, ops::Deref};
+
+pub(crate) mod preprocessor;
+pub(crate) mod prover;
+pub(crate) mod verifier;
+
+/// Number of usable rows at the end reserved for blinding, which must be
+/// greater than the number of evaluations opened of any witness polynomial.
+pub const NUM_BLINDING_ROWS: usize = 8;
+
+/// Zero-knowledge variant of [`HyperPlonk`].
+///
+/// The last [`NUM_BLINDING_ROWS`] usable rows are left unconstrained and
+/// filled with randomness in every witness, lookup and permutation
+/// polynomial, the zero-check sum-check is masked by a random low-degree
+/// polynomial as in Libra, and a random hiding polynomial is batched into the
+/// PCS opening so the merged polynomial opened is uniformly random.
+///
+/// [`HyperPlonk`]: crate::backend::hyperplonk::HyperPlonk
+#[derive(Clone, Debug)]
+pub struct ZkHyperPlonk<Pcs>(PhantomData<Pcs>);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct ZkHyperPlonkProverParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub(crate) pp: HyperPlonkProverParam<F, Pcs>,
+    pub(crate) mask_num_vars: usize,
+}
+
+impl<F, Pcs> Deref for ZkHyperPlonkProverParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    type Target = HyperPlonkProverParam<F, Pcs>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pp
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct ZkHyperPlonkVerifierParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pub(crate) vp: HyperPlonkVerifierParam<F, Pcs>,
+    pub(crate) mask_num_vars: usize,
+}
+
+impl<F, Pcs> Deref for ZkHyperPlonkVerifierParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    type Target = HyperPlonkVerifierParam<F, Pcs>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vp
+    }
+}
+
+impl<F, Pcs> PlonkishBackend<F> for ZkHyperPlonk<Pcs>
+where
+    F: PrimeField + Hash + Serialize + DeserializeOwned,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+{
+    type Pcs = Pcs;
+    type ProverParam = ZkHyperPlonkProverParam<F, Pcs>;
+    type VerifierParam = ZkHyperPlonkVerifierParam<F, Pcs>;
+
+    fn setup(
+        circuit_info: &PlonkishCircuitInfo<F>,
+        rng: impl RngCore,
+    ) -> Result<Pcs::Param, Error> {
+        circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
+        circuit_info.ensure_only_vector_lookups()?;
+
+        let num_vars = circuit_info.k;
+        let poly_size = 1 << num_vars;
+        let batch_size = batch_size(circuit_info);
+        Pcs::setup(poly_size, batch_size, rng)
+    }
+
+    fn preprocess(
+        param: &Pcs::Param,
+        circuit_info: &PlonkishCircuitInfo<F>,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        circuit_info.ensure_only_vector_lookups()?;
+
+        preprocess(param, circuit_info)
+    }
+
+    fn prove(
+        pp: &Self::ProverParam,
+        circuit: &impl PlonkishCircuit<F>,
+        transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+        mut rng: impl RngCore,
+    ) -> Result<(), Error> {
+        transcript.common_field_element(&pp.vp_digest)?;
+
+        let blinding_rows = blinding_rows(pp.num_vars);
+
+        let instance_polys = {
+            let instances = circuit.instances();
+            for (num_instances, instances) in pp.num_instances.iter().zip_eq(instances) {
+                assert_eq!(instances.len(), *num_instances);
+                for instance in instances.iter() {
+                    transcript.common_field_element(instance)?;
+                }
+            }
+            instance_polys::<_, BinaryField>(pp.num_vars, instances)
+        };
+
+        // Round 0..n
+
+        let mut witness_polys = Vec::with_capacity(pp.num_witness_polys.iter().sum());
+        let mut witness_comms = Vec::with_capacity(witness_polys.len());
+        let mut challenges = Vec::with_capacity(pp.num_challenges.iter().sum::<usize>() + 4);
+        for (round, (num_witness_polys, num_challenges)) in pp
+            .num_witness_polys
+            .iter()
+            .zip_eq(pp.num_challenges.iter())
+            .enumerate()
+        {
+            let timer = start_timer(|| format!("witness_collector-{round}"));
+            let mut polys = circuit
+                .synthesize(round, &challenges)?
+                .into_iter()
+                .map(MultilinearPolynomial::new)
+                .collect_vec();
+            assert_eq!(polys.len(), *num_witness_polys);
+            blind_polys(&mut polys, &blinding_rows, &mut rng);
+            end_timer(timer);
+
+            witness_comms.extend(Pcs::batch_commit_and_write(&pp.pcs, &polys, transcript)?);
+            witness_polys.extend(polys);
+            challenges.extend(transcript.squeeze_challenges(*num_challenges));
+        }
+        let polys = chain![&instance_polys, &pp.preprocess_polys, &witness_polys].collect_vec();
+
+        // Round n
+
+        let beta = transcript.squeeze_challenge();
+
+        let timer = start_timer(|| {
+            let num_lookups = pp.lookups.len() + pp.shuffles.len();
+            format!("lookup_compressed_polys-{num_lookups}")
+        });
+        let max_lookup_width = chain![&pp.lookups, &pp.shuffles]
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        let betas = powers(beta).take(max_lookup_width).collect_vec();
+        let [lookup_compressed_polys, shuffle_compressed_polys] =
+            [&pp.lookups, &pp.shuffles].map(|lookups| {
+                lookup_compressed_polys::<_, BinaryField>(lookups, &polys, &challenges, &betas)
+            });
+        end_timer(timer);
+
+        let timer = start_timer(|| format!("lookup_m_polys-{}", pp.lookups.len()));
+        let mut lookup_m_polys = lookup_m_polys(&lookup_compressed_polys, &blinding_rows)?;
+        blind_polys(&mut lookup_m_polys, &blinding_rows, &mut rng);
+        end_timer(timer);
+
+        let lookup_m_comms = Pcs::batch_commit_and_write(&pp.pcs, &lookup_m_polys, transcript)?;
+
+        // Round n+1
+
+        let gamma = transcript.squeeze_challenge();
+
+        let timer = start_timer(|| format!("lookup_h_polys-{}", pp.lookups.len()));
+        let lookup_h_polys = lookup_h_polys(
+            &lookup_compressed_polys,
+            &lookup_m_polys,
+            &gamma,
+            &blinding_rows,
+            &mut rng,
+        );
+        end_timer(timer);
+
+        let timer = start_timer(|| format!("shuffle_h_polys-{}", pp.shuffles.len()));
+        let shuffle_h_polys =
+            shuffle_h_polys(&shuffle_compressed_polys, &gamma, &blinding_rows, &mut rng);
+        end_timer(timer);
+
+        let timer = start_timer(|| format!("permutation_z_polys-{}", pp.permutation_polys.len()));
+        let mut permutation_z_polys = permutation_z_polys::<_, BinaryField>(
+            pp.num_permutation_z_polys,
+            &pp.permutation_polys,
+            &polys,
+            &beta,
+            &gamma,
+        );
+        blind_permutation_z_polys(&mut permutation_z_polys, &blinding_rows, &mut rng);
+        end_timer(timer);
+
+        let mask = SumCheckMask::rand(pp.num_vars, pp.expression.degree(), &mut rng);
+        let [mask_poly, hiding_poly] = mask_polys(pp.num_vars, &mask, &mut rng);
+
+        let lookup_h_permutation_z_mask_polys = chain![
+            lookup_h_polys.iter(),
+            shuffle_h_polys.iter(),
+            permutation_z_polys.iter(),
+            [&mask_poly, &hiding_poly],
+        ]
+        .collect_vec();
+        let lookup_h_permutation_z_mask_comms = Pcs::batch_commit_and_write(
+            &pp.pcs,
+            lookup_h_permutation_z_mask_polys.clone(),
+            transcript,
+        )?;
+        transcript.write_field_element(&mask.sum())?;
+
+        // Round n+2
+
+        let alpha = transcript.squeeze_challenge();
+        let y = transcript.squeeze_challenges(pp.num_vars);
+        let rho = transcript.squeeze_challenge();
+
+        let polys = chain![
+            polys,
+            pp.permutation_polys.iter().map(|(_, poly)| poly),
+            lookup_m_polys.iter(),
+            lookup_h_polys.iter(),
+            shuffle_h_polys.iter(),
+            permutation_z_polys.iter(),
+        ]
+        .collect_vec();
+        challenges.extend([beta, gamma, alpha]);
+        let (x, evals) = {
+            let ys = [y];
+            let virtual_poly =
+                VirtualPolynomial::new(&pp.expression, polys.iter().copied(), &challenges, &ys);
+            let (_, x, evals) = ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::prove_masked(
+                pp.num_vars,
+                virtual_poly,
+                F::ZERO,
+                &mask,
+                &rho,
+                transcript,
+            )?;
+            (x, evals)
+        };
+        let mask_eval = mask.evaluate(&x);
+        transcript.write_field_element(&mask_eval)?;
+        let (mut points, mut evals) = prove_evaluations(
+            pp.num_instances.len(),
+            &pp.expression,
+            &polys,
+            &x,
+            &evals,
+            transcript,
+        )?;
+
+        // Round n+3
+
+        let mask_poly_eval = {
+            let mask_num_vars = pp.mask_num_vars;
+            let expression = Expression::<F>::Polynomial(Query::new(0, Rotation::cur()))
+                * Expression::Polynomial(Query::new(1, Rotation::cur()));
+            let mask_poly = MultilinearPolynomial::new(mask_poly[..1 << mask_num_vars].to_vec());
+            let mask_weight_poly = mask_weight_poly(mask_num_vars, mask.degree(), &x);
+            let virtual_poly =
+                VirtualPolynomial::new(&expression, [&mask_poly, &mask_weight_poly], &[], &[]);
+            let (_, s, _) = ClassicSumCheck::<EvaluationsProver<_>>::prove(
+                &(),
+                mask_num_vars,
+                virtual_poly,
+                mask_eval,
+                transcript,
+            )?;
+            let mask_poly_eval = mask_poly.evaluate(&s);
+            transcript.write_field_element(&mask_poly_eval)?;
+            points.push(mask_point(pp.num_vars, &s));
+            mask_poly_eval
+        };
+        evals.push(Evaluation::new(
+            polys.len(),
+            points.len() - 1,
+            mask_poly_eval,
+        ));
+
+        let hiding_evals = points
+            .iter()
+            .map(|point| hiding_poly.evaluate(point))
+            .collect_vec();
+        transcript.write_field_elements(&hiding_evals)?;
+        evals.extend(
+            hiding_evals
+                .into_iter()
+                .enumerate()
+                .map(|(point, eval)| Evaluation::new(polys.len() + 1, point, eval)),
+        );
+
+        // PCS open
+
+        let polys = chain![polys, [&mask_poly, &hiding_poly]].collect_vec();
+        let dummy_comm = Pcs::Commitment::default();
+        let comms = chain![
+            iter::repeat(&dummy_comm).take(pp.num_instances.len()),
+            &pp.preprocess_comms,
+            &witness_comms,
+            &pp.permutation_comms,
+            &lookup_m_comms,
+            &lookup_h_permutation_z_mask_comms,
+        ]
+        .collect_vec();
+        let timer = start_timer(|| format!("pcs_batch_open-{}", evals.len()));
+        Pcs::batch_open(&pp.pcs, polys, comms, &points, &evals, transcript)?;
+        end_timer(timer);
+
+        Ok(())
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        instances: &[Vec<F>],
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+        _: impl RngCore,
+    ) -> Result<(), Error> {
+        transcript.common_field_element(&vp.vp_digest)?;
+
+        for (num_instances, instances) in vp.num_instances.iter().zip_eq(instances) {
+            assert_eq!(instances.len(), *num_instances);
+            for instance in instances.iter() {
+                transcript.common_field_element(instance)?;
+            }
+        }
+
+        // Round 0..n
+
+        let mut witness_comms = Vec::with_capacity(vp.num_witness_polys.iter().sum());
+        let mut challenges = Vec::with_capacity(vp.num_challenges.iter().sum::<usize>() + 4);
+        for (num_polys, num_challenges) in
+            vp.num_witness_polys.iter().zip_eq(vp.num_challenges.iter())
+        {
+            witness_comms.extend(Pcs::read_commitments(&vp.pcs, *num_polys, transcript)?);
+            challenges.extend(transcript.squeeze_challenges(*num_challenges));
+        }
+
+        // Round n
+
+        let beta = transcript.squeeze_challenge();
+
+        let lookup_m_comms = Pcs::read_commitments(&vp.pcs, vp.lookups.len(), transcript)?;
+
+        // Round n+1
+
+        let gamma = transcript.squeeze_challenge();
+
+        let lookup_h_permutation_z_mask_comms = Pcs::read_commitments(
+            &vp.pcs,
+            vp.lookups.len() + vp.num_shuffles + vp.num_permutation_z_polys + 2,
+            transcript,
+        )?;
+        let mask_sum = transcript.read_field_element()?;
+
+        // Round n+2
+
+        let alpha = transcript.squeeze_challenge();
+        let y = transcript.squeeze_challenges(vp.num_vars);
+        let rho = transcript.squeeze_challenge();
+
+        let degree = vp.expression.degree();
+        let (x_eval, x) = ClassicSumCheck::<EvaluationsProver<_>, BinaryField>::verify(
+            &(),
+            vp.num_vars,
+            degree,
+            rho * mask_sum,
+            transcript,
+        )?;
+        let mask_eval = transcript.read_field_element()?;
+        challenges.extend([beta, gamma, alpha]);
+        let (mut points, mut evals) = verify_evaluations(
+            vp.num_vars,
+            &vp.expression,
+            instances,
+            &challenges,
+            &y,
+            x_eval - rho * mask_eval,
+            &x,
+            transcript,
+        )?;
+
+        // Round n+3
+
+        let num_polys = vp.num_instances.len()
+            + vp.preprocess_comms.len()
+            + witness_comms.len()
+            + vp.permutation_comms.len()
+            + 2 * vp.lookups.len()
+            + vp.num_shuffles
+            + vp.num_permutation_z_polys;
+        let mask_poly_eval = {
+            let mask_num_vars = vp.mask_num_vars;
+            let (s_eval, s) = ClassicSumCheck::<EvaluationsProver<_>>::verify(
+                &(),
+                mask_num_vars,
+                2,
+                mask_eval,
+                transcript,
+            )?;
+            let mask_poly_eval = transcript.read_field_element()?;
+            let mask_weight_eval = mask_weight_poly(mask_num_vars, degree, &x).evaluate(&s);
+            if s_eval != mask_poly_eval * mask_weight_eval {
+                return Err(Error::InvalidSnark(
+                    "Unmatched between mask sum_check output and mask evaluation".to_string(),
+                ));
+            }
+            points.push(mask_point(vp.num_vars, &s));
+            mask_poly_eval
+        };
+        evals.push(Evaluation::new(num_polys, points.len() - 1, mask_poly_eval));
+
+        let hiding_evals = transcript.read_field_elements(points.len())?;
+        evals.extend(
+            hiding_evals
+                .into_iter()
+                .enumerate()
+                .map(|(point, eval)| Evaluation::new(num_polys + 1, point, eval)),
+        );
+
+        // PCS verify
+
+        let dummy_comm = Pcs::Commitment::default();
+        let comms = chain![
+            iter::repeat(&dummy_comm).take(vp.num_instances.len()),
+            &vp.preprocess_comms,
+            &witness_comms,
+            vp.permutation_comms.iter().map(|(_, comm)| comm),
+            &lookup_m_comms,
+            &lookup_h_permutation_z_mask_comms,
+        ]
+        .collect_vec();
+        Pcs::batch_verify(&vp.pcs, comms, &points, &evals, transcript)?;
+
+        Ok(())
+    }
+}
+
+impl<Pcs> WitnessEncoding for ZkHyperPlonk<Pcs> {
+    fn row_mapping(k: usize) -> Vec<usize> {
+        let mut usable_indices = BinaryField::new(k).usable_indices();
+        usable_indices.truncate(usable_indices.len().saturating_sub(NUM_BLINDING_ROWS));
+        usable_indices
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::{
+            hyperplonk::util::{
+                rand_vanilla_plonk_circuit_w_blinding_rows,
+                rand_vanilla_plonk_w_dynamic_lookup_circuit_w_blinding_rows,
+                rand_vanilla_plonk_w_lookup_circuit_w_blinding_rows,
+                rand_vanilla_plonk_w_shuffle_circuit_w_blinding_rows, rand_vanilla_plonk_witness,
+            },
+            mock::MockCircuit,
+            test::run_plonkish_backend,
+            zkhyperplonk::{ZkHyperPlonk, NUM_BLINDING_ROWS},
+            PlonkishBackend, PlonkishCircuit,
+        },
+        pcs::{
+            multilinear::{Gemini, MultilinearIpa, MultilinearKzg, Zeromorph},
+            univariate::UnivariateKzg,
+        },
+        util::{
+            arithmetic::Field,
+            expression::rotate::{BinaryField, Rotatable},
+            test::seeded_std_rng,
+            transcript::{InMemoryTranscript, Keccak256Transcript, PoseidonTranscript},
+        },
+    };
+    use halo2_curves::{
+        bn256::{self, Bn256},
+        grumpkin,
+    };
+
+    macro_rules! tests {
+        ($suffix:ident, $pcs:ty, $num_vars_range:expr) => {
+            paste::paste! {
+                #[test]
+                fn [<vanilla_plonk_w_ $suffix>]() {
+                    run_plonkish_backend::<_, ZkHyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_circuit_w_blinding_rows::<_, BinaryField>(num_vars, NUM_BLINDING_ROWS, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_ $suffix _w_poseidon_transcript>]() {
+                    run_plonkish_backend::<_, ZkHyperPlonk<$pcs>, PoseidonTranscript<_, _>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_circuit_w_blinding_rows::<_, BinaryField>(num_vars, NUM_BLINDING_ROWS, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_lookup_w_ $suffix>]() {
+                    run_plonkish_backend::<_, ZkHyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_lookup_circuit_w_blinding_rows::<_, BinaryField>(num_vars, NUM_BLINDING_ROWS, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_dynamic_lookup_w_ $suffix>]() {
+                    run_plonkish_backend::<_, ZkHyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_dynamic_lookup_circuit_w_blinding_rows::<_, BinaryField>(num_vars, NUM_BLINDING_ROWS, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+
+                #[test]
+                fn [<vanilla_plonk_w_shuffle_w_ $suffix>]() {
+                    run_plonkish_backend::<_, ZkHyperPlonk<$pcs>, Keccak256Transcript<_>, _>($num_vars_range, |num_vars| {
+                        rand_vanilla_plonk_w_shuffle_circuit_w_blinding_rows::<_, BinaryField>(num_vars, NUM_BLINDING_ROWS, seeded_std_rng(), seeded_std_rng())
+                    });
+                }
+            }
+        };
+        ($suffix:ident, $pcs:ty) => {
+            tests!($suffix, $pcs, 7..16);
+        };
+    }
+
+    tests!(ipa, MultilinearIpa<grumpkin::G1Affine>);
+    tests!(kzg, MultilinearKzg<Bn256>);
+    tests!(gemini_kzg, Gemini<UnivariateKzg<Bn256>>);
+    tests!(zeromorph_kzg, Zeromorph<UnivariateKzg<Bn256>>);
+
+    #[test]
+    #[cfg(not(feature = "sanity-check"))]
+    fn bad_shuffle_witness() {
+        type Pb = ZkHyperPlonk<MultilinearKzg<Bn256>>;
+
+        let num_vars = 8;
+        let (circuit_info, circuit) =
+            rand_vanilla_plonk_w_shuffle_circuit_w_blinding_rows::<bn256::Fr, BinaryField>(
+                num_vars,
+                NUM_BLINDING_ROWS,
+                seeded_std_rng(),
+                seeded_std_rng(),
+            );
+        let circuit = {
+            let mut witnesses = circuit.synthesize(0, &[]).unwrap();
+            let row = BinaryField::new(num_vars).usable_indices()[1];
+            witnesses[3][row] += bn256::Fr::ONE;
+            MockCircuit::new(circuit.instances().to_vec(), witnesses)
+        };
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+
+        // Shuffle h polynomials satisfy the constraint on every row but don't
+        // sum to zero over active rows, which randomness on blinding rows
+        // must not be able to cancel.
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+            transcript.into_proof()
+        };
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        assert!(Pb::verify(&vp, circuit.instances(), &mut transcript, seeded_std_rng()).is_err());
+    }
+
+    #[test]
+    fn different_witnesses() {
+        type Pb = ZkHyperPlonk<MultilinearKzg<Bn256>>;
+
+        let num_vars = 8;
+        let (circuit_info, circuit) =
+            rand_vanilla_plonk_circuit_w_blinding_rows::<bn256::Fr, BinaryField>(
+                num_vars,
+                NUM_BLINDING_ROWS,
+                seeded_std_rng(),
+                seeded_std_rng(),
+            );
+        let instances = circuit.instances().to_vec();
+        let witnesses = [
+            circuit.synthesize(0, &[]).unwrap(),
+            rand_vanilla_plonk_witness::<_, BinaryField>(
+                &circuit_info,
+                &instances,
+                seeded_std_rng(),
+            ),
+        ];
+        assert_ne!(witnesses[0], witnesses[1]);
+        let param = Pb::setup(&circuit_info, seeded_std_rng()).unwrap();
+        let (pp, vp) = Pb::preprocess(&param, &circuit_info).unwrap();
+
+        // Proofs of different witnesses of the same instance should both
+        // verify and share nothing, since every element is either a hiding
+        // commitment or an evaluation masked by blinding rows.
+        let [lhs, rhs] = witnesses.map(|witnesses| {
+            let circuit = MockCircuit::new(instances.clone(), witnesses);
+            let proof = {
+                let mut transcript = Keccak256Transcript::new(());
+                Pb::prove(&pp, &circuit, &mut transcript, seeded_std_rng()).unwrap();
+                transcript.into_proof()
+            };
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            Pb::verify(&vp, &instances, &mut transcript, seeded_std_rng()).unwrap();
+            proof
+        });
+        assert_eq!(lhs.len(), rhs.len());
+        assert!(lhs
+            .chunks(32)
+            .zip(rhs.chunks(32))
+            .all(|(lhs, rhs)| lhs != rhs));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    backend::{
+        hyperplonk::{
+            preprocessor::{
+                self, lookup_constraints, max_degree, num_builtin_witness_polys,
+                permutation_constraints, vp_digest,
+            },
+            HyperPlonk,
+            LookupStrategy::LogUp,
+            PermutationStrategy::Plonk,
+        },
+        zkhyperplonk::{ZkHyperPlonkProverParam, ZkHyperPlonkVerifierParam, NUM_BLINDING_ROWS},
+        CircuitInfoError, PlonkishBackend, PlonkishCircuitInfo,
+    },
+    pcs::PolynomialCommitmentScheme,
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::PrimeField,
+        chain,
+        expression::{
+            rotate::{BinaryField, Rotatable},
+            Expression, Query, Rotation,
+        },
+        DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+use std::{array, collections::HashMap, hash::Hash};
+
+pub(crate) fn batch_size<F: PrimeField>(circuit_info: &PlonkishCircuitInfo<F>) -> usize {
+    preprocessor::batch_size(circuit_info, LogUp, Plonk) + 2
+}
+
+/// Returns the last [`NUM_BLINDING_ROWS`] usable rows, starting from the one
+/// where the first permutation grand product is checked to be one.
+pub(crate) fn blinding_rows(num_vars: usize) -> Vec<usize> {
+    let usable_indices = BinaryField::new(num_vars).usable_indices();
+    usable_indices[usable_indices.len() - NUM_BLINDING_ROWS..].to_vec()
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn preprocess<F, Pcs>(
+    param: &Pcs::Param,
+    circuit_info: &PlonkishCircuitInfo<F>,
+) -> Result<
+    (
+        ZkHyperPlonkProverParam<F, Pcs>,
+        ZkHyperPlonkVerifierParam<F, Pcs>,
+    ),
+    Error,
+>
+where
+    F: PrimeField + Hash + Serialize + DeserializeOwned,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+{
+    circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
+
+    let num_vars = circuit_info.k;
+    let (num_permutation_z_polys, expression) = compose(circuit_info);
+    validate_blinding_rows(circuit_info, &expression)?;
+
+    let mask_num_vars = {
+        let num_mask_coeffs = num_vars * (expression.degree() + 1);
+        num_mask_coeffs.next_power_of_two().ilog2() as usize
+    };
+    if mask_num_vars > num_vars {
+        return Err(Error::InvalidCircuitInfo(vec![
+            CircuitInfoError::TooFewVarsForMask {
+                num_vars,
+                mask_num_vars,
+            },
+        ]));
+    }
+
+    let (mut pp, vp) = {
+        let (mut pp, mut vp) = HyperPlonk::<Pcs>::preprocess(param, circuit_info)?;
+        let batch_size = batch_size(circuit_info);
+        let (pcs_pp, pcs_vp) = Pcs::trim(param, 1 << num_vars, batch_size)?;
+        pp.pcs = pcs_pp;
+        vp.pcs = pcs_vp;
+        pp.num_permutation_z_polys = num_permutation_z_polys;
+        vp.num_permutation_z_polys = num_permutation_z_polys;
+        pp.expression = expression.clone();
+        vp.expression = expression;
+        vp.vp_digest = F::ZERO;
+        (pp, vp)
+    };
+
+    let mut vp = ZkHyperPlonkVerifierParam { vp, mask_num_vars };
+    vp.vp.vp_digest = vp_digest(&vp);
+    pp.vp_digest = vp.vp_digest;
+
+    Ok((ZkHyperPlonkProverParam { pp, mask_num_vars }, vp))
+}
+
+/// Same as HyperPlonk's `compose` but every constraint is only enforced on
+/// rows other than the blinding ones, the lookup and shuffle `h` polynomials
+/// are only summed over rows other than the blinding ones, and the first
+/// permutation grand product is checked to be one on the first blinding row
+/// instead of wrapping around.
+pub(crate) fn compose<F: PrimeField>(
+    circuit_info: &PlonkishCircuitInfo<F>,
+) -> (usize, Expression<F>) {
+    let challenge_offset = circuit_info.num_challenges.iter().sum::<usize>();
+    let [beta, gamma, alpha] =
+        &array::from_fn(|idx| Expression::<F>::Challenge(challenge_offset + idx));
+
+    let (lookup_constraints, lookup_zero_checks) =
+        lookup_constraints(circuit_info, LogUp, beta, gamma);
+
+    let max_degree = max_degree(circuit_info, LogUp, Some(&lookup_constraints));
+    let num_builtin_witness_polys = num_builtin_witness_polys(circuit_info, LogUp);
+    let (num_permutation_z_polys, permutation_constraints) = permutation_constraints(
+        circuit_info,
+        max_degree,
+        beta,
+        gamma,
+        num_builtin_witness_polys,
+    );
+
+    let num_blinding_rows = NUM_BLINDING_ROWS as i32;
+    let one = &Expression::one();
+    let q_active = &(one
+        - (1..=num_blinding_rows)
+            .map(|i| Expression::lagrange(-i))
+            .sum::<Expression<_>>());
+    let z_0_last = (num_permutation_z_polys > 0).then(|| {
+        let z_offset = circuit_info.num_poly()
+            + circuit_info.permutation_polys().len()
+            + num_builtin_witness_polys;
+        let z_0 = Expression::<F>::Polynomial(Query::new(z_offset, Rotation::cur()));
+        Expression::lagrange(-num_blinding_rows) * (z_0 - one)
+    });
+
+    let expression = {
+        let constraints = chain![
+            chain![
+                circuit_info.constraints.iter(),
+                lookup_constraints.iter(),
+                permutation_constraints.iter(),
+            ]
+            .map(|constraint| q_active * constraint),
+            z_0_last,
+        ]
+        .collect_vec();
+        let eq = Expression::eq_xy(0);
+        let zero_check_on_every_row = Expression::distribute_powers(constraints, alpha) * eq;
+        Expression::distribute_powers(
+            chain![
+                lookup_zero_checks.iter().map(|h| q_active * h),
+                [zero_check_on_every_row],
+            ],
+            alpha,
+        )
+    };
+
+    (num_permutation_z_polys, expression)
+}
+
+fn validate_blinding_rows<F: PrimeField>(
+    circuit_info: &PlonkishCircuitInfo<F>,
+    expression: &Expression<F>,
+) -> Result<(), Error> {
+    let mut errors = Vec::new();
+
+    let num_usable_rows = (1 << circuit_info.k) - 1;
+    let max_num_instances = circuit_info.num_instances.iter().max().copied();
+    let num_reserved_rows = NUM_BLINDING_ROWS + max_num_instances.unwrap_or_default();
+    if num_usable_rows < num_reserved_rows {
+        errors.push(CircuitInfoError::TooFewUsableRows {
+            num_usable_rows,
+            num_reserved_rows,
+        });
+    }
+
+    let blinding_rows = blinding_rows(circuit_info.k);
+    for (cycle_idx, cycle) in circuit_info.permutations.iter().enumerate() {
+        errors.extend(
+            cycle
+                .iter()
+                .filter(|(_, row)| blinding_rows.contains(row))
+                .map(|(poly, row)| CircuitInfoError::PermutationOnBlindingRow {
+                    cycle: cycle_idx,
+                    poly: *poly,
+                    row: *row,
+                }),
+        );
+    }
+
+    let witness_offset = circuit_info.num_instances.len() + circuit_info.preprocess_polys.len();
+    let builtin_witness_offset = circuit_info.num_poly() + circuit_info.permutation_polys().len();
+    let num_evals = expression
+        .used_query()
+        .into_iter()
+        .filter(|query| {
+            (witness_offset..circuit_info.num_poly()).contains(&query.poly())
+                || query.poly() >= builtin_witness_offset
+        })
+        .fold(HashMap::<_, usize>::new(), |mut num_evals, query| {
+            *num_evals.entry(query.poly()).or_default() += 1 << query.rotation().distance();
+            num_evals
+        });
+    errors.extend(
+        num_evals
+            .into_iter()
+            .filter(|(_, num_evals)| *num_evals >= NUM_BLINDING_ROWS)
+            .sorted()
+            .map(|(poly, num_evals)| CircuitInfoError::TooManyEvaluations {
+                poly,
+                num_evals,
+                max_num_evals: NUM_BLINDING_ROWS - 1,
+            }),
+    );
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidCircuitInfo(errors))
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    piop::sum_check::classic::SumCheckMask,
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::{sum, BatchInvert, PrimeField},
+        chain,
+        parallel::{par_map_collect, parallelize},
+        Itertools,
+    },
+    Error,
+};
+use rand::RngCore;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    iter,
+};
+
+pub(crate) fn blind_polys<F: PrimeField>(
+    polys: &mut [MultilinearPolynomial<F>],
+    blinding_rows: &[usize],
+    mut rng: impl RngCore,
+) {
+    for poly in polys.iter_mut() {
+        for row in blinding_rows.iter() {
+            poly[*row] = F::random(&mut rng);
+        }
+    }
+}
+
+/// Blinds permutation grand product polynomials except the first one on the
+/// first blinding row, which is checked to be one.
+pub(crate) fn blind_permutation_z_polys<F: PrimeField>(
+    z_polys: &mut [MultilinearPolynomial<F>],
+    blinding_rows: &[usize],
+    mut rng: impl RngCore,
+) {
+    if let Some((z_0, z_polys)) = z_polys.split_first_mut() {
+        assert_eq!(z_0[blinding_rows[0]], F::ONE);
+        for row in blinding_rows.iter().skip(1) {
+            z_0[*row] = F::random(&mut rng);
+        }
+        blind_polys(z_polys, blinding_rows, rng);
+    }
+}
+
+pub(crate) fn lookup_m_polys<F: PrimeField + Hash>(
+    compressed_polys: &[[MultilinearPolynomial<F>; 2]],
+    blinding_rows: &[usize],
+) -> Result<Vec<MultilinearPolynomial<F>>, Error> {
+    let blinding_rows = blinding_rows.iter().copied().collect::<HashSet<_>>();
+    compressed_polys
+        .iter()
+        .map(|compressed_polys| lookup_m_poly(compressed_polys, &blinding_rows))
+        .try_collect()
+}
+
+/// Same as HyperPlonk's `lookup_m_poly` but ignores inputs and tables on
+/// blinding rows.
+pub(super) fn lookup_m_poly<F: PrimeField + Hash>(
+    compressed_polys: &[MultilinearPolynomial<F>; 2],
+    blinding_rows: &HashSet<usize>,
+) -> Result<MultilinearPolynomial<F>, Error> {
+    let [input, table] = compressed_polys;
+
+    let indice_map = table
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !blinding_rows.contains(idx))
+        .map(|(idx, table)| (table, idx))
+        .collect::<HashMap<_, _>>();
+
+    let mut m = vec![0; 1 << input.num_vars()];
+    for (_, input) in input
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !blinding_rows.contains(idx))
+    {
+        let idx = indice_map
+            .get(input)
+            .ok_or_else(|| Error::InvalidSnark("Invalid lookup input".to_string()))?;
+        m[*idx] += 1;
+    }
+    let m = par_map_collect(m, |count| match count {
+        0 => F::ZERO,
+        1 => F::ONE,
+        count => F::from(count),
+    });
+    Ok(MultilinearPolynomial::new(m))
+}
+
+pub(crate) fn lookup_h_polys<F: PrimeField + Hash>(
+    compressed_polys: &[[MultilinearPolynomial<F>; 2]],
+    m_polys: &[MultilinearPolynomial<F>],
+    gamma: &F,
+    blinding_rows: &[usize],
+    mut rng: impl RngCore,
+) -> Vec<MultilinearPolynomial<F>> {
+    compressed_polys
+        .iter()
+        .zip(m_polys.iter())
+        .map(|(compressed_polys, m_poly)| {
+            lookup_h_poly(compressed_polys, m_poly, gamma, blinding_rows, &mut rng)
+        })
+        .collect()
+}
+
+/// Same as [`lookup_h_polys`] but with multiplicities all being one.
+pub(crate) fn shuffle_h_polys<F: PrimeField + Hash>(
+    compressed_polys: &[[MultilinearPolynomial<F>; 2]],
+    gamma: &F,
+    blinding_rows: &[usize],
+    mut rng: impl RngCore,
+) -> Vec<MultilinearPolynomial<F>> {
+    let Some([input, _]) = compressed_polys.first() else {
+        return Vec::new();
+    };
+    let ones = MultilinearPolynomial::new(vec![F::ONE; 1 << input.num_vars()]);
+    compressed_polys
+        .iter()
+        .map(|compressed_polys| {
+            lookup_h_poly(compressed_polys, &ones, gamma, blinding_rows, &mut rng)
+        })
+        .collect()
+}
+
+/// Same as HyperPlonk's `lookup_h_poly` on rows other than blinding ones, and
+/// random on blinding rows, which are excluded from the sum checked to be zero.
+pub(super) fn lookup_h_poly<F: PrimeField + Hash>(
+    compressed_polys: &[MultilinearPolynomial<F>; 2],
+    m_poly: &MultilinearPolynomial<F>,
+    gamma: &F,
+    blinding_rows: &[usize],
+    mut rng: impl RngCore,
+) -> MultilinearPolynomial<F> {
+    let [input, table] = compressed_polys;
+    let mut h_input: Vec<_> = par_map_collect(input.evals(), |input| *gamma + input);
+    let mut h_table: Vec<_> = par_map_collect(table.evals(), |table| *gamma + table);
+    chain![&mut h_input, &mut h_table].batch_invert();
+
+    parallelize(&mut h_input, |(h_input, start)| {
+        for (h_input, (h_table, m)) in h_input
+            .iter_mut()
+            .zip(h_table[start..].iter().zip(m_poly[start..].iter()))
+        {
+            *h_input -= *h_table * m;
+        }
+    });
+
+    for row in blinding_rows {
+        h_input[*row] = F::random(&mut rng);
+    }
+
+    if cfg!(feature = "sanity-check") {
+        let blinding_rows = blinding_rows.iter().copied().collect::<HashSet<_>>();
+        let h_input = h_input
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !blinding_rows.contains(idx))
+            .map(|(_, h)| h);
+        assert_eq!(sum::<F>(h_input), F::ZERO);
+    }
+
+    MultilinearPolynomial::new(h_input)
+}
+
+/// Returns the polynomial committing coefficients of `mask` and a random
+/// polynomial to hide the batch opening.
+pub(crate) fn mask_polys<F: PrimeField>(
+    num_vars: usize,
+    mask: &SumCheckMask<F>,
+    mut rng: impl RngCore,
+) -> [MultilinearPolynomial<F>; 2] {
+    let mask_poly = {
+        let coeffs = mask.coeffs().iter().flatten().copied();
+        chain![coeffs, iter::repeat_with(|| F::random(&mut rng))]
+            .take(1 << num_vars)
+            .collect_vec()
+    };
+    let hiding_poly = MultilinearPolynomial::rand(num_vars, &mut rng);
+    [MultilinearPolynomial::new(mask_poly), hiding_poly]
+}
@@ -0,0 +1,31 @@
+use crate::{
+    poly::multilinear::MultilinearPolynomial,
+    util::arithmetic::{powers, PrimeField},
+};
+use std::iter;
+
+/// Returns polynomial `V` such that `Σ_b M(b) * V(b)` equals to evaluation of
+/// sum-check mask on `x`, where `M` has coefficients of the mask in its first
+/// `2^mask_num_vars` evaluations.
+pub(crate) fn mask_weight_poly<F: PrimeField>(
+    mask_num_vars: usize,
+    degree: usize,
+    x: &[F],
+) -> MultilinearPolynomial<F> {
+    let mut evals = vec![F::ZERO; 1 << mask_num_vars];
+    for (evals, x_i) in evals.chunks_mut(degree + 1).zip(x) {
+        for (eval, x_i_power) in evals.iter_mut().zip(powers(*x_i)) {
+            *eval = x_i_power;
+        }
+    }
+    MultilinearPolynomial::new(evals)
+}
+
+/// Returns point `s || 0^{num_vars - s.len()}` to open the mask polynomial.
+pub(crate) fn mask_point<F: PrimeField>(num_vars: usize, s: &[F]) -> Vec<F> {
+    s.iter()
+        .copied()
+        .chain(iter::repeat(F::ZERO))
+        .take(num_vars)
+        .collect()
+}
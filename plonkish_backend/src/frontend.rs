@@ -1,2 +1,4 @@
+pub mod circom;
 #[cfg(feature = "frontend-halo2")]
 pub mod halo2;
+pub mod native;
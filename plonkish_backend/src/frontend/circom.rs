@@ -0,0 +1,415 @@
+use crate::{
+    backend::{PlonkishCircuitInfo, WitnessEncoding},
+    frontend::native::{CircuitBuilder, Column, NativeCircuit, WitnessAssigner},
+    util::{
+        arithmetic::{fe_from_le_bytes, modulus, PrimeField},
+        expression::Expression,
+        izip, Itertools,
+    },
+    Error,
+};
+use num_bigint::BigUint;
+use std::{collections::HashMap, io::Read, iter};
+
+/// Circuit lowered from [`R1cs`], which serves the witness of a `.wtns` file.
+pub type R1csCircuit<F> =
+    NativeCircuit<F, Box<dyn Fn(&mut WitnessAssigner<F>) -> Result<(), Error>>>;
+
+/// Linear combination of wires, where wire `0` is the constant one.
+pub type LinearCombination<F> = Vec<(usize, F)>;
+
+/// R1CS parsed from circom `.r1cs` file, where wires are ordered as constant
+/// one, public outputs, public inputs then private ones.
+///
+/// It's lowered into a circuit with `width` witness columns, where `width` is
+/// the max number of distinct wires in a constraint. Each constraint takes a
+/// row and is enforced by the single gate
+/// `(Σ q_a_i * w_i + q_a) * (Σ q_b_i * w_i + q_b) - (Σ q_c_i * w_i + q_c)`, and
+/// cells of the same wire are copy constrained to each other.
+#[derive(Clone, Debug)]
+pub struct R1cs<F> {
+    num_wires: usize,
+    num_public_outputs: usize,
+    num_public_inputs: usize,
+    constraints: Vec<[LinearCombination<F>; 3]>,
+}
+
+impl<F: PrimeField> R1cs<F> {
+    pub fn read(mut reader: impl Read) -> Result<Self, Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|err| Error::Serialization(err.to_string()))?;
+        let sections = sections(&bytes, b"r1cs", 1)?;
+
+        let mut header = section(&sections, 1)?;
+        header.prime::<F>()?;
+        let num_wires = header.u32()? as usize;
+        let num_public_outputs = header.u32()? as usize;
+        let num_public_inputs = header.u32()? as usize;
+        let num_private_inputs = header.u32()? as usize;
+        let _num_labels = header.u64()?;
+        let num_constraints = header.u32()? as usize;
+        if 1 + num_public_outputs + num_public_inputs + num_private_inputs > num_wires {
+            return Err(Error::Serialization(
+                "Too many inputs in r1cs header".to_string(),
+            ));
+        }
+
+        let mut body = section(&sections, 2)?;
+        let constraints = (0..num_constraints)
+            .map(|_| {
+                let mut lc = || {
+                    let num_terms = body.u32()?;
+                    (0..num_terms)
+                        .map(|_| {
+                            let wire = body.u32()? as usize;
+                            if wire >= num_wires {
+                                return Err(Error::Serialization(format!(
+                                    "Wire {wire} out of {num_wires} wires"
+                                )));
+                            }
+                            Ok((wire, body.fe()?))
+                        })
+                        .try_collect::<_, Vec<_>, _>()
+                };
+                Ok::<_, Error>([lc()?, lc()?, lc()?])
+            })
+            .try_collect::<_, Vec<_>, _>()?;
+
+        Ok(Self {
+            num_wires,
+            num_public_outputs,
+            num_public_inputs,
+            constraints,
+        })
+    }
+
+    pub fn num_wires(&self) -> usize {
+        self.num_wires
+    }
+
+    pub fn num_instances(&self) -> usize {
+        self.num_public_outputs + self.num_public_inputs
+    }
+
+    pub fn constraints(&self) -> &[[LinearCombination<F>; 3]] {
+        &self.constraints
+    }
+
+    pub fn circuit_info<E: WitnessEncoding>(
+        &self,
+        k: usize,
+    ) -> Result<PlonkishCircuitInfo<F>, Error> {
+        self.builder(k).0.circuit_info::<E>()
+    }
+
+    /// Returns circuit with `witness` of all wires, which is usually read by
+    /// [`read_wtns`].
+    pub fn circuit<E: WitnessEncoding>(
+        &self,
+        k: usize,
+        witness: Vec<F>,
+    ) -> Result<R1csCircuit<F>, Error> {
+        if witness.len() != self.num_wires {
+            return Err(Error::InvalidSnark(format!(
+                "Expect {} wires but got {} in witness",
+                self.num_wires,
+                witness.len()
+            )));
+        }
+
+        let (builder, columns) = self.builder(k);
+        let instances = witness[1..1 + self.num_instances()].to_vec();
+        let assignments = izip!(0.., self.wires())
+            .flat_map(|(row, wires)| {
+                izip!(&columns, wires).map(move |(column, wire)| (*column, row, wire))
+            })
+            .map(|(column, row, wire)| (column, row, witness[wire]))
+            .collect_vec();
+        builder.circuit::<E, Box<dyn Fn(&mut WitnessAssigner<F>) -> Result<(), Error>>>(
+            vec![instances],
+            Box::new(move |assigner| {
+                assignments
+                    .iter()
+                    .try_for_each(|(column, row, value)| assigner.assign(*column, *row, *value))
+            }),
+        )
+    }
+
+    /// Returns distinct non-constant wires of each constraint.
+    fn wires(&self) -> Vec<Vec<usize>> {
+        self.constraints
+            .iter()
+            .map(|constraint| {
+                constraint
+                    .iter()
+                    .flatten()
+                    .map(|(wire, _)| *wire)
+                    .filter(|wire| *wire != 0)
+                    .unique()
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn builder(&self, k: usize) -> (CircuitBuilder<F>, Vec<Column>) {
+        let wires = self.wires();
+        let width = wires.iter().map(Vec::len).max().unwrap_or_default().max(1);
+
+        let mut builder = CircuitBuilder::new(k);
+        let instance = builder.instance_column(self.num_instances());
+        let [q_a, q_b, q_c] = [(); 3].map(|_| {
+            iter::repeat_with(|| builder.fixed_column())
+                .take(width + 1)
+                .collect_vec()
+        });
+        let columns = iter::repeat_with(|| builder.witness_column(0))
+            .take(width)
+            .collect_vec();
+
+        let [a, b, c] = [&q_a, &q_b, &q_c].map(|q| {
+            izip!(q, &columns)
+                .map(|(q, w)| q.cur() * w.cur())
+                .chain(Some(q[width].cur()))
+                .sum::<Expression<F>>()
+        });
+        builder.create_gate(a * b - c);
+
+        let mut first_cell = HashMap::new();
+        for (row, (constraint, wires)) in izip!(&self.constraints, &wires).enumerate() {
+            let idx = |wire| {
+                if wire == 0 {
+                    width
+                } else {
+                    wires.iter().position(|w| *w == wire).unwrap()
+                }
+            };
+            for (q, lc) in izip!([&q_a, &q_b, &q_c], constraint) {
+                let mut coeffs = vec![F::ZERO; width + 1];
+                for (wire, coeff) in lc {
+                    coeffs[idx(*wire)] += coeff;
+                }
+                for (q, coeff) in izip!(q, coeffs) {
+                    if coeff != F::ZERO {
+                        builder.assign_fixed(*q, row, coeff);
+                    }
+                }
+            }
+            for (column, wire) in izip!(&columns, wires) {
+                match first_cell.get(wire) {
+                    Some(cell) => builder.copy(*cell, (*column, row)),
+                    None => {
+                        first_cell.insert(*wire, (*column, row));
+                    }
+                }
+            }
+        }
+        for (idx, wire) in (1..1 + self.num_instances()).enumerate() {
+            if let Some(cell) = first_cell.get(&wire) {
+                builder.copy((instance, idx), *cell);
+            }
+        }
+
+        (builder, columns)
+    }
+}
+
+/// Reads witness of all wires from circom `.wtns` file.
+pub fn read_wtns<F: PrimeField>(mut reader: impl Read) -> Result<Vec<F>, Error> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|err| Error::Serialization(err.to_string()))?;
+    let sections = sections(&bytes, b"wtns", 2)?;
+
+    let mut header = section(&sections, 1)?;
+    header.prime::<F>()?;
+    let num_witnesses = header.u32()?;
+
+    let mut body = section(&sections, 2)?;
+    (0..num_witnesses).map(|_| body.fe()).try_collect()
+}
+
+fn sections<'a>(
+    bytes: &'a [u8],
+    magic: &[u8; 4],
+    version: u32,
+) -> Result<HashMap<u32, Bytes<'a>>, Error> {
+    let mut bytes = Bytes(bytes);
+    if bytes.take(4)? != magic {
+        return Err(Error::Serialization("Invalid magic".to_string()));
+    }
+    if bytes.u32()? != version {
+        return Err(Error::Serialization("Unsupported version".to_string()));
+    }
+    let num_sections = bytes.u32()?;
+    (0..num_sections)
+        .map(|_| {
+            let ty = bytes.u32()?;
+            let size = bytes.u64()? as usize;
+            Ok((ty, Bytes(bytes.take(size)?)))
+        })
+        .try_collect()
+}
+
+fn section<'a>(sections: &HashMap<u32, Bytes<'a>>, ty: u32) -> Result<Bytes<'a>, Error> {
+    sections
+        .get(&ty)
+        .copied()
+        .ok_or_else(|| Error::Serialization(format!("Missing section {ty}")))
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Bytes<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < n {
+            return Err(Error::Serialization("Unexpected end of file".to_string()));
+        }
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// Reads field size and prime, and checks they match `F`.
+    fn prime<F: PrimeField>(&mut self) -> Result<(), Error> {
+        let field_size = self.u32()? as usize;
+        if field_size != F::Repr::default().as_ref().len()
+            || BigUint::from_bytes_le(self.take(field_size)?) != modulus::<F>()
+        {
+            return Err(Error::Serialization("Unmatched prime".to_string()));
+        }
+        Ok(())
+    }
+
+    fn fe<F: PrimeField>(&mut self) -> Result<F, Error> {
+        let bytes = self.take(F::Repr::default().as_ref().len())?;
+        if BigUint::from_bytes_le(bytes) >= modulus::<F>() {
+            return Err(Error::Serialization(
+                "Non-canonical field element".to_string(),
+            ));
+        }
+        Ok(fe_from_le_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::{
+            hyperplonk::HyperPlonk,
+            mock_prover::{MockProver, VerifyFailure},
+            test::run_plonkish_backend,
+            unihyperplonk::UniHyperPlonk,
+        },
+        frontend::circom::{read_wtns, R1cs},
+        pcs::{multilinear::MultilinearKzg, univariate::UnivariateKzg},
+        util::{
+            arithmetic::Field,
+            expression::rotate::{BinaryField, Lexical},
+            test::seeded_std_rng,
+            transcript::Keccak256Transcript,
+        },
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    const FIXTURES: [(&str, &[u8], &[u8]); 2] = [
+        (
+            "multiplier",
+            include_bytes!("../../fixtures/circom/multiplier.r1cs"),
+            include_bytes!("../../fixtures/circom/multiplier.wtns"),
+        ),
+        (
+            "cubic",
+            include_bytes!("../../fixtures/circom/cubic.r1cs"),
+            include_bytes!("../../fixtures/circom/cubic.wtns"),
+        ),
+    ];
+
+    #[test]
+    fn read_cubic() {
+        let r1cs = R1cs::<Fr>::read(FIXTURES[1].1).unwrap();
+        assert_eq!(r1cs.num_wires(), 5);
+        assert_eq!(r1cs.num_instances(), 2);
+        assert_eq!(
+            r1cs.constraints()[2],
+            [
+                vec![(4, Fr::ONE), (2, Fr::ONE), (0, Fr::from(5))],
+                vec![(0, Fr::ONE)],
+                vec![(1, Fr::ONE)],
+            ]
+        );
+        let witness = read_wtns::<Fr>(FIXTURES[1].2).unwrap();
+        assert_eq!(witness, [1, 35, 3, 9, 27].map(Fr::from));
+    }
+
+    #[test]
+    fn read_invalid() {
+        let (_, r1cs, wtns) = FIXTURES[0];
+        assert!(R1cs::<Fr>::read(&r1cs[..r1cs.len() - 1]).is_err());
+        assert!(R1cs::<Fr>::read(wtns).is_err());
+        assert!(read_wtns::<Fr>(r1cs).is_err());
+    }
+
+    macro_rules! tests {
+        ($suffix:ident, $pb:ty, $rotatable:ty) => {
+            paste::paste! {
+                #[test]
+                fn [<circom_mock_w_ $suffix>]() {
+                    for (_, r1cs, wtns) in FIXTURES {
+                        let r1cs = R1cs::<Fr>::read(r1cs).unwrap();
+                        let witness = read_wtns(wtns).unwrap();
+                        let circuit_info = r1cs.circuit_info::<$pb>(3).unwrap();
+                        let circuit = r1cs.circuit::<$pb>(3, witness).unwrap();
+                        let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                        assert_eq!(prover.verify(), Ok(()));
+                    }
+                }
+
+                #[test]
+                fn [<circom_mock_bad_witness_w_ $suffix>]() {
+                    let (_, r1cs, wtns) = FIXTURES[1];
+                    let r1cs = R1cs::<Fr>::read(r1cs).unwrap();
+                    let mut witness = read_wtns::<Fr>(wtns).unwrap();
+                    witness[3] += Fr::ONE;
+                    let circuit_info = r1cs.circuit_info::<$pb>(3).unwrap();
+                    let circuit = r1cs.circuit::<$pb>(3, witness).unwrap();
+                    let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                    let failures = prover.verify().unwrap_err();
+                    assert!(failures.iter().all(|failure| matches!(failure, VerifyFailure::Constraint { constraint: 0, .. })));
+                    assert_eq!(failures.len(), 2);
+                }
+
+                #[test]
+                fn [<circom_w_ $suffix>]() {
+                    for (_, r1cs, wtns) in FIXTURES {
+                        let r1cs = R1cs::<Fr>::read(r1cs).unwrap();
+                        let witness = read_wtns::<Fr>(wtns).unwrap();
+                        run_plonkish_backend::<_, $pb, Keccak256Transcript<_>, _>(3..6, |k| {
+                            let circuit_info = r1cs.circuit_info::<$pb>(k).unwrap();
+                            let circuit = r1cs.circuit::<$pb>(k, witness.clone()).unwrap();
+                            (circuit_info, circuit)
+                        });
+                    }
+                }
+            }
+        };
+    }
+
+    tests!(hyperplonk, HyperPlonk<MultilinearKzg<Bn256>>, BinaryField);
+    tests!(
+        unihyperplonk,
+        UniHyperPlonk<UnivariateKzg<Bn256>, true>,
+        Lexical
+    );
+}
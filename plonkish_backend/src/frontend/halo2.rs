@@ -1,5 +1,5 @@
 use crate::{
-    backend::{PlonkishCircuit, PlonkishCircuitInfo, WitnessEncoding},
+    backend::{Lookup, PlonkishCircuit, PlonkishCircuitInfo, WitnessEncoding},
     util::{
         arithmetic::{BatchInvert, Field},
         chain,
@@ -132,6 +132,24 @@ impl<F: Field, C: Circuit<F>> PlonkishCircuit<F> for Halo2Circuit<F, C> {
                     })
                     .collect_vec()
             })
+            .map(Lookup::Vector)
+            .collect();
+        let shuffles = cs
+            .shuffles()
+            .iter()
+            .map(|shuffle| {
+                shuffle
+                    .input_expressions()
+                    .iter()
+                    .zip(shuffle.shuffle_expressions())
+                    .map(|(input, shuffle)| {
+                        let [input, shuffle] = [input, shuffle].map(|expression| {
+                            convert_expression(cs, &advice_idx, challenge_idx, expression)
+                        });
+                        (input, shuffle)
+                    })
+                    .collect_vec()
+            })
             .collect();
 
         let num_instances = instances.iter().map(Vec::len).collect_vec();
@@ -156,6 +174,7 @@ impl<F: Field, C: Circuit<F>> PlonkishCircuit<F> for Halo2Circuit<F, C> {
             num_challenges: num_by_phase(&cs.challenge_phase()),
             constraints,
             lookups,
+            shuffles,
             permutations,
             max_degree: Some(cs.degree::<false>()),
         })
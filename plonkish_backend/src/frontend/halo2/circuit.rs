@@ -1,4 +1,4 @@
-pub use vanilla_plonk::VanillaPlonk;
+pub use vanilla_plonk::{VanillaPlonk, VanillaPlonkWithShuffle};
 
 mod vanilla_plonk {
     use crate::{
@@ -10,7 +10,7 @@ mod vanilla_plonk {
         plonk::{Advice, Assigned, Circuit, Column, ConstraintSystem, Error, Fixed},
         poly::Rotation,
     };
-    use rand::RngCore;
+    use rand::{seq::SliceRandom, RngCore};
     use std::iter;
 
     #[derive(Clone)]
@@ -121,4 +121,80 @@ mod vanilla_plonk {
             vec![vec![pi]]
         }
     }
+
+    #[derive(Clone)]
+    pub struct VanillaPlonkWithShuffleConfig {
+        vanilla_plonk: VanillaPlonkConfig,
+        shuffle: Column<Advice>,
+    }
+
+    impl VanillaPlonkWithShuffleConfig {
+        fn configure<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+            let vanilla_plonk = VanillaPlonkConfig::configure(meta);
+            let shuffle = meta.advice_column();
+            meta.shuffle("w_l ~ shuffle", |meta| {
+                let [w_l, shuffle] = [vanilla_plonk.wires[0], shuffle]
+                    .map(|column| meta.query_advice(column, Rotation::cur()));
+                vec![(w_l, shuffle)]
+            });
+            VanillaPlonkWithShuffleConfig {
+                vanilla_plonk,
+                shuffle,
+            }
+        }
+    }
+
+    /// [`VanillaPlonk`] with `w_l` shuffled into another advice column.
+    #[derive(Clone, Default)]
+    pub struct VanillaPlonkWithShuffle<F>(VanillaPlonk<F>, Vec<Assigned<F>>);
+
+    impl<F: Field> Circuit<F> for VanillaPlonkWithShuffle<F> {
+        type Config = VanillaPlonkWithShuffleConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            unimplemented!()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            meta.set_minimum_degree(4);
+            VanillaPlonkWithShuffleConfig::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            self.0
+                .synthesize(config.vanilla_plonk, layouter.namespace(|| ""))?;
+            layouter.assign_region(
+                || "",
+                |mut region| {
+                    for (offset, value) in self.1.iter().enumerate() {
+                        region.assign_advice(
+                            || "",
+                            config.shuffle,
+                            offset,
+                            || Value::known(*value),
+                        )?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    impl<F: Field> CircuitExt<F> for VanillaPlonkWithShuffle<F> {
+        fn rand(k: usize, mut rng: impl RngCore) -> Self {
+            let vanilla_plonk = VanillaPlonk::rand(k, &mut rng);
+            let mut shuffle = vanilla_plonk.1.iter().map(|values| values[5]).collect_vec();
+            shuffle.shuffle(&mut rng);
+            Self(vanilla_plonk, shuffle)
+        }
+
+        fn instances(&self) -> Vec<Vec<F>> {
+            self.0.instances()
+        }
+    }
 }
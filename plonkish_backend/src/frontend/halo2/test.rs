@@ -4,9 +4,15 @@ use crate::backend::{
     PlonkishCircuit,
 };
 use crate::{
-    frontend::halo2::{circuit::VanillaPlonk, CircuitExt, Halo2Circuit},
+    frontend::halo2::{
+        circuit::{VanillaPlonk, VanillaPlonkWithShuffle},
+        CircuitExt, Halo2Circuit,
+    },
     pcs::multilinear::MultilinearKzg,
-    util::transcript::Keccak256Transcript,
+    util::{
+        expression::{Expression, Query, Rotation},
+        transcript::Keccak256Transcript,
+    },
 };
 use halo2_curves::bn256::{Bn256, Fr};
 use rand::rngs::OsRng;
@@ -36,3 +42,24 @@ fn e2e_vanilla_plonk() {
         (circuit.circuit_info().unwrap(), circuit)
     });
 }
+
+#[test]
+fn vanilla_plonk_w_shuffle_circuit_info() {
+    let circuit =
+        Halo2Circuit::new::<HyperPlonk<()>>(3, VanillaPlonkWithShuffle::<Fr>::rand(3, OsRng));
+    let circuit_info = circuit.circuit_info().unwrap();
+    let [w_l, shuffle] =
+        [6, 9].map(|poly| Expression::Polynomial(Query::new(poly, Rotation::cur())));
+    assert_eq!(circuit_info.shuffles, vec![vec![(w_l, shuffle)]]);
+    assert_eq!(circuit_info.num_witness_polys, [4]);
+}
+
+#[test]
+fn e2e_vanilla_plonk_w_shuffle() {
+    type Pb = HyperPlonk<MultilinearKzg<Bn256>>;
+    run_plonkish_backend::<_, Pb, Keccak256Transcript<_>, _>(3..16, |num_vars| {
+        let circuit =
+            Halo2Circuit::new::<Pb>(num_vars, VanillaPlonkWithShuffle::rand(num_vars, OsRng));
+        (circuit.circuit_info().unwrap(), circuit)
+    });
+}
@@ -0,0 +1,716 @@
+use crate::{
+    backend::{
+        DecomposableLookup, Lookup, PlonkishCircuit, PlonkishCircuitInfo, Subtable, WitnessEncoding,
+    },
+    util::{
+        arithmetic::Field,
+        chain,
+        expression::{Expression, Query},
+        Itertools,
+    },
+    Error,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColumnType {
+    Instance,
+    Fixed,
+    Selector,
+    /// Witness column committed in the given phase.
+    Witness(usize),
+}
+
+/// Column declared by [`CircuitBuilder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Column {
+    id: usize,
+    column_type: ColumnType,
+}
+
+impl Column {
+    pub fn column_type(&self) -> ColumnType {
+        self.column_type
+    }
+
+    pub fn cur<F: Clone>(&self) -> Expression<F> {
+        self.rot(0)
+    }
+
+    pub fn next<F: Clone>(&self) -> Expression<F> {
+        self.rot(1)
+    }
+
+    pub fn prev<F: Clone>(&self) -> Expression<F> {
+        self.rot(-1)
+    }
+
+    /// Returns expression querying this column on the row offset by
+    /// `rotation` from the current one.
+    pub fn rot<F: Clone>(&self, rotation: i32) -> Expression<F> {
+        Expression::Polynomial(Query::new(self.id, rotation))
+    }
+}
+
+/// Challenge squeezed after witness columns of `phase` are committed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Challenge {
+    id: usize,
+    phase: usize,
+}
+
+impl Challenge {
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    pub fn expr<F: Clone>(&self) -> Expression<F> {
+        Expression::Challenge(self.id)
+    }
+}
+
+/// Builder of [`PlonkishCircuitInfo`] and [`NativeCircuit`] without going
+/// through halo2.
+///
+/// Rows given to the builder are logical ones, which are mapped by
+/// [`WitnessEncoding::row_mapping`] of the chosen backend when building, so
+/// the same circuit works with every backend.
+#[derive(Clone, Debug)]
+pub struct CircuitBuilder<F> {
+    k: usize,
+    columns: Vec<Column>,
+    num_instances: Vec<usize>,
+    challenges: Vec<Challenge>,
+    constraints: Vec<Expression<F>>,
+    lookups: Vec<Lookup<F>>,
+    shuffles: Vec<Vec<(Expression<F>, Expression<F>)>>,
+    copies: Vec<[(Column, usize); 2]>,
+    fixeds: Vec<(Column, usize, F)>,
+    max_degree: Option<usize>,
+}
+
+impl<F: Field> CircuitBuilder<F> {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            columns: Vec::new(),
+            num_instances: Vec::new(),
+            challenges: Vec::new(),
+            constraints: Vec::new(),
+            lookups: Vec::new(),
+            shuffles: Vec::new(),
+            copies: Vec::new(),
+            fixeds: Vec::new(),
+            max_degree: None,
+        }
+    }
+
+    pub fn instance_column(&mut self, num_instances: usize) -> Column {
+        self.num_instances.push(num_instances);
+        self.column(ColumnType::Instance)
+    }
+
+    pub fn fixed_column(&mut self) -> Column {
+        self.column(ColumnType::Fixed)
+    }
+
+    pub fn selector_column(&mut self) -> Column {
+        self.column(ColumnType::Selector)
+    }
+
+    pub fn witness_column(&mut self, phase: usize) -> Column {
+        self.column(ColumnType::Witness(phase))
+    }
+
+    pub fn challenge(&mut self, phase: usize) -> Challenge {
+        let challenge = Challenge {
+            id: self.challenges.len(),
+            phase,
+        };
+        self.challenges.push(challenge);
+        challenge
+    }
+
+    /// Adds a constraint which is enforced to be zero on every row.
+    pub fn create_gate(&mut self, constraint: Expression<F>) {
+        self.constraints.push(constraint);
+    }
+
+    /// Adds a vector lookup of tuples of `(input, table)`, where table could
+    /// also be queries of witness columns. When input is masked by selector,
+    /// table should be masked by another selector disabled on some row, so the
+    /// masked input finds the zero tuple in table.
+    pub fn lookup(&mut self, lookup: impl IntoIterator<Item = (Expression<F>, Expression<F>)>) {
+        self.lookups
+            .push(Lookup::Vector(lookup.into_iter().collect()));
+    }
+
+    /// Adds a decomposable lookup of `output` into a large table, where each
+    /// chunk of `(index, value, subtable)` is looked up from its subtable, and
+    /// `combine` queries polynomial `i` as value of the `i`-th chunk. See
+    /// [`DecomposableLookup`] for details.
+    pub fn decomposable_lookup(
+        &mut self,
+        chunks: impl IntoIterator<Item = (Expression<F>, Expression<F>, Subtable)>,
+        output: Expression<F>,
+        combine: Expression<F>,
+    ) {
+        self.lookups.push(Lookup::Decomposable(DecomposableLookup {
+            chunks: chunks.into_iter().collect(),
+            output,
+            combine,
+        }));
+    }
+
+    /// Adds a vector shuffle of tuples of `(input, shuffle)`, which enforces
+    /// inputs to be a permutation of shuffles.
+    pub fn shuffle(&mut self, shuffle: impl IntoIterator<Item = (Expression<F>, Expression<F>)>) {
+        self.shuffles.push(shuffle.into_iter().collect());
+    }
+
+    pub fn copy(&mut self, lhs: (Column, usize), rhs: (Column, usize)) {
+        self.copies.push([lhs, rhs]);
+    }
+
+    pub fn assign_fixed(&mut self, column: Column, row: usize, value: F) {
+        assert!(matches!(
+            column.column_type,
+            ColumnType::Fixed | ColumnType::Selector
+        ));
+        self.fixeds.push((column, row, value));
+    }
+
+    pub fn enable_selector(&mut self, column: Column, row: usize) {
+        assert_eq!(column.column_type, ColumnType::Selector);
+        self.fixeds.push((column, row, F::ONE));
+    }
+
+    pub fn set_max_degree(&mut self, max_degree: usize) {
+        self.max_degree = Some(max_degree);
+    }
+
+    pub fn circuit_info<E: WitnessEncoding>(&self) -> Result<PlonkishCircuitInfo<F>, Error> {
+        let row_mapping = E::row_mapping(self.k);
+        let layout = self.layout();
+        let row = |row: usize| {
+            row_mapping.get(row).copied().ok_or_else(|| {
+                Error::InvalidSnark(format!(
+                    "Row {row} out of {} usable rows",
+                    row_mapping.len()
+                ))
+            })
+        };
+
+        let num_instance_polys = self.num_instances.len();
+        let mut preprocess_polys = vec![vec![F::ZERO; 1 << self.k]; layout.num_preprocess_polys];
+        for (column, idx, value) in self.fixeds.iter() {
+            preprocess_polys[layout.poly_idx[column.id] - num_instance_polys][row(*idx)?] = *value;
+        }
+
+        let mut permutation = Permutation::default();
+        for [(lhs, lhs_row), (rhs, rhs_row)] in self.copies.iter() {
+            permutation.copy(
+                (layout.poly_idx[lhs.id], row(*lhs_row)?),
+                (layout.poly_idx[rhs.id], row(*rhs_row)?),
+            );
+        }
+
+        let circuit_info = PlonkishCircuitInfo {
+            k: self.k,
+            num_instances: self.num_instances.clone(),
+            preprocess_polys,
+            num_witness_polys: layout.num_witness_polys.clone(),
+            num_challenges: layout.num_challenges.clone(),
+            constraints: self
+                .constraints
+                .iter()
+                .map(|constraint| layout.expression(constraint))
+                .collect(),
+            lookups: self
+                .lookups
+                .iter()
+                .map(|lookup| layout.lookup(lookup))
+                .collect(),
+            shuffles: layout.lookups(&self.shuffles),
+            permutations: permutation.into_cycles(),
+            max_degree: self.max_degree,
+        };
+        circuit_info.validate().map_err(Error::InvalidCircuitInfo)?;
+        Ok(circuit_info)
+    }
+
+    /// Returns circuit with `instances` whose witness is assigned by `assign`,
+    /// which is called once for each phase.
+    pub fn circuit<E, W>(
+        &self,
+        instances: Vec<Vec<F>>,
+        assign: W,
+    ) -> Result<NativeCircuit<F, W>, Error>
+    where
+        E: WitnessEncoding,
+        W: Fn(&mut WitnessAssigner<F>) -> Result<(), Error>,
+    {
+        if instances.len() != self.num_instances.len()
+            || instances
+                .iter()
+                .zip(self.num_instances.iter())
+                .any(|(instances, num_instances)| instances.len() != *num_instances)
+        {
+            return Err(Error::InvalidSnark(
+                "Unmatched number of instances".to_string(),
+            ));
+        }
+
+        Ok(NativeCircuit {
+            circuit_info: self.circuit_info::<E>()?,
+            instances,
+            layout: self.layout(),
+            row_mapping: E::row_mapping(self.k),
+            assign,
+        })
+    }
+
+    fn column(&mut self, column_type: ColumnType) -> Column {
+        let column = Column {
+            id: self.columns.len(),
+            column_type,
+        };
+        self.columns.push(column);
+        column
+    }
+
+    fn layout(&self) -> Layout {
+        let num_phases = chain![
+            self.columns
+                .iter()
+                .filter_map(|column| match column.column_type {
+                    ColumnType::Witness(phase) => Some(phase + 1),
+                    _ => None,
+                }),
+            self.challenges.iter().map(|challenge| challenge.phase + 1),
+        ]
+        .max()
+        .unwrap_or_default();
+
+        let columns_of = |is_type: fn(&ColumnType) -> bool| {
+            self.columns
+                .iter()
+                .filter(|column| is_type(&column.column_type))
+                .collect_vec()
+        };
+        let instances = columns_of(|column_type| matches!(column_type, ColumnType::Instance));
+        let preprocesses = columns_of(|column_type| {
+            matches!(column_type, ColumnType::Fixed | ColumnType::Selector)
+        });
+        let witnesses = (0..num_phases)
+            .map(|phase| {
+                self.columns
+                    .iter()
+                    .filter(|column| column.column_type == ColumnType::Witness(phase))
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let mut poly_idx = vec![0; self.columns.len()];
+        let mut witness_idx = vec![None; self.columns.len()];
+        for (idx, column) in chain![&instances, &preprocesses].enumerate() {
+            poly_idx[column.id] = idx;
+        }
+        let witness_offset = instances.len() + preprocesses.len();
+        for (idx, (phase, idx_in_phase, column)) in witnesses
+            .iter()
+            .enumerate()
+            .flat_map(|(phase, columns)| {
+                columns
+                    .iter()
+                    .enumerate()
+                    .map(move |(idx_in_phase, column)| (phase, idx_in_phase, column))
+            })
+            .enumerate()
+        {
+            poly_idx[column.id] = witness_offset + idx;
+            witness_idx[column.id] = Some((phase, idx_in_phase));
+        }
+
+        let mut challenge_idx = vec![0; self.challenges.len()];
+        let challenges = self
+            .challenges
+            .iter()
+            .sorted_by_key(|challenge| challenge.phase);
+        for (idx, challenge) in challenges.enumerate() {
+            challenge_idx[challenge.id] = idx;
+        }
+
+        Layout {
+            num_preprocess_polys: preprocesses.len(),
+            num_witness_polys: witnesses.iter().map(Vec::len).collect(),
+            num_challenges: (0..num_phases)
+                .map(|phase| {
+                    self.challenges
+                        .iter()
+                        .filter(|challenge| challenge.phase == phase)
+                        .count()
+                })
+                .collect(),
+            poly_idx,
+            witness_idx,
+            challenge_idx,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Layout {
+    num_preprocess_polys: usize,
+    num_witness_polys: Vec<usize>,
+    num_challenges: Vec<usize>,
+    /// Polynomial index of each column.
+    poly_idx: Vec<usize>,
+    /// Phase and index in phase of each witness column.
+    witness_idx: Vec<Option<(usize, usize)>>,
+    /// Challenge index of each challenge ordered by phase.
+    challenge_idx: Vec<usize>,
+}
+
+impl Layout {
+    fn expression<F: Field>(&self, expression: &Expression<F>) -> Expression<F> {
+        expression.evaluate(
+            &|constant| Expression::Constant(constant),
+            &|common_poly| Expression::CommonPolynomial(common_poly),
+            &|query| {
+                Expression::Polynomial(Query::new(self.poly_idx[query.poly()], query.rotation()))
+            },
+            &|challenge| Expression::Challenge(self.challenge_idx[challenge]),
+            &|value| -value,
+            &|lhs, rhs| lhs + rhs,
+            &|lhs, rhs| lhs * rhs,
+            &|value, scalar| value * scalar,
+        )
+    }
+
+    fn lookup<F: Field>(&self, lookup: &Lookup<F>) -> Lookup<F> {
+        match lookup {
+            Lookup::Vector(lookup) => Lookup::Vector(self.vector_lookup(lookup)),
+            Lookup::Decomposable(lookup) => Lookup::Decomposable(DecomposableLookup {
+                chunks: lookup
+                    .chunks
+                    .iter()
+                    .map(|(index, value, subtable)| {
+                        (self.expression(index), self.expression(value), *subtable)
+                    })
+                    .collect(),
+                output: self.expression(&lookup.output),
+                combine: lookup.combine.clone(),
+            }),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn lookups<F: Field>(
+        &self,
+        lookups: &[Vec<(Expression<F>, Expression<F>)>],
+    ) -> Vec<Vec<(Expression<F>, Expression<F>)>> {
+        lookups
+            .iter()
+            .map(|lookup| self.vector_lookup(lookup))
+            .collect()
+    }
+
+    fn vector_lookup<F: Field>(
+        &self,
+        lookup: &[(Expression<F>, Expression<F>)],
+    ) -> Vec<(Expression<F>, Expression<F>)> {
+        lookup
+            .iter()
+            .map(|(input, table)| (self.expression(input), self.expression(table)))
+            .collect()
+    }
+}
+
+/// Circuit built by [`CircuitBuilder::circuit`].
+#[derive(Clone, Debug)]
+pub struct NativeCircuit<F, W> {
+    circuit_info: PlonkishCircuitInfo<F>,
+    instances: Vec<Vec<F>>,
+    layout: Layout,
+    row_mapping: Vec<usize>,
+    assign: W,
+}
+
+impl<F, W> PlonkishCircuit<F> for NativeCircuit<F, W>
+where
+    F: Field,
+    W: Fn(&mut WitnessAssigner<F>) -> Result<(), Error>,
+{
+    fn circuit_info_without_preprocess(&self) -> Result<PlonkishCircuitInfo<F>, Error> {
+        Ok(self.circuit_info.clone())
+    }
+
+    fn circuit_info(&self) -> Result<PlonkishCircuitInfo<F>, Error> {
+        Ok(self.circuit_info.clone())
+    }
+
+    fn instances(&self) -> &[Vec<F>] {
+        &self.instances
+    }
+
+    fn synthesize(&self, phase: usize, challenges: &[F]) -> Result<Vec<Vec<F>>, Error> {
+        let mut assigner = WitnessAssigner {
+            phase,
+            instances: &self.instances,
+            challenges,
+            layout: &self.layout,
+            row_mapping: &self.row_mapping,
+            polys: vec![
+                vec![F::ZERO; 1 << self.circuit_info.k];
+                self.layout.num_witness_polys[phase]
+            ],
+        };
+        (self.assign)(&mut assigner)?;
+        Ok(assigner.polys)
+    }
+}
+
+/// Assigner of witness columns in current phase, which ignores assignments to
+/// witness columns of other phases.
+#[derive(Debug)]
+pub struct WitnessAssigner<'a, F> {
+    phase: usize,
+    instances: &'a [Vec<F>],
+    challenges: &'a [F],
+    layout: &'a Layout,
+    row_mapping: &'a [usize],
+    polys: Vec<Vec<F>>,
+}
+
+impl<'a, F: Field> WitnessAssigner<'a, F> {
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    pub fn instance(&self, column: Column, row: usize) -> Option<F> {
+        assert_eq!(column.column_type, ColumnType::Instance);
+        self.instances[self.layout.poly_idx[column.id]]
+            .get(row)
+            .copied()
+    }
+
+    /// Returns value of `challenge`, or `None` if it's not squeezed yet.
+    pub fn challenge(&self, challenge: Challenge) -> Option<F> {
+        self.challenges
+            .get(self.layout.challenge_idx[challenge.id])
+            .copied()
+    }
+
+    pub fn assign(&mut self, column: Column, row: usize, value: F) -> Result<(), Error> {
+        let Some((phase, idx)) = self.layout.witness_idx[column.id] else {
+            return Err(Error::InvalidSnark(format!(
+                "Assign to non-witness column {column:?}"
+            )));
+        };
+        if phase != self.phase {
+            return Ok(());
+        }
+        let Some(row) = self.row_mapping.get(row).copied() else {
+            return Err(Error::InvalidSnark(format!(
+                "Row {row} out of {} usable rows",
+                self.row_mapping.len()
+            )));
+        };
+        self.polys[idx][row] = value;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Permutation {
+    cycles: Vec<HashSet<(usize, usize)>>,
+    cycle_idx: HashMap<(usize, usize), usize>,
+}
+
+impl Permutation {
+    fn copy(&mut self, lhs: (usize, usize), rhs: (usize, usize)) {
+        match (
+            self.cycle_idx.get(&lhs).copied(),
+            self.cycle_idx.get(&rhs).copied(),
+        ) {
+            (Some(lhs_cycle_idx), Some(rhs_cycle_idx)) => {
+                if lhs_cycle_idx == rhs_cycle_idx {
+                    return;
+                }
+                for cell in self.cycles[rhs_cycle_idx].iter().copied() {
+                    self.cycle_idx.insert(cell, lhs_cycle_idx);
+                }
+                let rhs_cycle = mem::take(&mut self.cycles[rhs_cycle_idx]);
+                self.cycles[lhs_cycle_idx].extend(rhs_cycle);
+            }
+            cycle_idx => {
+                let cycle_idx = if let (Some(cycle_idx), None) | (None, Some(cycle_idx)) = cycle_idx
+                {
+                    cycle_idx
+                } else {
+                    let cycle_idx = self.cycles.len();
+                    self.cycles.push(Default::default());
+                    cycle_idx
+                };
+                for cell in [lhs, rhs] {
+                    self.cycles[cycle_idx].insert(cell);
+                    self.cycle_idx.insert(cell, cycle_idx);
+                }
+            }
+        };
+    }
+
+    fn into_cycles(self) -> Vec<Vec<(usize, usize)>> {
+        self.cycles
+            .into_iter()
+            .filter_map(|cycle| {
+                (!cycle.is_empty()).then(|| cycle.into_iter().sorted().collect_vec())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        accumulation::{protostar::Protostar, test::run_accumulation_scheme},
+        backend::{
+            hyperplonk::HyperPlonk,
+            mock_prover::{MockProver, VerifyFailure},
+            test::run_plonkish_backend,
+            unihyperplonk::UniHyperPlonk,
+            PlonkishCircuitInfo, WitnessEncoding,
+        },
+        frontend::native::{CircuitBuilder, NativeCircuit, WitnessAssigner},
+        pcs::{multilinear::MultilinearKzg, univariate::UnivariateKzg},
+        util::{
+            arithmetic::Field,
+            expression::rotate::{BinaryField, Lexical},
+            test::seeded_std_rng,
+            transcript::Keccak256Transcript,
+            Itertools,
+        },
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use std::iter;
+
+    fn fibonacci(n: usize) -> Vec<Fr> {
+        iter::successors(Some((Fr::ONE, Fr::ONE)), |(a, b)| Some((*b, *a + b)))
+            .map(|(a, _)| a)
+            .take(n)
+            .collect()
+    }
+
+    // Fibonacci sequence in `a` with every term looked up in a fixed table,
+    // and `b = a * theta` in the second phase.
+    fn fibonacci_circuit<E: WitnessEncoding>(
+        k: usize,
+        bad_witness: bool,
+    ) -> (
+        PlonkishCircuitInfo<Fr>,
+        NativeCircuit<Fr, impl Fn(&mut WitnessAssigner<Fr>) -> Result<(), Error>>,
+    ) {
+        let n = E::row_mapping(k).len();
+        let fibonacci = fibonacci(n);
+
+        let mut builder = CircuitBuilder::new(k);
+        let instance = builder.instance_column(3);
+        let q_fib = builder.selector_column();
+        let q_b = builder.selector_column();
+        let table = builder.fixed_column();
+        let a = builder.witness_column(0);
+        let theta = builder.challenge(0);
+        let b = builder.witness_column(1);
+
+        builder.create_gate(q_fib.cur() * (a.rot(2) - a.next() - a.cur()));
+        builder.create_gate(q_b.cur() * (b.cur() - a.cur() * theta.expr()));
+        builder.lookup([(q_fib.cur() * a.cur(), table.cur())]);
+        for (row, fibonacci) in fibonacci[..n - 1].iter().enumerate() {
+            builder.assign_fixed(table, row, *fibonacci);
+        }
+        for row in 0..n - 2 {
+            builder.enable_selector(q_fib, row);
+        }
+        for row in 0..n {
+            builder.enable_selector(q_b, row);
+        }
+        builder.copy((instance, 0), (a, 0));
+        builder.copy((instance, 1), (a, 1));
+        builder.copy((instance, 2), (a, n - 1));
+
+        let circuit_info = builder.circuit_info::<E>().unwrap();
+        let instances = vec![vec![fibonacci[0], fibonacci[1], fibonacci[n - 1]]];
+        let circuit = builder
+            .circuit::<E, _>(instances, move |assigner| {
+                for (row, value) in fibonacci.iter().enumerate() {
+                    assigner.assign(a, row, *value)?;
+                }
+                if bad_witness {
+                    assigner.assign(a, 3, Fr::ONE)?;
+                }
+                if let Some(theta) = assigner.challenge(theta) {
+                    for (row, value) in fibonacci.iter().enumerate() {
+                        assigner.assign(b, row, *value * theta)?;
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+        (circuit_info, circuit)
+    }
+
+    macro_rules! tests {
+        ($suffix:ident, $pb:ty, $rotatable:ty) => {
+            paste::paste! {
+                #[test]
+                fn [<fibonacci_mock_w_ $suffix>]() {
+                    for k in 3..10 {
+                        let (circuit_info, circuit) = fibonacci_circuit::<$pb>(k, false);
+                        let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                        assert_eq!(prover.verify(), Ok(()));
+                    }
+                }
+
+                #[test]
+                fn [<fibonacci_mock_bad_witness_w_ $suffix>]() {
+                    let (circuit_info, circuit) = fibonacci_circuit::<$pb>(4, true);
+                    let prover = MockProver::<_, $rotatable>::run(&circuit_info, &circuit, seeded_std_rng()).unwrap();
+                    let failures = prover.verify().unwrap_err();
+                    assert!(failures.iter().any(|failure| matches!(failure, VerifyFailure::Constraint { constraint: 0, .. })));
+                    assert!(failures.iter().any(|failure| matches!(failure, VerifyFailure::Constraint { constraint: 1, .. })));
+                }
+
+                #[test]
+                fn [<fibonacci_w_ $suffix>]() {
+                    run_plonkish_backend::<_, $pb, Keccak256Transcript<_>, _>(3..10, |k| {
+                        fibonacci_circuit::<$pb>(k, false)
+                    });
+                }
+            }
+        };
+    }
+
+    tests!(hyperplonk, HyperPlonk<MultilinearKzg<Bn256>>, BinaryField);
+    tests!(
+        unihyperplonk,
+        UniHyperPlonk<UnivariateKzg<Bn256>, true>,
+        Lexical
+    );
+
+    #[test]
+    fn fibonacci_w_protostar_hyperplonk() {
+        type Fs = Protostar<HyperPlonk<MultilinearKzg<Bn256>>>;
+        run_accumulation_scheme::<_, Fs, Keccak256Transcript<_>, _>(3..10, |k| {
+            let (circuit_info, _) = fibonacci_circuit::<Fs>(k, false);
+            let circuits = iter::repeat_with(|| fibonacci_circuit::<Fs>(k, false).1)
+                .take(3)
+                .collect_vec();
+            (circuit_info, circuits)
+        });
+    }
+}
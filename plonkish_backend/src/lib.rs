@@ -12,6 +12,7 @@ pub use halo2_curves;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Error {
+    InvalidCircuitInfo(Vec<backend::CircuitInfoError>),
     InvalidSumcheck(String),
     InvalidPcsParam(String),
     InvalidPcsOpen(String),
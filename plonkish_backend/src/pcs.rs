@@ -1,14 +1,17 @@
 use crate::{
     poly::Polynomial,
     util::{
-        arithmetic::Field,
-        transcript::{TranscriptRead, TranscriptWrite},
-        DeserializeOwned, Serialize,
+        arithmetic::{
+            powers, variable_base_msm, Curve, CurveAffine, Field, MultiMillerLoop, PrimeField,
+        },
+        chain,
+        transcript::{FieldTranscript, TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Itertools, Serialize,
     },
     Error,
 };
 use rand::RngCore;
-use std::fmt::Debug;
+use std::{fmt::Debug, mem::size_of};
 
 pub mod multilinear;
 pub mod univariate;
@@ -163,13 +166,114 @@ pub trait Additive<F: Field>: Clone + Debug + Default + PartialEq + Eq {
         Self: 'b;
 }
 
+/// Accumulator of a deferred KZG pairing check `e(lhs, g2) = ∏ e(rhs_i, h_i)`,
+/// where `g2` and `h_i` are given by [`DeferredKzg::accumulator_g2s`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KzgAccumulator<C: CurveAffine> {
+    lhs: C,
+    rhs: Vec<C>,
+}
+
+impl<C: CurveAffine> KzgAccumulator<C> {
+    pub fn new(lhs: C, rhs: Vec<C>) -> Self {
+        Self { lhs, rhs }
+    }
+
+    pub fn lhs(&self) -> &C {
+        &self.lhs
+    }
+
+    pub fn rhs(&self) -> &[C] {
+        &self.rhs
+    }
+
+    /// Folds `accumulators` into one by powers of `r`, which should be sampled
+    /// after all `accumulators` are fixed, so the folded one is valid only if
+    /// all of them are valid with overwhelming probability.
+    pub fn fold<'a>(accumulators: impl IntoIterator<Item = &'a Self>, r: &C::Scalar) -> Self {
+        let accumulators = accumulators.into_iter().collect_vec();
+        assert!(!accumulators.is_empty());
+
+        let num_rhs = accumulators[0].rhs.len();
+        assert!(accumulators.iter().all(|acc| acc.rhs.len() == num_rhs));
+
+        let powers_of_r = powers(*r).take(accumulators.len()).collect_vec();
+        let lhs = variable_base_msm(&powers_of_r, accumulators.iter().map(|acc| &acc.lhs));
+        let rhs = (0..num_rhs)
+            .map(|idx| {
+                variable_base_msm(&powers_of_r, accumulators.iter().map(|acc| &acc.rhs[idx]))
+                    .to_affine()
+            })
+            .collect();
+        Self::new(lhs.to_affine(), rhs)
+    }
+}
+
+/// KZG based PCS whose verifier can output the pairing check as a
+/// [`KzgAccumulator`] instead of checking it, so many of them can be folded and
+/// decided by a single pairing.
+pub trait DeferredKzg<M: MultiMillerLoop>: PolynomialCommitmentScheme<M::Scalar> {
+    /// Returns `g2` followed by `h_i` of the pairing check of accumulator.
+    fn accumulator_g2s(vp: &Self::VerifierParam) -> Vec<M::G2Affine>;
+
+    /// Same as [`PolynomialCommitmentScheme::verify`] but returns accumulator
+    /// of the pairing check.
+    fn verify_deferred(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<M::Scalar, Self::Polynomial>,
+        eval: &M::Scalar,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error>;
+
+    /// Same as [`PolynomialCommitmentScheme::batch_verify`] but returns
+    /// accumulator of the pairing check.
+    fn batch_verify_deferred<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<M::Scalar, Self::Polynomial>],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error>
+    where
+        Self::Commitment: 'a;
+
+    fn decide(
+        vp: &Self::VerifierParam,
+        accumulator: &KzgAccumulator<M::G1Affine>,
+    ) -> Result<(), Error> {
+        let g2s = Self::accumulator_g2s(vp);
+        assert_eq!(g2s.len(), accumulator.rhs.len() + 1);
+
+        let g2s = chain![[-g2s[0]], g2s[1..].iter().copied()]
+            .map(M::G2Prepared::from)
+            .collect_vec();
+        let terms = chain![[&accumulator.lhs], &accumulator.rhs]
+            .zip(&g2s)
+            .collect_vec();
+        M::pairings_product_is_identity(&terms)
+            .then_some(())
+            .ok_or_else(|| Error::InvalidPcsOpen("Invalid KZG accumulator".to_string()))
+    }
+}
+
+fn squeeze_challenge_idx<F: PrimeField>(
+    transcript: &mut impl FieldTranscript<F>,
+    cap: usize,
+) -> usize {
+    let challenge = transcript.squeeze_challenge();
+    let mut bytes = [0; size_of::<u32>()];
+    bytes.copy_from_slice(&challenge.to_repr().as_ref()[..size_of::<u32>()]);
+    u32::from_le_bytes(bytes) as usize % cap
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
-        pcs::{Evaluation, PolynomialCommitmentScheme},
+        pcs::{DeferredKzg, Evaluation, KzgAccumulator, PolynomialCommitmentScheme},
         poly::Polynomial,
         util::{
-            arithmetic::PrimeField,
+            arithmetic::{Field, MultiMillerLoop, PrimeField},
             chain,
             transcript::{InMemoryTranscript, TranscriptRead, TranscriptWrite},
             Itertools,
@@ -187,39 +291,54 @@ mod test {
             + InMemoryTranscript<Param = ()>,
     {
         for k in 3..16 {
-            // Setup
-            let (pp, vp) = {
-                let mut rng = OsRng;
-                let poly_size = 1 << k;
-                let param = Pcs::setup(poly_size, 1, &mut rng).unwrap();
-                Pcs::trim(&param, poly_size, 1).unwrap()
-            };
-            // Commit and open
-            let proof = {
-                let mut transcript = T::new(());
-                let poly = <Pcs::Polynomial as Polynomial<F>>::rand(1 << k, OsRng);
-                let comm = Pcs::commit_and_write(&pp, &poly, &mut transcript).unwrap();
-                let point = <Pcs::Polynomial as Polynomial<F>>::squeeze_point(k, &mut transcript);
-                let eval = poly.evaluate(&point);
-                transcript.write_field_element(&eval).unwrap();
-                Pcs::open(&pp, &poly, &comm, &point, &eval, &mut transcript).unwrap();
-                transcript.into_proof()
-            };
-            // Verify
-            let result = {
-                let mut transcript = T::from_proof((), proof.as_slice());
-                Pcs::verify(
-                    &vp,
-                    &Pcs::read_commitment(&vp, &mut transcript).unwrap(),
-                    &<Pcs::Polynomial as Polynomial<F>>::squeeze_point(k, &mut transcript),
-                    &transcript.read_field_element().unwrap(),
-                    &mut transcript,
-                )
-            };
-            assert_eq!(result, Ok(()));
+            run_commit_open_verify_with_poly_size::<F, Pcs, T>(1 << k, 1 << k);
         }
     }
 
+    /// Same as [`run_commit_open_verify`] but with `Pcs` setup for `param_size`
+    /// and trimmed to `poly_size`, which are not necessarily power of two.
+    pub(super) fn run_commit_open_verify_with_poly_size<F, Pcs, T>(
+        param_size: usize,
+        poly_size: usize,
+    ) where
+        F: PrimeField,
+        Pcs: PolynomialCommitmentScheme<F>,
+        T: TranscriptRead<Pcs::CommitmentChunk, F>
+            + TranscriptWrite<Pcs::CommitmentChunk, F>
+            + InMemoryTranscript<Param = ()>,
+    {
+        let k = poly_size.next_power_of_two().ilog2() as usize;
+        // Setup
+        let (pp, vp) = {
+            let mut rng = OsRng;
+            let param = Pcs::setup(param_size, 1, &mut rng).unwrap();
+            Pcs::trim(&param, poly_size, 1).unwrap()
+        };
+        // Commit and open
+        let proof = {
+            let mut transcript = T::new(());
+            let poly = <Pcs::Polynomial as Polynomial<F>>::rand(poly_size, OsRng);
+            let comm = Pcs::commit_and_write(&pp, &poly, &mut transcript).unwrap();
+            let point = <Pcs::Polynomial as Polynomial<F>>::squeeze_point(k, &mut transcript);
+            let eval = poly.evaluate(&point);
+            transcript.write_field_element(&eval).unwrap();
+            Pcs::open(&pp, &poly, &comm, &point, &eval, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        // Verify
+        let result = {
+            let mut transcript = T::from_proof((), proof.as_slice());
+            Pcs::verify(
+                &vp,
+                &Pcs::read_commitment(&vp, &mut transcript).unwrap(),
+                &<Pcs::Polynomial as Polynomial<F>>::squeeze_point(k, &mut transcript),
+                &transcript.read_field_element().unwrap(),
+                &mut transcript,
+            )
+        };
+        assert_eq!(result, Ok(()));
+    }
+
     pub(super) fn run_batch_commit_open_verify<F, Pcs, T>()
     where
         F: PrimeField,
@@ -229,72 +348,148 @@ mod test {
             + InMemoryTranscript<Param = ()>,
     {
         for k in 3..16 {
-            let batch_size = 8;
-            let num_points = batch_size >> 1;
-            let mut rng = OsRng;
-            // Setup
-            let (pp, vp) = {
-                let poly_size = 1 << k;
-                let param = Pcs::setup(poly_size, batch_size, &mut rng).unwrap();
-                Pcs::trim(&param, poly_size, batch_size).unwrap()
-            };
-            // Batch commit and open
-            let evals = chain![
-                (0..num_points).map(|point| (0, point)),
-                (0..batch_size).map(|poly| (poly, 0)),
-                iter::repeat_with(|| (rng.gen_range(0..batch_size), rng.gen_range(0..num_points)))
+            run_batch_commit_open_verify_with_poly_size::<F, Pcs, T>(1 << k, 1 << k);
+        }
+    }
+
+    /// Same as [`run_batch_commit_open_verify`] but with `Pcs` setup for
+    /// `param_size` and trimmed to `poly_size`, which are not necessarily power
+    /// of two.
+    pub(super) fn run_batch_commit_open_verify_with_poly_size<F, Pcs, T>(
+        param_size: usize,
+        poly_size: usize,
+    ) where
+        F: PrimeField,
+        Pcs: PolynomialCommitmentScheme<F>,
+        T: TranscriptRead<Pcs::CommitmentChunk, F>
+            + TranscriptWrite<Pcs::CommitmentChunk, F>
+            + InMemoryTranscript<Param = ()>,
+    {
+        let k = poly_size.next_power_of_two().ilog2() as usize;
+        let batch_size = 8;
+        let num_points = batch_size >> 1;
+        let mut rng = OsRng;
+        // Setup
+        let (pp, vp) = {
+            let param = Pcs::setup(param_size, batch_size, &mut rng).unwrap();
+            Pcs::trim(&param, poly_size, batch_size).unwrap()
+        };
+        // Batch commit and open
+        let evals = chain![
+            (0..num_points).map(|point| (0, point)),
+            (0..batch_size).map(|poly| (poly, 0)),
+            iter::repeat_with(|| (rng.gen_range(0..batch_size), rng.gen_range(0..num_points)))
+                .take(batch_size)
+        ]
+        .unique()
+        .collect_vec();
+        let proof = {
+            let mut transcript = T::new(());
+            let polys =
+                iter::repeat_with(|| <Pcs::Polynomial as Polynomial<F>>::rand(poly_size, OsRng))
                     .take(batch_size)
-            ]
-            .unique()
+                    .collect_vec();
+            let comms = Pcs::batch_commit_and_write(&pp, &polys, &mut transcript).unwrap();
+            let points = iter::repeat_with(|| {
+                <Pcs::Polynomial as Polynomial<F>>::squeeze_point(k, &mut transcript)
+            })
+            .take(num_points)
             .collect_vec();
-            let proof = {
-                let mut transcript = T::new(());
-                let polys =
-                    iter::repeat_with(|| <Pcs::Polynomial as Polynomial<F>>::rand(1 << k, OsRng))
-                        .take(batch_size)
-                        .collect_vec();
-                let comms = Pcs::batch_commit_and_write(&pp, &polys, &mut transcript).unwrap();
-                let points = iter::repeat_with(|| {
+            let evals = evals
+                .iter()
+                .copied()
+                .map(|(poly, point)| Evaluation {
+                    poly,
+                    point,
+                    value: polys[poly].evaluate(&points[point]),
+                })
+                .collect_vec();
+            transcript
+                .write_field_elements(evals.iter().map(Evaluation::value))
+                .unwrap();
+            Pcs::batch_open(&pp, &polys, &comms, &points, &evals, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        // Batch verify
+        let result = {
+            let mut transcript = T::from_proof((), proof.as_slice());
+            Pcs::batch_verify(
+                &vp,
+                &Pcs::read_commitments(&vp, batch_size, &mut transcript).unwrap(),
+                &iter::repeat_with(|| {
                     <Pcs::Polynomial as Polynomial<F>>::squeeze_point(k, &mut transcript)
                 })
                 .take(num_points)
-                .collect_vec();
-                let evals = evals
+                .collect_vec(),
+                &evals
                     .iter()
                     .copied()
-                    .map(|(poly, point)| Evaluation {
-                        poly,
-                        point,
-                        value: polys[poly].evaluate(&points[point]),
-                    })
-                    .collect_vec();
-                transcript
-                    .write_field_elements(evals.iter().map(Evaluation::value))
-                    .unwrap();
-                Pcs::batch_open(&pp, &polys, &comms, &points, &evals, &mut transcript).unwrap();
-                transcript.into_proof()
-            };
-            // Batch verify
-            let result = {
+                    .zip(transcript.read_field_elements(evals.len()).unwrap())
+                    .map(|((poly, point), eval)| Evaluation::new(poly, point, eval))
+                    .collect_vec(),
+                &mut transcript,
+            )
+        };
+        assert_eq!(result, Ok(()));
+    }
+
+    pub(super) fn run_verify_deferred_fold_decide<M, Pcs, T>()
+    where
+        M: MultiMillerLoop,
+        Pcs: DeferredKzg<M>,
+        T: TranscriptRead<Pcs::CommitmentChunk, M::Scalar>
+            + TranscriptWrite<Pcs::CommitmentChunk, M::Scalar>
+            + InMemoryTranscript<Param = ()>,
+    {
+        let k = 10;
+        let num_proofs = 4;
+        // Setup
+        let (pp, vp) = {
+            let mut rng = OsRng;
+            let param = Pcs::setup(1 << k, 1, &mut rng).unwrap();
+            Pcs::trim(&param, 1 << k, 1).unwrap()
+        };
+        // Commit and open
+        let proofs = iter::repeat_with(|| {
+            let mut transcript = T::new(());
+            let poly = <Pcs::Polynomial as Polynomial<M::Scalar>>::rand(1 << k, OsRng);
+            let comm = Pcs::commit_and_write(&pp, &poly, &mut transcript).unwrap();
+            let point =
+                <Pcs::Polynomial as Polynomial<M::Scalar>>::squeeze_point(k, &mut transcript);
+            let eval = poly.evaluate(&point);
+            transcript.write_field_element(&eval).unwrap();
+            Pcs::open(&pp, &poly, &comm, &point, &eval, &mut transcript).unwrap();
+            transcript.into_proof()
+        })
+        .take(num_proofs)
+        .collect_vec();
+        // Verify deferred
+        let accumulators = proofs
+            .iter()
+            .map(|proof| {
                 let mut transcript = T::from_proof((), proof.as_slice());
-                Pcs::batch_verify(
+                Pcs::verify_deferred(
                     &vp,
-                    &Pcs::read_commitments(&vp, batch_size, &mut transcript).unwrap(),
-                    &iter::repeat_with(|| {
-                        <Pcs::Polynomial as Polynomial<F>>::squeeze_point(k, &mut transcript)
-                    })
-                    .take(num_points)
-                    .collect_vec(),
-                    &evals
-                        .iter()
-                        .copied()
-                        .zip(transcript.read_field_elements(evals.len()).unwrap())
-                        .map(|((poly, point), eval)| Evaluation::new(poly, point, eval))
-                        .collect_vec(),
+                    &Pcs::read_commitment(&vp, &mut transcript).unwrap(),
+                    &<Pcs::Polynomial as Polynomial<M::Scalar>>::squeeze_point(k, &mut transcript),
+                    &transcript.read_field_element().unwrap(),
                     &mut transcript,
                 )
-            };
-            assert_eq!(result, Ok(()));
+                .unwrap()
+            })
+            .collect_vec();
+        for accumulator in accumulators.iter() {
+            assert_eq!(Pcs::decide(&vp, accumulator), Ok(()));
         }
+        // Fold and decide
+        let r = M::Scalar::random(OsRng);
+        let accumulator = KzgAccumulator::fold(&accumulators, &r);
+        assert_eq!(Pcs::decide(&vp, &accumulator), Ok(()));
+
+        let mut accumulators = accumulators;
+        accumulators[0] =
+            KzgAccumulator::new(*accumulators[1].lhs(), accumulators[0].rhs().to_vec());
+        let accumulator = KzgAccumulator::fold(&accumulators, &r);
+        assert!(Pcs::decide(&vp, &accumulator).is_err());
     }
 }
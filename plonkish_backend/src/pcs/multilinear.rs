@@ -1,16 +1,36 @@
 use crate::{
+    pcs::Evaluation,
+    piop::sum_check::{
+        classic::{ClassicSumCheck, CoefficientsProver},
+        evaluate, SumCheck as _, VirtualPolynomial,
+    },
     poly::multilinear::MultilinearPolynomial,
-    util::{arithmetic::Field, end_timer, izip, parallel::parallelize, start_timer, Itertools},
+    util::{
+        arithmetic::{inner_product, Field, PrimeField},
+        end_timer,
+        expression::{Expression, Query, Rotation},
+        izip,
+        parallel::parallelize,
+        start_timer,
+        transcript::{FieldTranscriptRead, FieldTranscriptWrite},
+        Itertools,
+    },
     Error,
 };
+use std::collections::HashMap;
 
+type SumCheck<F> = ClassicSumCheck<CoefficientsProver<F>>;
+
+mod basefold;
 mod brakedown;
 mod gemini;
 mod hyrax;
 mod ipa;
 mod kzg;
+mod ligero;
 mod zeromorph;
 
+pub use basefold::{MultilinearBasefold, MultilinearBasefoldCommitment, MultilinearBasefoldParam};
 pub use brakedown::{
     MultilinearBrakedown, MultilinearBrakedownCommitment, MultilinearBrakedownParam,
 };
@@ -21,6 +41,7 @@ pub use kzg::{
     MultilinearKzg, MultilinearKzgCommitment, MultilinearKzgParam, MultilinearKzgProverParam,
     MultilinearKzgVerifierParam,
 };
+pub use ligero::{MultilinearLigero, MultilinearLigeroCommitment, MultilinearLigeroParam};
 pub use zeromorph::{Zeromorph, ZeromorphKzgProverParam, ZeromorphKzgVerifierParam};
 
 fn validate_input<'a, F: Field>(
@@ -106,10 +127,111 @@ fn quotients<F: Field, T>(
     (quotients, remainder[0])
 }
 
+/// Reduces evaluations on multiple points to evaluations of each distinct
+/// poly on a single point, by sum-check on
+/// `Σ_i eq(t, i) * f_{poly_i}(X) * eq(X, point_i)`, for schemes that can only
+/// open on a single point. Evaluations on the new point are written, and
+/// indices of distinct polys with the new point are returned.
+fn prove_multi_point_reduction<F: PrimeField>(
+    num_vars: usize,
+    polys: &[&MultilinearPolynomial<F>],
+    points: &[Vec<F>],
+    evals: &[Evaluation<F>],
+    transcript: &mut impl FieldTranscriptWrite<F>,
+) -> Result<(Vec<usize>, Vec<F>), Error> {
+    let indices = evals.iter().map(Evaluation::poly).unique().collect_vec();
+    if points.len() == 1 {
+        return Ok((indices, points[0].clone()));
+    }
+
+    let timer = start_timer(|| format!("multi_point_reduction-{}", evals.len()));
+    let ell = evals.len().next_power_of_two().ilog2() as usize;
+    let t = transcript.squeeze_challenges(ell);
+
+    let eq_xt = MultilinearPolynomial::eq_xy(&t);
+    let expression = multi_point_reduction_expression(&indices, evals, &eq_xt);
+    let polys = indices.iter().map(|idx| polys[*idx]);
+    let virtual_poly = VirtualPolynomial::new(&expression, polys, &[], points);
+    let sum = inner_product(evals.iter().map(Evaluation::value), &eq_xt[..evals.len()]);
+    let (_, point, poly_evals) = SumCheck::prove(&(), num_vars, virtual_poly, sum, transcript)?;
+
+    transcript.write_field_elements(poly_evals.values())?;
+    end_timer(timer);
+
+    Ok((indices, point))
+}
+
+/// Verifies proof generated by [`prove_multi_point_reduction`], and returns
+/// indices of distinct polys with the new point and their evaluations on it.
+#[allow(clippy::type_complexity)]
+fn verify_multi_point_reduction<F: PrimeField>(
+    num_vars: usize,
+    points: &[Vec<F>],
+    evals: &[Evaluation<F>],
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<(Vec<usize>, Vec<F>, Vec<F>), Error> {
+    let indices = evals.iter().map(Evaluation::poly).unique().collect_vec();
+    if points.len() == 1 {
+        let poly_evals = evals
+            .iter()
+            .unique_by(|eval| eval.poly())
+            .map(|eval| *eval.value())
+            .collect_vec();
+        return Ok((indices, points[0].clone(), poly_evals));
+    }
+
+    let ell = evals.len().next_power_of_two().ilog2() as usize;
+    let t = transcript.squeeze_challenges(ell);
+
+    let eq_xt = MultilinearPolynomial::eq_xy(&t);
+    let expression = multi_point_reduction_expression(&indices, evals, &eq_xt);
+    let sum = inner_product(evals.iter().map(Evaluation::value), &eq_xt[..evals.len()]);
+    let (x_eval, point) = SumCheck::verify(&(), num_vars, expression.degree(), sum, transcript)?;
+
+    let poly_evals = transcript.read_field_elements(indices.len())?;
+    let query_evals = poly_evals
+        .iter()
+        .enumerate()
+        .map(|(poly, eval)| (Query::new(poly, Rotation::cur()), *eval))
+        .collect();
+    let ys = points.iter().map(Vec::as_slice).collect_vec();
+    let eval = evaluate::<_, usize>(&expression, num_vars, &query_evals, &[], &ys, &point);
+    if x_eval != eval {
+        return Err(Error::InvalidPcsOpen(
+            "Unmatched between sum_check output and query evaluation".to_string(),
+        ));
+    }
+
+    Ok((indices, point, poly_evals))
+}
+
+fn multi_point_reduction_expression<F: PrimeField>(
+    indices: &[usize],
+    evals: &[Evaluation<F>],
+    eq_xt: &MultilinearPolynomial<F>,
+) -> Expression<F> {
+    let indices = indices
+        .iter()
+        .enumerate()
+        .map(|(poly, idx)| (*idx, poly))
+        .collect::<HashMap<_, _>>();
+    evals
+        .iter()
+        .zip(eq_xt.evals())
+        .map(|(eval, eq_xt_i)| {
+            let poly = indices[&eval.poly()];
+            Expression::<F>::eq_xy(eval.point())
+                * Expression::Polynomial(Query::new(poly, Rotation::cur()))
+                * eq_xt_i
+        })
+        .sum()
+}
+
 mod additive {
     use crate::{
         pcs::{
-            multilinear::validate_input, Additive, Evaluation, Point, PolynomialCommitmentScheme,
+            multilinear::validate_input, Additive, DeferredKzg, Evaluation, KzgAccumulator, Point,
+            PolynomialCommitmentScheme,
         },
         piop::sum_check::{
             classic::{ClassicSumCheck, CoefficientsProver},
@@ -117,11 +239,11 @@ mod additive {
         },
         poly::multilinear::MultilinearPolynomial,
         util::{
-            arithmetic::{fe_to_bytes, inner_product, PrimeField},
+            arithmetic::{fe_to_bytes, inner_product, MultiMillerLoop, PrimeField},
             end_timer,
             expression::{Expression, Query, Rotation},
             start_timer,
-            transcript::{TranscriptRead, TranscriptWrite},
+            transcript::{FieldTranscriptRead, TranscriptRead, TranscriptWrite},
             Itertools,
         },
         Error,
@@ -262,6 +384,50 @@ mod additive {
     {
         validate_input("batch verify", num_vars, [], points)?;
 
+        let (scalars, challenges, g_prime_eval) =
+            batch_verify_reduction(num_vars, points, evals, transcript)?;
+        let g_prime_comm = {
+            let bases = evals.iter().map(|eval| comms[eval.poly()]);
+            Pcs::Commitment::msm(&scalars, bases)
+        };
+        Pcs::verify(vp, &g_prime_comm, &challenges, &g_prime_eval, transcript)
+    }
+
+    /// Same as [`batch_verify`] but returns accumulator of the pairing check.
+    pub fn batch_verify_deferred<M, Pcs>(
+        vp: &Pcs::VerifierParam,
+        num_vars: usize,
+        comms: Vec<&Pcs::Commitment>,
+        points: &[Point<M::Scalar, Pcs::Polynomial>],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error>
+    where
+        M: MultiMillerLoop,
+        Pcs: DeferredKzg<M, Polynomial = MultilinearPolynomial<M::Scalar>>,
+        Pcs::Commitment: Additive<M::Scalar>,
+    {
+        validate_input("batch verify", num_vars, [], points)?;
+
+        let (scalars, challenges, g_prime_eval) =
+            batch_verify_reduction(num_vars, points, evals, transcript)?;
+        let g_prime_comm = {
+            let bases = evals.iter().map(|eval| comms[eval.poly()]);
+            Pcs::Commitment::msm(&scalars, bases)
+        };
+        Pcs::verify_deferred(vp, &g_prime_comm, &challenges, &g_prime_eval, transcript)
+    }
+
+    /// Verifies the sum-check of [`batch_verify`], and returns scalar of each
+    /// evaluation, such that the commitment of `g'` is linear combination of
+    /// commitments of `evals` by the scalars, with the point and evaluation
+    /// of `g'` to be opened.
+    pub fn batch_verify_reduction<F: PrimeField>(
+        num_vars: usize,
+        points: &[Vec<F>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl FieldTranscriptRead<F>,
+    ) -> Result<(Vec<F>, Vec<F>, F), Error> {
         let ell = evals.len().next_power_of_two().ilog2() as usize;
         let t = transcript.squeeze_challenges(ell);
 
@@ -275,15 +441,11 @@ mod additive {
             .iter()
             .map(|point| eq_xy_eval(&challenges, point))
             .collect_vec();
-        let g_prime_comm = {
-            let scalars = evals
-                .iter()
-                .zip(eq_xt.evals())
-                .map(|(eval, eq_xt_i)| eq_xy_evals[eval.point()] * eq_xt_i)
-                .collect_vec();
-            let bases = evals.iter().map(|eval| comms[eval.poly()]);
-            Pcs::Commitment::msm(&scalars, bases)
-        };
-        Pcs::verify(vp, &g_prime_comm, &challenges, &g_prime_eval, transcript)
+        let scalars = evals
+            .iter()
+            .zip(eq_xt.evals())
+            .map(|(eval, eq_xt_i)| eq_xy_evals[eval.point()] * eq_xt_i)
+            .collect_vec();
+        Ok((scalars, challenges, g_prime_eval))
     }
 }
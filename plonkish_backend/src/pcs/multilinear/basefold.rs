@@ -0,0 +1,657 @@
+//! Implementation of multilinear polynomial commitment scheme described in
+//! [ZCF23], which encodes evaluations with random foldable codes, and proves
+//! evaluation by sum-check interleaved with FRI-style folding of codeword.
+//!
+//! [ZCF23]: https://eprint.iacr.org/2023/1705.pdf
+
+use crate::{
+    pcs::{
+        multilinear::{
+            err_too_many_variates, prove_multi_point_reduction, validate_input,
+            verify_multi_point_reduction,
+        },
+        squeeze_challenge_idx, Evaluation, Point, PolynomialCommitmentScheme,
+    },
+    piop::sum_check::eq_xy_eval,
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::{
+            barycentric_interpolate, barycentric_weights, div_ceil, inner_product, powers,
+            BatchInvert, PrimeField,
+        },
+        end_timer,
+        hash::{Hash, MerkleTree, Output},
+        izip,
+        parallel::{num_threads, par_map_collect, parallelize, parallelize_iter},
+        start_timer,
+        transcript::{TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+use rand::RngCore;
+use std::{borrow::Cow, iter, marker::PhantomData, slice};
+
+const LOG_RATE: usize = 3;
+
+const NUM_QUERIES: usize = 120;
+
+#[derive(Debug)]
+pub struct MultilinearBasefold<F: PrimeField, H: Hash>(PhantomData<(F, H)>);
+
+impl<F: PrimeField, H: Hash> Clone for MultilinearBasefold<F, H> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct MultilinearBasefoldParam<F> {
+    num_vars: usize,
+    log_rate: usize,
+    num_queries: usize,
+    twiddles: Vec<Vec<F>>,
+    inv_twiddles: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> MultilinearBasefoldParam<F> {
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn log_rate(&self) -> usize {
+        self.log_rate
+    }
+
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    pub fn codeword_len(&self, num_vars: usize) -> usize {
+        1 << (num_vars + self.log_rate)
+    }
+
+    /// Encodes evaluations `m` of multilinear polynomial of `k` variables into
+    /// `Enc_k(m) = (Enc_{k-1}(m_0) + t_k ∘ Enc_{k-1}(m_1), Enc_{k-1}(m_0) - t_k ∘ Enc_{k-1}(m_1))`,
+    /// where `m_0` and `m_1` are evaluations with first variable being `0` and
+    /// `1`, and `Enc_0` is repetition code.
+    fn encode(&self, evals: &[F]) -> Vec<F> {
+        let num_vars = evals.len().ilog2() as usize;
+        assert!(num_vars <= self.num_vars);
+
+        // Sub-messages to be combined are placed next to each other in
+        // bit-reversed order, so each level is done by in-place butterflies.
+        let mut codeword = vec![F::ZERO; self.codeword_len(num_vars)];
+        parallelize(&mut codeword, |(codeword, start)| {
+            for (value, idx) in codeword.iter_mut().zip(start..) {
+                *value = evals[bit_reverse(idx >> self.log_rate, num_vars)];
+            }
+        });
+
+        let chunk_size = div_ceil(codeword.len(), num_threads()).next_power_of_two();
+        for twiddles in &self.twiddles[..num_vars] {
+            let len = twiddles.len() << 1;
+            if len <= chunk_size {
+                parallelize_iter(codeword.chunks_mut(chunk_size), |codeword| {
+                    for codeword in codeword.chunks_mut(len) {
+                        let (lo, hi) = codeword.split_at_mut(len >> 1);
+                        butterfly(lo, hi, twiddles);
+                    }
+                });
+            } else {
+                for codeword in codeword.chunks_mut(len) {
+                    let (lo, hi) = codeword.split_at_mut(len >> 1);
+                    let chunk_size = div_ceil(lo.len(), num_threads());
+                    parallelize_iter(
+                        izip!(
+                            lo.chunks_mut(chunk_size),
+                            hi.chunks_mut(chunk_size),
+                            twiddles.chunks(chunk_size)
+                        ),
+                        |(lo, hi, twiddles)| butterfly(lo, hi, twiddles),
+                    );
+                }
+            }
+        }
+
+        codeword
+    }
+
+    /// Returns `Enc_{k-1}((1 - r) * m_0 + r * m_1)` from `Enc_k(m)`.
+    fn fold(&self, num_vars: usize, codeword: &[F], challenge: &F) -> Vec<F> {
+        let (lo, hi) = codeword.split_at(codeword.len() >> 1);
+        let inv_twiddles = &self.inv_twiddles[num_vars - 1];
+        par_map_collect(0..lo.len(), |idx| {
+            fold(&lo[idx], &hi[idx], &inv_twiddles[idx], challenge)
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct MultilinearBasefoldCommitment<F, H: Hash> {
+    codeword: Vec<F>,
+    tree: MerkleTree<H>,
+}
+
+impl<F: PrimeField, H: Hash> MultilinearBasefoldCommitment<F, H> {
+    /// Merklizes `codeword` with leaves being hashes of pairs
+    /// `(codeword[j], codeword[j + n/2])`, which are always opened together.
+    fn new(codeword: Vec<F>) -> Self {
+        let (lo, hi) = codeword.split_at(codeword.len() >> 1);
+        let leaves = par_map_collect(0..lo.len(), |idx| hash_pair::<F, H>(&lo[idx], &hi[idx]));
+        let tree = MerkleTree::new(leaves);
+        Self { codeword, tree }
+    }
+
+    fn from_root(root: Output<H>) -> Self {
+        Self {
+            codeword: Vec::new(),
+            tree: MerkleTree::from_root(root),
+        }
+    }
+
+    pub fn codeword(&self) -> &[F] {
+        &self.codeword
+    }
+
+    pub fn tree(&self) -> &MerkleTree<H> {
+        &self.tree
+    }
+
+    pub fn root(&self) -> &Output<H> {
+        self.tree.root()
+    }
+
+    fn write_opening(
+        &self,
+        leaf: usize,
+        transcript: &mut impl TranscriptWrite<Output<H>, F>,
+    ) -> Result<(), Error> {
+        let half = self.codeword.len() >> 1;
+        transcript.write_field_elements([&self.codeword[leaf], &self.codeword[leaf + half]])?;
+        self.tree.write_path(leaf, transcript)
+    }
+}
+
+impl<F: PrimeField, H: Hash> AsRef<[Output<H>]> for MultilinearBasefoldCommitment<F, H> {
+    fn as_ref(&self) -> &[Output<H>] {
+        slice::from_ref(self.tree.root())
+    }
+}
+
+impl<F, H> PolynomialCommitmentScheme<F> for MultilinearBasefold<F, H>
+where
+    F: PrimeField + Serialize + DeserializeOwned,
+    H: Hash,
+{
+    type Param = MultilinearBasefoldParam<F>;
+    type ProverParam = MultilinearBasefoldParam<F>;
+    type VerifierParam = MultilinearBasefoldParam<F>;
+    type Polynomial = MultilinearPolynomial<F>;
+    type Commitment = MultilinearBasefoldCommitment<F, H>;
+    type CommitmentChunk = Output<H>;
+
+    fn setup(poly_size: usize, _: usize, mut rng: impl RngCore) -> Result<Self::Param, Error> {
+        assert!(poly_size.is_power_of_two());
+        let num_vars = poly_size.ilog2() as usize;
+
+        let twiddles = (0..num_vars)
+            .map(|num_vars| {
+                iter::repeat_with(|| F::random(&mut rng))
+                    .filter(|twiddle| *twiddle != F::ZERO)
+                    .take(1 << (num_vars + LOG_RATE))
+                    .collect_vec()
+            })
+            .collect_vec();
+        let inv_twiddles = twiddles
+            .iter()
+            .map(|twiddles| {
+                let mut inv_twiddles = twiddles.clone();
+                inv_twiddles.iter_mut().batch_invert();
+                inv_twiddles
+            })
+            .collect();
+
+        Ok(MultilinearBasefoldParam {
+            num_vars,
+            log_rate: LOG_RATE,
+            num_queries: NUM_QUERIES,
+            twiddles,
+            inv_twiddles,
+        })
+    }
+
+    fn trim(
+        param: &Self::Param,
+        poly_size: usize,
+        _: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        assert!(poly_size.is_power_of_two());
+        let num_vars = poly_size.ilog2() as usize;
+        if param.num_vars < num_vars {
+            return Err(err_too_many_variates("trim", param.num_vars, num_vars));
+        }
+
+        let param = MultilinearBasefoldParam {
+            num_vars,
+            log_rate: param.log_rate,
+            num_queries: param.num_queries,
+            twiddles: param.twiddles[..num_vars].to_vec(),
+            inv_twiddles: param.inv_twiddles[..num_vars].to_vec(),
+        };
+        Ok((param.clone(), param))
+    }
+
+    fn commit(pp: &Self::ProverParam, poly: &Self::Polynomial) -> Result<Self::Commitment, Error> {
+        validate_input("commit", pp.num_vars(), [poly], None)?;
+
+        Ok(MultilinearBasefoldCommitment::new(pp.encode(poly.evals())))
+    }
+
+    fn batch_commit<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+    ) -> Result<Vec<Self::Commitment>, Error>
+    where
+        Self::Polynomial: 'a,
+    {
+        polys
+            .into_iter()
+            .map(|poly| Self::commit(pp, poly))
+            .collect()
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        validate_input("open", pp.num_vars(), [poly], [point])?;
+
+        if cfg!(feature = "sanity-check") {
+            assert_eq!(poly.evaluate(point), *eval);
+        }
+
+        prove_basefold(pp, &[poly], &[comm], point, transcript)
+    }
+
+    fn batch_open<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let polys = polys.into_iter().collect_vec();
+        let comms = comms.into_iter().collect_vec();
+        let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+        validate_input(
+            "batch open",
+            pp.num_vars(),
+            evals.iter().map(|eval| polys[eval.poly()]),
+            points,
+        )?;
+
+        if cfg!(feature = "sanity-check") {
+            for eval in evals {
+                let (poly, point) = (&polys[eval.poly()], &points[eval.point()]);
+                assert_eq!(poly.evaluate(point), *eval.value());
+            }
+        }
+
+        let (indices, point) =
+            prove_multi_point_reduction(num_vars, &polys, points, evals, transcript)?;
+
+        let polys = indices.iter().map(|idx| polys[*idx]).collect_vec();
+        let comms = indices.iter().map(|idx| comms[*idx]).collect_vec();
+        prove_basefold(pp, &polys, &comms, &point, transcript)
+    }
+
+    fn read_commitments(
+        _: &Self::VerifierParam,
+        num_polys: usize,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        transcript.read_commitments(num_polys).map(|roots| {
+            roots
+                .into_iter()
+                .map(MultilinearBasefoldCommitment::from_root)
+                .collect_vec()
+        })
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        validate_input("verify", vp.num_vars(), [], [point])?;
+
+        verify_basefold(vp, &[comm], point, &[*eval], transcript)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let comms = comms.into_iter().collect_vec();
+        let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+        validate_input("batch verify", vp.num_vars(), [], points)?;
+
+        let (indices, point, poly_evals) =
+            verify_multi_point_reduction(num_vars, points, evals, transcript)?;
+
+        let comms = indices.iter().map(|idx| comms[*idx]).collect_vec();
+        verify_basefold(vp, &comms, &point, &poly_evals, transcript)
+    }
+}
+
+/// Proves evaluation of `f = Σ_i α^i * f_i` on `point` by sum-check on
+/// `f(X) * eq(X, point)`, where after each round the codeword of `f` is folded
+/// by the challenge and committed, then opened on queried positions to show
+/// folding consistency. The `α` is only squeezed when there are multiple
+/// polys.
+fn prove_basefold<F: PrimeField, H: Hash>(
+    pp: &MultilinearBasefoldParam<F>,
+    polys: &[&MultilinearPolynomial<F>],
+    comms: &[&MultilinearBasefoldCommitment<F, H>],
+    point: &[F],
+    transcript: &mut impl TranscriptWrite<Output<H>, F>,
+) -> Result<(), Error> {
+    let num_vars = point.len();
+
+    let (mut poly, codeword) = if polys.len() == 1 {
+        (Cow::Borrowed(polys[0]), Cow::Borrowed(comms[0].codeword()))
+    } else {
+        let alphas = powers(transcript.squeeze_challenge())
+            .take(polys.len())
+            .collect_vec();
+        let poly = izip!(&alphas, polys)
+            .map(|(alpha, poly)| (alpha, *poly))
+            .sum::<MultilinearPolynomial<_>>();
+        let mut codeword = vec![F::ZERO; pp.codeword_len(num_vars)];
+        parallelize(&mut codeword, |(codeword, start)| {
+            for (alpha, comm) in izip!(&alphas, comms) {
+                izip!(codeword.iter_mut(), &comm.codeword()[start..])
+                    .for_each(|(value, comm_value)| *value += *alpha * comm_value);
+            }
+        });
+        (Cow::Owned(poly), Cow::Owned(codeword))
+    };
+
+    let timer = start_timer(|| format!("basefold_commit_phase-{num_vars}"));
+    let mut eq = MultilinearPolynomial::eq_xy(point);
+    let mut oracles = Vec::<MultilinearBasefoldCommitment<F, H>>::with_capacity(num_vars);
+    for round in 0..num_vars {
+        let msg = sum_check_round(poly.evals(), eq.evals());
+        transcript.write_field_elements(&msg)?;
+
+        let challenge = transcript.squeeze_challenge();
+        poly = Cow::Owned(poly.fix_var(&challenge));
+        eq = eq.fix_var(&challenge);
+
+        if round < num_vars - 1 {
+            let prev = oracles
+                .last()
+                .map(MultilinearBasefoldCommitment::codeword)
+                .unwrap_or(&codeword);
+            let oracle =
+                MultilinearBasefoldCommitment::new(pp.fold(num_vars - round, prev, &challenge));
+            transcript.write_commitment(oracle.root())?;
+            oracles.push(oracle);
+        }
+    }
+    transcript.write_field_element(&poly[0])?;
+    end_timer(timer);
+
+    let timer = start_timer(|| format!("basefold_query_phase-{}", pp.num_queries()));
+    let half = pp.codeword_len(num_vars) >> 1;
+    for _ in 0..pp.num_queries() {
+        let idx = squeeze_challenge_idx(transcript, half);
+        for comm in comms {
+            comm.write_opening(idx, transcript)?;
+        }
+        for oracle in oracles.iter() {
+            oracle.write_opening(idx % (oracle.codeword().len() >> 1), transcript)?;
+        }
+    }
+    end_timer(timer);
+
+    Ok(())
+}
+
+fn verify_basefold<F: PrimeField, H: Hash>(
+    vp: &MultilinearBasefoldParam<F>,
+    comms: &[&MultilinearBasefoldCommitment<F, H>],
+    point: &[F],
+    evals: &[F],
+    transcript: &mut impl TranscriptRead<Output<H>, F>,
+) -> Result<(), Error> {
+    let num_vars = point.len();
+
+    let alphas = if comms.len() == 1 {
+        vec![F::ONE]
+    } else {
+        powers(transcript.squeeze_challenge())
+            .take(comms.len())
+            .collect_vec()
+    };
+
+    let points = [F::ZERO, F::ONE, F::ONE.double()];
+    let weights = barycentric_weights(&points);
+    let mut sum = inner_product(&alphas, evals);
+    let mut challenges = Vec::with_capacity(num_vars);
+    let mut roots = Vec::with_capacity(num_vars);
+    for round in 0..num_vars {
+        let msg = transcript.read_field_elements(3)?;
+        if msg[0] + msg[1] != sum {
+            return Err(Error::InvalidPcsOpen(format!(
+                "Sum-check consistency failure at round {round}"
+            )));
+        }
+
+        let challenge = transcript.squeeze_challenge();
+        sum = barycentric_interpolate(&weights, &points, &msg, &challenge);
+        challenges.push(challenge);
+
+        if round < num_vars - 1 {
+            roots.push(transcript.read_commitment()?);
+        }
+    }
+    let final_eval = transcript.read_field_element()?;
+    if sum != final_eval * eq_xy_eval(&challenges, point) {
+        return Err(err_unmatched_sum_check_output());
+    }
+
+    let half = vp.codeword_len(num_vars) >> 1;
+    let depth = half.ilog2() as usize;
+    for _ in 0..vp.num_queries() {
+        let idx = squeeze_challenge_idx(transcript, half);
+        let (mut lo, mut hi) = (F::ZERO, F::ZERO);
+        for (alpha, comm) in izip!(&alphas, comms) {
+            let (comm_lo, comm_hi) = read_opening::<F, H>(comm.root(), depth, idx, transcript)?;
+            lo += *alpha * comm_lo;
+            hi += *alpha * comm_hi;
+        }
+
+        // Codeword of 0-variate poly is repetition code without any folding.
+        if num_vars == 0 {
+            if lo != final_eval || hi != final_eval {
+                return Err(Error::InvalidPcsOpen("Repetition failure".to_string()));
+            }
+            continue;
+        }
+
+        for (round, challenge) in challenges.iter().enumerate() {
+            let half = half >> round;
+            let leaf = idx % half;
+            let folded = fold(
+                &lo,
+                &hi,
+                &vp.inv_twiddles[num_vars - round - 1][leaf],
+                challenge,
+            );
+            let expected = if round < num_vars - 1 {
+                let next_half = half >> 1;
+                (lo, hi) = read_opening::<F, H>(
+                    &roots[round],
+                    depth - round - 1,
+                    leaf % next_half,
+                    transcript,
+                )?;
+                if leaf < next_half {
+                    lo
+                } else {
+                    hi
+                }
+            } else {
+                final_eval
+            };
+            if folded != expected {
+                return Err(Error::InvalidPcsOpen("Folding failure".to_string()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns evaluations of `Σ_b f(X, b) * eq(X, b)` on `X = 0, 1, 2`.
+fn sum_check_round<F: PrimeField>(poly: &[F], eq: &[F]) -> [F; 3] {
+    let chunk_size = div_ceil(poly.len() >> 1, num_threads());
+    let num_chunks = div_ceil(poly.len() >> 1, chunk_size);
+    let sums: Vec<[F; 3]> = par_map_collect(0..num_chunks, |chunk| {
+        let start = (chunk * chunk_size) << 1;
+        izip!(poly[start..].chunks_exact(2), eq[start..].chunks_exact(2))
+            .take(chunk_size)
+            .fold([F::ZERO; 3], |mut sums, (f, eq)| {
+                sums[0] += f[0] * eq[0];
+                sums[1] += f[1] * eq[1];
+                sums[2] += (f[1].double() - f[0]) * (eq[1].double() - eq[0]);
+                sums
+            })
+    });
+    sums.into_iter().fold([F::ZERO; 3], |mut acc, sums| {
+        izip!(&mut acc, sums).for_each(|(acc, sum)| *acc += sum);
+        acc
+    })
+}
+
+/// Returns `(1 - r) * Enc(m_0)[j] + r * Enc(m_1)[j]` from
+/// `(Enc(m)[j], Enc(m)[j + n/2])`.
+fn fold<F: PrimeField>(lo: &F, hi: &F, inv_twiddle: &F, challenge: &F) -> F {
+    let even = *lo + hi;
+    let odd = (*lo - hi) * inv_twiddle;
+    (even + (odd - even) * challenge) * F::TWO_INV
+}
+
+fn butterfly<F: PrimeField>(lo: &mut [F], hi: &mut [F], twiddles: &[F]) {
+    izip!(lo, hi, twiddles).for_each(|(lo, hi, twiddle)| {
+        let hi_twiddle = *hi * twiddle;
+        *hi = *lo - hi_twiddle;
+        *lo += hi_twiddle;
+    });
+}
+
+fn bit_reverse(idx: usize, num_bits: usize) -> usize {
+    if num_bits == 0 {
+        0
+    } else {
+        idx.reverse_bits() >> (usize::BITS as usize - num_bits)
+    }
+}
+
+fn hash_pair<F: PrimeField, H: Hash>(lo: &F, hi: &F) -> Output<H> {
+    let mut hasher = H::new();
+    hasher.update_field_element(lo);
+    hasher.update_field_element(hi);
+    hasher.finalize_fixed()
+}
+
+fn read_opening<F: PrimeField, H: Hash>(
+    root: &Output<H>,
+    depth: usize,
+    leaf: usize,
+    transcript: &mut impl TranscriptRead<Output<H>, F>,
+) -> Result<(F, F), Error> {
+    let pair = transcript.read_field_elements(2)?;
+    let leaf_hash = hash_pair::<F, H>(&pair[0], &pair[1]);
+    MerkleTree::<H>::read_and_verify_path(root, depth, leaf, leaf_hash, transcript)?;
+    Ok((pair[0], pair[1]))
+}
+
+fn err_unmatched_sum_check_output() -> Error {
+    Error::InvalidPcsOpen("Unmatched between sum_check output and query evaluation".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        pcs::{
+            multilinear::basefold::MultilinearBasefold,
+            squeeze_challenge_idx,
+            test::{run_batch_commit_open_verify, run_commit_open_verify},
+            PolynomialCommitmentScheme,
+        },
+        poly::multilinear::MultilinearPolynomial,
+        util::{
+            arithmetic::Field,
+            hash::Keccak256,
+            transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptWrite},
+        },
+    };
+    use halo2_curves::bn256::Fr;
+    use rand::rngs::OsRng;
+
+    type Pcs = MultilinearBasefold<Fr, Keccak256>;
+
+    #[test]
+    fn commit_open_verify() {
+        run_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify() {
+        run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn reject_wrong_eval_of_constant_poly() {
+        let (pp, vp) = {
+            let param = Pcs::setup(1, 1, OsRng).unwrap();
+            Pcs::trim(&param, 1, 1).unwrap()
+        };
+        let poly = MultilinearPolynomial::new(vec![Fr::random(OsRng)]);
+        let wrong_eval = poly.evals()[0] + Fr::ONE;
+        // Claims `wrong_eval` as final evaluation, which is consistent with
+        // the sum-check of no rounds, with openings of the honest codeword.
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            let comm = Pcs::commit_and_write(&pp, &poly, &mut transcript).unwrap();
+            transcript.write_field_element(&wrong_eval).unwrap();
+            let half = pp.codeword_len(0) >> 1;
+            for _ in 0..pp.num_queries() {
+                let idx = squeeze_challenge_idx::<Fr>(&mut transcript, half);
+                comm.write_opening(idx, &mut transcript).unwrap();
+            }
+            transcript.into_proof()
+        };
+        let result = {
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            let comm = Pcs::read_commitment(&vp, &mut transcript).unwrap();
+            Pcs::verify(&vp, &comm, &Vec::new(), &wrong_eval, &mut transcript)
+        };
+        assert!(result.is_err());
+    }
+}
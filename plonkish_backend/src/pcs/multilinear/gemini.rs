@@ -3,34 +3,40 @@
 
 use crate::{
     pcs::{
-        multilinear::additive,
-        univariate::{err_too_large_deree, UnivariateKzg, UnivariateKzgCommitment},
-        Evaluation, Point, PolynomialCommitmentScheme,
+        multilinear::{additive, prove_multi_point_reduction, verify_multi_point_reduction},
+        univariate::{
+            err_too_large_deree, KzgBatchOpening, UnivariateFri, UnivariateFriCommitment,
+            UnivariateFriParam, UnivariateKzg, UnivariateKzgCommitment,
+        },
+        DeferredKzg, Evaluation, KzgAccumulator, Point, PolynomialCommitmentScheme,
     },
     poly::{
         multilinear::{merge_into, MultilinearPolynomial},
         univariate::UnivariatePolynomial,
     },
     util::{
-        arithmetic::{squares, Field, MultiMillerLoop},
+        arithmetic::{horner, inner_product, powers, squares, Field, MultiMillerLoop, PrimeField},
         chain,
+        hash::{Hash, Output},
+        izip,
         transcript::{TranscriptRead, TranscriptWrite},
         DeserializeOwned, Itertools, Serialize,
     },
     Error,
 };
 use rand::RngCore;
-use std::{marker::PhantomData, ops::Neg};
+use std::{borrow::Cow, marker::PhantomData, ops::Neg};
 
 #[derive(Clone, Debug)]
 pub struct Gemini<Pcs>(PhantomData<Pcs>);
 
-impl<M> PolynomialCommitmentScheme<M::Scalar> for Gemini<UnivariateKzg<M>>
+impl<M, B> PolynomialCommitmentScheme<M::Scalar> for Gemini<UnivariateKzg<M, B>>
 where
     M: MultiMillerLoop,
     M::Scalar: Serialize + DeserializeOwned,
     M::G1Affine: Serialize + DeserializeOwned,
     M::G2Affine: Serialize + DeserializeOwned,
+    B: KzgBatchOpening<M>,
 {
     type Param = <UnivariateKzg<M> as PolynomialCommitmentScheme<M::Scalar>>::Param;
     type ProverParam = <UnivariateKzg<M> as PolynomialCommitmentScheme<M::Scalar>>::ProverParam;
@@ -58,7 +64,7 @@ where
             return Err(err_too_large_deree("commit", pp.degree(), got));
         }
 
-        Ok(UnivariateKzg::commit_monomial(pp, poly.evals()))
+        Ok(UnivariateKzg::<M>::commit_monomial(pp, poly.evals()))
     }
 
     fn batch_commit<'a>(
@@ -127,7 +133,7 @@ where
             .collect_vec();
         transcript.write_field_elements(evals[1..].iter().map(Evaluation::value))?;
 
-        UnivariateKzg::<M>::batch_open(pp, &fs, &comms, &points, &evals, transcript)
+        UnivariateKzg::<M, B>::batch_open(pp, &fs, &comms, &points, &evals, transcript)
     }
 
     fn batch_open<'a>(
@@ -152,7 +158,7 @@ where
         num_polys: usize,
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
     ) -> Result<Vec<Self::Commitment>, Error> {
-        UnivariateKzg::read_commitments(vp, num_polys, transcript)
+        UnivariateKzg::<M>::read_commitments(vp, num_polys, transcript)
     }
 
     fn verify(
@@ -162,6 +168,41 @@ where
         eval: &M::Scalar,
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
     ) -> Result<(), Error> {
+        let accumulator = Self::verify_deferred(vp, comm, point, eval, transcript)?;
+        Self::decide(vp, &accumulator)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<M::Scalar, Self::Polynomial>],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<(), Error> {
+        let accumulator = Self::batch_verify_deferred(vp, comms, points, evals, transcript)?;
+        Self::decide(vp, &accumulator)
+    }
+}
+
+impl<M, B> DeferredKzg<M> for Gemini<UnivariateKzg<M, B>>
+where
+    M: MultiMillerLoop,
+    M::Scalar: Serialize + DeserializeOwned,
+    M::G1Affine: Serialize + DeserializeOwned,
+    M::G2Affine: Serialize + DeserializeOwned,
+    B: KzgBatchOpening<M>,
+{
+    fn accumulator_g2s(vp: &Self::VerifierParam) -> Vec<M::G2Affine> {
+        UnivariateKzg::<M, B>::accumulator_g2s(vp)
+    }
+
+    fn verify_deferred(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<M::Scalar, Self::Polynomial>,
+        eval: &M::Scalar,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
         let num_vars = point.len();
         let comms = chain![[comm.0], transcript.read_commitments(num_vars - 1)?]
             .map(UnivariateKzgCommitment)
@@ -187,20 +228,279 @@ where
             .collect_vec();
         let points = chain!([beta], squares_of_beta.into_iter().map(Neg::neg)).collect_vec();
 
-        UnivariateKzg::<M>::batch_verify(vp, &comms, &points, &evals, transcript)
+        UnivariateKzg::<M, B>::batch_verify_deferred(vp, &comms, &points, &evals, transcript)
     }
 
-    fn batch_verify<'a>(
+    fn batch_verify_deferred<'a>(
         vp: &Self::VerifierParam,
         comms: impl IntoIterator<Item = &'a Self::Commitment>,
         points: &[Point<M::Scalar, Self::Polynomial>],
         evals: &[Evaluation<M::Scalar>],
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
+        let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+        let comms = comms.into_iter().collect_vec();
+        additive::batch_verify_deferred::<M, Self>(vp, num_vars, comms, points, evals, transcript)
+    }
+}
+
+impl<F, H> PolynomialCommitmentScheme<F> for Gemini<UnivariateFri<F, H>>
+where
+    F: PrimeField + Serialize + DeserializeOwned,
+    H: Hash,
+{
+    type Param = UnivariateFriParam;
+    type ProverParam = UnivariateFriParam;
+    type VerifierParam = UnivariateFriParam;
+    type Polynomial = MultilinearPolynomial<F>;
+    type Commitment = UnivariateFriCommitment<F, H>;
+    type CommitmentChunk = Output<H>;
+
+    fn setup(poly_size: usize, batch_size: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
+        UnivariateFri::<F, H>::setup(poly_size, batch_size, rng)
+    }
+
+    fn trim(
+        param: &Self::Param,
+        poly_size: usize,
+        batch_size: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        UnivariateFri::<F, H>::trim(param, poly_size, batch_size)
+    }
+
+    fn commit(pp: &Self::ProverParam, poly: &Self::Polynomial) -> Result<Self::Commitment, Error> {
+        if pp.degree() + 1 < poly.evals().len() {
+            let got = poly.evals().len() - 1;
+            return Err(err_too_large_deree("commit", pp.degree(), got));
+        }
+
+        Ok(UnivariateFri::commit_monomial(pp, poly.evals()))
+    }
+
+    fn batch_commit<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        polys
+            .into_iter()
+            .map(|poly| Self::commit(pp, poly))
+            .collect()
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
     ) -> Result<(), Error> {
+        if pp.degree() + 1 < poly.evals().len() {
+            let got = poly.evals().len() - 1;
+            return Err(err_too_large_deree("open", pp.degree(), got));
+        }
+
+        if cfg!(feature = "sanity-check") {
+            assert_eq!(Self::commit(pp, poly).unwrap().root(), comm.root());
+            assert_eq!(poly.evaluate(point), *eval);
+        }
+
+        open_fri(pp, &[poly], &[comm], point, transcript)
+    }
+
+    fn batch_open<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let polys = polys.into_iter().collect_vec();
+        let comms = comms.into_iter().collect_vec();
         let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+        for eval in evals {
+            let poly = &polys[eval.poly()];
+            if pp.degree() + 1 < poly.evals().len() {
+                let got = poly.evals().len() - 1;
+                return Err(err_too_large_deree("batch open", pp.degree(), got));
+            }
+        }
+
+        if cfg!(feature = "sanity-check") {
+            for eval in evals {
+                let (poly, point) = (&polys[eval.poly()], &points[eval.point()]);
+                assert_eq!(poly.evaluate(point), *eval.value());
+            }
+        }
+
+        let (indices, point) =
+            prove_multi_point_reduction(num_vars, &polys, points, evals, transcript)?;
+
+        let polys = indices.iter().map(|idx| polys[*idx]).collect_vec();
+        let comms = indices.iter().map(|idx| comms[*idx]).collect_vec();
+        open_fri(pp, &polys, &comms, &point, transcript)
+    }
+
+    fn read_commitments(
+        vp: &Self::VerifierParam,
+        num_polys: usize,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        UnivariateFri::<F, H>::read_commitments(vp, num_polys, transcript)
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        verify_fri(vp, &[comm], point, &[*eval], transcript)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
         let comms = comms.into_iter().collect_vec();
-        additive::batch_verify::<_, Self>(vp, num_vars, comms, points, evals, transcript)
+        let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+
+        let (indices, point, poly_evals) =
+            verify_multi_point_reduction(num_vars, points, evals, transcript)?;
+
+        let comms = indices.iter().map(|idx| comms[*idx]).collect_vec();
+        verify_fri(vp, &comms, &point, &poly_evals, transcript)
+    }
+}
+
+/// Proves evaluations of `polys` on the same `point`, where `polys` are
+/// batched by powers of `α` before folding, then each of them is opened on
+/// `β` and `-β` together with folded ones on `-β^(2^i)` by a single FRI, since
+/// the evaluation of batched one on `β` can't be derived from commitments.
+/// The `α` is only squeezed when there are multiple polys.
+fn open_fri<F: PrimeField, H: Hash>(
+    pp: &UnivariateFriParam,
+    polys: &[&MultilinearPolynomial<F>],
+    comms: &[&UnivariateFriCommitment<F, H>],
+    point: &[F],
+    transcript: &mut impl TranscriptWrite<Output<H>, F>,
+) -> Result<(), Error> {
+    let num_vars = point.len();
+
+    let g = if polys.len() == 1 {
+        Cow::Borrowed(polys[0])
+    } else {
+        let alphas = powers(transcript.squeeze_challenge()).take(polys.len());
+        Cow::Owned(izip!(alphas, polys.iter().copied()).sum::<MultilinearPolynomial<_>>())
+    };
+    let fs = {
+        let mut fs = Vec::with_capacity(num_vars - 1);
+        for x_i in &point[..num_vars - 1] {
+            let f_i_minus_one = fs.last().map(Vec::as_slice).unwrap_or(g.evals());
+            let mut f_i = Vec::with_capacity(f_i_minus_one.len() >> 1);
+            merge_into(&mut f_i, f_i_minus_one, x_i, 1, 0);
+            fs.push(f_i);
+        }
+        fs
+    };
+    let fs_comms = fs
+        .iter()
+        .map(|f| {
+            let comm = UnivariateFri::<F, H>::commit_monomial(pp, f);
+            transcript.write_commitment(comm.root())?;
+            Ok(comm)
+        })
+        .try_collect::<_, Vec<_>, Error>()?;
+
+    let beta = transcript.squeeze_challenge();
+    let points = chain![[beta], squares(beta).map(Neg::neg)]
+        .take(num_vars + 1)
+        .collect_vec();
+
+    let evals = chain![
+        polys.iter().enumerate().flat_map(|(idx, poly)| {
+            [0, 1].map(|point| Evaluation::new(idx, point, horner(poly.evals(), &points[point])))
+        }),
+        fs.iter().enumerate().map(|(idx, f)| {
+            let point = idx + 2;
+            Evaluation::new(polys.len() + idx, point, horner(f, &points[point]))
+        })
+    ]
+    .collect_vec();
+    transcript.write_field_elements(evals.iter().map(Evaluation::value))?;
+
+    let comms = chain![comms.iter().copied(), &fs_comms].collect_vec();
+    UnivariateFri::batch_open_committed(pp, &comms, &points, &evals, transcript)
+}
+
+fn verify_fri<F: PrimeField, H: Hash>(
+    vp: &UnivariateFriParam,
+    comms: &[&UnivariateFriCommitment<F, H>],
+    point: &[F],
+    evals: &[F],
+    transcript: &mut impl TranscriptRead<Output<H>, F>,
+) -> Result<(), Error> {
+    let num_vars = point.len();
+
+    let alphas = if comms.len() == 1 {
+        vec![F::ONE]
+    } else {
+        powers(transcript.squeeze_challenge())
+            .take(comms.len())
+            .collect_vec()
+    };
+    let fs_comms = transcript
+        .read_commitments(num_vars - 1)?
+        .into_iter()
+        .map(UnivariateFriCommitment::from_root)
+        .collect_vec();
+
+    let beta = transcript.squeeze_challenge();
+    let squares_of_beta = squares(beta).take(num_vars).collect_vec();
+
+    let fri_evals = transcript.read_field_elements(2 * comms.len() + num_vars - 1)?;
+    let (poly_evals, fs_evals) = fri_evals.split_at(2 * comms.len());
+
+    let g_evals = izip!(&alphas, poly_evals.chunks(2))
+        .fold([F::ZERO; 2], |[pos, neg], (alpha, evals)| {
+            [pos + *alpha * evals[0], neg + *alpha * evals[1]]
+        });
+    let one = F::ONE;
+    let two = one.double();
+    let neg_evals = chain![[&g_evals[1]], fs_evals].collect_vec();
+    let eval_0 = neg_evals
+        .into_iter()
+        .zip(&squares_of_beta)
+        .zip(point)
+        .rev()
+        .fold(
+            inner_product(&alphas, evals),
+            |eval_pos, ((eval_neg, sqaure_of_beta), x_i)| {
+                (two * sqaure_of_beta * eval_pos - ((one - x_i) * sqaure_of_beta - x_i) * eval_neg)
+                    * ((one - x_i) * sqaure_of_beta + x_i).invert().unwrap()
+            },
+        );
+    if eval_0 != g_evals[0] {
+        return Err(Error::InvalidPcsOpen(
+            "Unmatched between Gemini folding and batched evaluation".to_string(),
+        ));
     }
+
+    let points = chain!([beta], squares_of_beta.into_iter().map(Neg::neg)).collect_vec();
+    let evals = chain![
+        (0..comms.len()).flat_map(|idx| [(idx, 0), (idx, 1)]),
+        (comms.len()..).zip(2..num_vars + 1)
+    ]
+    .zip(fri_evals.iter())
+    .map(|((idx, point), eval)| Evaluation::new(idx, point, *eval))
+    .collect_vec();
+    let comms = chain![comms.iter().copied(), &fs_comms].collect_vec();
+    UnivariateFri::<F, H>::batch_verify(vp, comms, &points, &evals, transcript)
 }
 
 #[cfg(test)]
@@ -208,15 +508,22 @@ mod test {
     use crate::{
         pcs::{
             multilinear::gemini::Gemini,
-            test::{run_batch_commit_open_verify, run_commit_open_verify},
-            univariate::UnivariateKzg,
+            test::{
+                run_batch_commit_open_verify, run_commit_open_verify,
+                run_verify_deferred_fold_decide,
+            },
+            univariate::{ShplonkBatch, UnivariateFri, UnivariateKzg},
         },
-        util::transcript::Keccak256Transcript,
+        util::{hash::Keccak256, transcript::Keccak256Transcript},
     };
-    use halo2_curves::bn256::Bn256;
+    use halo2_curves::bn256::{Bn256, Fr};
 
     type Pcs = Gemini<UnivariateKzg<Bn256>>;
 
+    type ShplonkPcs = Gemini<UnivariateKzg<Bn256, ShplonkBatch>>;
+
+    type FriPcs = Gemini<UnivariateFri<Fr, Keccak256>>;
+
     #[test]
     fn commit_open_verify() {
         run_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
@@ -226,4 +533,29 @@ mod test {
     fn batch_commit_open_verify() {
         run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
     }
+
+    #[test]
+    fn verify_deferred_fold_decide() {
+        run_verify_deferred_fold_decide::<Bn256, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn commit_open_verify_shplonk() {
+        run_commit_open_verify::<_, ShplonkPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify_shplonk() {
+        run_batch_commit_open_verify::<_, ShplonkPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn commit_open_verify_fri() {
+        run_commit_open_verify::<_, FriPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify_fri() {
+        run_batch_commit_open_verify::<_, FriPcs, Keccak256Transcript<_>>();
+    }
 }
@@ -5,14 +5,18 @@ use crate::{
             ipa::{MultilinearIpa, MultilinearIpaCommitment, MultilinearIpaParam},
             validate_input,
         },
+        univariate::ipa::verify_bulletproof_reduction_msm,
         Additive, Evaluation, Point, PolynomialCommitmentScheme,
     },
     poly::multilinear::MultilinearPolynomial,
     util::{
-        arithmetic::{batch_projective_to_affine, div_ceil, variable_base_msm, CurveAffine, Group},
+        arithmetic::{
+            batch_projective_to_affine, div_ceil, variable_base_msm, CurveAffine, Field, Group,
+        },
+        izip,
         parallel::parallelize,
         transcript::{TranscriptRead, TranscriptWrite},
-        Deserialize, DeserializeOwned, Itertools, Serialize,
+        Deserialize, DeserializeOwned, Either, Itertools, Serialize,
     },
     Error,
 };
@@ -283,19 +287,7 @@ where
         eval: &C::Scalar,
         transcript: &mut impl TranscriptRead<C, C::Scalar>,
     ) -> Result<(), Error> {
-        assert_eq!(comm.0.len(), vp.num_chunks());
-
-        let (lo, hi) = point.split_at(vp.row_num_vars());
-        let comm = {
-            MultilinearIpaCommitment(if hi.is_empty() {
-                assert_eq!(vp.num_chunks(), 1);
-                comm.0[0]
-            } else {
-                let scalars = MultilinearPolynomial::eq_xy(hi).into_evals();
-                variable_base_msm(&scalars, &comm.0).into()
-            })
-        };
-        MultilinearIpa::verify(&vp.ipa, &comm, &lo.to_vec(), eval, transcript)
+        verify_msm(vp, &[C::Scalar::ONE], &[comm], point, eval, transcript)
     }
 
     fn batch_verify<'a>(
@@ -306,10 +298,93 @@ where
         transcript: &mut impl TranscriptRead<C, C::Scalar>,
     ) -> Result<(), Error> {
         let comms = comms.into_iter().collect_vec();
-        additive::batch_verify::<_, Self>(vp, vp.num_vars(), comms, points, evals, transcript)
+        validate_input("batch verify", vp.num_vars(), [], points)?;
+
+        let (scalars, challenges, g_prime_eval) =
+            additive::batch_verify_reduction(vp.num_vars(), points, evals, transcript)?;
+        let scalars = izip!(evals, scalars).fold(
+            vec![C::Scalar::ZERO; comms.len()],
+            |mut acc, (eval, scalar)| {
+                acc[eval.poly()] += scalar;
+                acc
+            },
+        );
+        verify_msm(vp, &scalars, &comms, &challenges, &g_prime_eval, transcript)
     }
 }
 
+#[cfg(feature = "benchmark")]
+impl<C> MultilinearHyrax<C>
+where
+    C: CurveAffine + Serialize + DeserializeOwned,
+    C::ScalarExt: Serialize + DeserializeOwned,
+{
+    /// Same as [`PolynomialCommitmentScheme::batch_verify`] but combines
+    /// commitments per chunk and then rows by separate MSMs before verifying
+    /// IPA, which is only kept to benchmark against the merged MSM.
+    pub fn batch_verify_per_chunk<'a>(
+        vp: &MultilinearHyraxParam<C>,
+        comms: impl IntoIterator<Item = &'a MultilinearHyraxCommitment<C>>,
+        points: &[Vec<C::Scalar>],
+        evals: &[Evaluation<C::Scalar>],
+        transcript: &mut impl TranscriptRead<C, C::Scalar>,
+    ) -> Result<(), Error> {
+        let comms = comms.into_iter().collect_vec();
+        validate_input("batch verify", vp.num_vars(), [], points)?;
+
+        let (scalars, challenges, g_prime_eval) =
+            additive::batch_verify_reduction(vp.num_vars(), points, evals, transcript)?;
+        let g_prime_comm = {
+            let bases = evals.iter().map(|eval| comms[eval.poly()]);
+            MultilinearHyraxCommitment::msm(&scalars, bases)
+        };
+        let (lo, hi) = challenges.split_at(vp.row_num_vars());
+        let comm = MultilinearIpaCommitment(if hi.is_empty() {
+            g_prime_comm.0[0]
+        } else {
+            let scalars = MultilinearPolynomial::eq_xy(hi).into_evals();
+            variable_base_msm(&scalars, &g_prime_comm.0).into()
+        });
+        MultilinearIpa::verify(&vp.ipa, &comm, &lo.to_vec(), &g_prime_eval, transcript)
+    }
+}
+
+/// Verifies opening of linear combination of `comms` by `scalars`, where all
+/// MSMs, including the ones combining commitments and rows, are merged into
+/// the single MSM of IPA verifier.
+fn verify_msm<C: CurveAffine>(
+    vp: &MultilinearHyraxParam<C>,
+    scalars: &[C::Scalar],
+    comms: &[&MultilinearHyraxCommitment<C>],
+    point: &[C::Scalar],
+    eval: &C::Scalar,
+    transcript: &mut impl TranscriptRead<C, C::Scalar>,
+) -> Result<(), Error> {
+    let (lo, hi) = point.split_at(vp.row_num_vars());
+    let row_scalars = if hi.is_empty() {
+        assert_eq!(vp.num_chunks(), 1);
+        vec![C::Scalar::ONE]
+    } else {
+        MultilinearPolynomial::eq_xy(hi).into_evals()
+    };
+    let (comm_scalars, comm_bases) = izip!(scalars, comms)
+        .filter(|(_, comm)| !comm.0.is_empty())
+        .flat_map(|(scalar, comm)| {
+            assert_eq!(comm.0.len(), vp.num_chunks());
+            izip!(&row_scalars, &comm.0).map(move |(row_scalar, base)| (*scalar * row_scalar, base))
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    verify_bulletproof_reduction_msm(
+        vp.g(),
+        vp.h(),
+        &comm_scalars,
+        &comm_bases,
+        Either::Right(lo),
+        eval,
+        transcript,
+    )
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
@@ -1,7 +1,7 @@
 use crate::{
     pcs::{
         multilinear::{additive, err_too_many_variates, quotients, validate_input},
-        Additive, Evaluation, Point, PolynomialCommitmentScheme,
+        Additive, DeferredKzg, Evaluation, KzgAccumulator, Point, PolynomialCommitmentScheme,
     },
     poly::multilinear::MultilinearPolynomial,
     util::{
@@ -17,7 +17,7 @@ use crate::{
     Error,
 };
 use rand::RngCore;
-use std::{iter, marker::PhantomData, ops::Neg, slice};
+use std::{iter, marker::PhantomData, slice};
 
 #[derive(Clone, Debug)]
 pub struct MultilinearKzg<M: MultiMillerLoop>(PhantomData<M>);
@@ -323,41 +323,76 @@ where
         eval: &M::Scalar,
         transcript: &mut impl TranscriptRead<M::G1Affine, M::Scalar>,
     ) -> Result<(), Error> {
+        let accumulator = Self::verify_deferred(vp, comm, point, eval, transcript)?;
+        Self::decide(vp, &accumulator)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<M::Scalar, Self::Polynomial>],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<M::G1Affine, M::Scalar>,
+    ) -> Result<(), Error> {
+        let accumulator = Self::batch_verify_deferred(vp, comms, points, evals, transcript)?;
+        Self::decide(vp, &accumulator)
+    }
+}
+
+impl<M> DeferredKzg<M> for MultilinearKzg<M>
+where
+    M: MultiMillerLoop,
+    M::Scalar: Serialize + DeserializeOwned,
+    M::G1Affine: Serialize + DeserializeOwned,
+    M::G2Affine: Serialize + DeserializeOwned,
+{
+    fn accumulator_g2s(vp: &Self::VerifierParam) -> Vec<M::G2Affine> {
+        chain![[vp.g2], vp.ss.iter().copied()].collect()
+    }
+
+    /// Returns accumulator of `e(C - v * g1 + Σ x_i * π_i, g2) = ∏ e(π_i, s_i * g2)`,
+    /// where quotients `π_i` are padded by identity to `vp.num_vars()`.
+    fn verify_deferred(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<M::Scalar, Self::Polynomial>,
+        eval: &M::Scalar,
+        transcript: &mut impl TranscriptRead<M::G1Affine, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
         validate_input("verify", vp.num_vars(), [], [point])?;
 
         let quotients = transcript.read_commitments(point.len())?;
 
-        let window_size = window_size(point.len());
-        let window_table = window_table(window_size, vp.g2);
+        let neg_eval = -*eval;
+        let lhs = variable_base_msm(
+            chain![[&M::Scalar::ONE, &neg_eval], point],
+            chain![[&comm.0, &vp.g1], &quotients],
+        )
+        .into();
         let rhs = chain![
-            [vp.g2.neg()],
-            vp.ss(point.len())
-                .iter()
-                .cloned()
-                .zip_eq(fixed_base_msm(window_size, &window_table, point))
-                .map(|(s_i, x_i)| (s_i - x_i.into()).into()),
-        ]
-        .map_into()
-        .collect_vec();
-        let lhs = chain![
-            [(comm.0.to_curve() - vp.g1 * eval).into()],
-            quotients.iter().cloned()
+            quotients,
+            iter::repeat(M::G1Affine::identity()).take(vp.num_vars() - point.len())
         ]
-        .collect_vec();
-        M::pairings_product_is_identity(&lhs.iter().zip_eq(rhs.iter()).collect_vec())
-            .then_some(())
-            .ok_or_else(|| Error::InvalidPcsOpen("Invalid multilinear KZG open".to_string()))
+        .collect();
+        Ok(KzgAccumulator::new(lhs, rhs))
     }
 
-    fn batch_verify<'a>(
+    fn batch_verify_deferred<'a>(
         vp: &Self::VerifierParam,
         comms: impl IntoIterator<Item = &'a Self::Commitment>,
         points: &[Point<M::Scalar, Self::Polynomial>],
         evals: &[Evaluation<M::Scalar>],
         transcript: &mut impl TranscriptRead<M::G1Affine, M::Scalar>,
-    ) -> Result<(), Error> {
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
         let comms = comms.into_iter().collect_vec();
-        additive::batch_verify::<_, Self>(vp, vp.num_vars(), comms, points, evals, transcript)
+        additive::batch_verify_deferred::<M, Self>(
+            vp,
+            vp.num_vars(),
+            comms,
+            points,
+            evals,
+            transcript,
+        )
     }
 }
 
@@ -366,7 +401,10 @@ mod test {
     use crate::{
         pcs::{
             multilinear::kzg::MultilinearKzg,
-            test::{run_batch_commit_open_verify, run_commit_open_verify},
+            test::{
+                run_batch_commit_open_verify, run_commit_open_verify,
+                run_verify_deferred_fold_decide,
+            },
         },
         util::transcript::Keccak256Transcript,
     };
@@ -383,4 +421,9 @@ mod test {
     fn batch_commit_open_verify() {
         run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
     }
+
+    #[test]
+    fn verify_deferred_fold_decide() {
+        run_verify_deferred_fold_decide::<Bn256, Pcs, Keccak256Transcript<_>>();
+    }
 }
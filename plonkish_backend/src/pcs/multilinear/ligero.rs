@@ -0,0 +1,235 @@
+//! Implementation of multilinear polynomial commitment scheme described in
+//! [AHIV17], which is the same as [`MultilinearBrakedown`] but uses
+//! [`ReedSolomon`] as the linear codes.
+//!
+//! [AHIV17]: https://eprint.iacr.org/2022/1608.pdf
+//! [`MultilinearBrakedown`]: crate::pcs::multilinear::MultilinearBrakedown
+
+use crate::{
+    pcs::{
+        multilinear::{
+            brakedown::{commit_linear_code, open_linear_code, verify_linear_code},
+            validate_input, MultilinearBrakedownCommitment,
+        },
+        Evaluation, Point, PolynomialCommitmentScheme,
+    },
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::PrimeField,
+        code::{LinearCodes, ReedSolomon},
+        hash::MerkleHash,
+        transcript::{TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+use rand::RngCore;
+use std::marker::PhantomData;
+
+#[derive(Debug)]
+pub struct MultilinearLigero<F: PrimeField, H: MerkleHash<F>, const LOG_RATE: usize = 2>(
+    PhantomData<(F, H)>,
+);
+
+impl<F: PrimeField, H: MerkleHash<F>, const LOG_RATE: usize> Clone
+    for MultilinearLigero<F, H, LOG_RATE>
+{
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultilinearLigeroParam<F: PrimeField> {
+    num_vars: usize,
+    num_rows: usize,
+    reed_solomon: ReedSolomon<F>,
+}
+
+impl<F: PrimeField> MultilinearLigeroParam<F> {
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn reed_solomon(&self) -> &ReedSolomon<F> {
+        &self.reed_solomon
+    }
+}
+
+pub type MultilinearLigeroCommitment<F, H> = MultilinearBrakedownCommitment<F, H>;
+
+impl<F, H, const LOG_RATE: usize> PolynomialCommitmentScheme<F>
+    for MultilinearLigero<F, H, LOG_RATE>
+where
+    F: PrimeField + Serialize + DeserializeOwned,
+    H: MerkleHash<F>,
+{
+    type Param = MultilinearLigeroParam<F>;
+    type ProverParam = MultilinearLigeroParam<F>;
+    type VerifierParam = MultilinearLigeroParam<F>;
+    type Polynomial = MultilinearPolynomial<F>;
+    type Commitment = MultilinearLigeroCommitment<F, H>;
+    type CommitmentChunk = H::Output;
+
+    fn setup(poly_size: usize, _: usize, _: impl RngCore) -> Result<Self::Param, Error> {
+        assert!(poly_size.is_power_of_two());
+        let num_vars = poly_size.ilog2() as usize;
+        let reed_solomon = ReedSolomon::new_multilinear(num_vars, LOG_RATE);
+        Ok(MultilinearLigeroParam {
+            num_vars,
+            num_rows: (1 << num_vars) / reed_solomon.row_len(),
+            reed_solomon,
+        })
+    }
+
+    fn trim(
+        param: &Self::Param,
+        poly_size: usize,
+        _: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        assert!(poly_size.is_power_of_two());
+        if poly_size == 1 << param.num_vars {
+            Ok((param.clone(), param.clone()))
+        } else {
+            Err(Error::InvalidPcsParam(
+                "Can't trim MultilinearLigeroParam into different poly_size".to_string(),
+            ))
+        }
+    }
+
+    fn commit(pp: &Self::ProverParam, poly: &Self::Polynomial) -> Result<Self::Commitment, Error> {
+        validate_input("commit", pp.num_vars(), [poly], None)?;
+
+        Ok(commit_linear_code(&pp.reed_solomon, pp.num_rows, poly))
+    }
+
+    fn batch_commit<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+    ) -> Result<Vec<Self::Commitment>, Error>
+    where
+        Self::Polynomial: 'a,
+    {
+        polys
+            .into_iter()
+            .map(|poly| Self::commit(pp, poly))
+            .collect()
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        validate_input("open", pp.num_vars(), [poly], [point])?;
+
+        open_linear_code(
+            &pp.reed_solomon,
+            pp.num_rows,
+            poly,
+            comm,
+            point,
+            eval,
+            transcript,
+        )
+    }
+
+    fn batch_open<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let polys = polys.into_iter().collect_vec();
+        let comms = comms.into_iter().collect_vec();
+        for eval in evals {
+            Self::open(
+                pp,
+                polys[eval.poly()],
+                comms[eval.poly()],
+                &points[eval.point()],
+                eval.value(),
+                transcript,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn read_commitments(
+        _: &Self::VerifierParam,
+        num_polys: usize,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        transcript.read_commitments(num_polys).map(|roots| {
+            roots
+                .into_iter()
+                .map(MultilinearLigeroCommitment::from_root)
+                .collect_vec()
+        })
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        validate_input("verify", vp.num_vars(), [], [point])?;
+
+        verify_linear_code(&vp.reed_solomon, vp.num_rows, comm, point, eval, transcript)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let comms = comms.into_iter().collect_vec();
+        for eval in evals {
+            Self::verify(
+                vp,
+                comms[eval.poly()],
+                &points[eval.point()],
+                eval.value(),
+                transcript,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        pcs::{
+            multilinear::ligero::MultilinearLigero,
+            test::{run_batch_commit_open_verify, run_commit_open_verify},
+        },
+        util::{hash::Keccak256, transcript::Keccak256Transcript},
+    };
+    use halo2_curves::bn256::Fr;
+
+    type Pcs = MultilinearLigero<Fr, Keccak256>;
+
+    #[test]
+    fn commit_open_verify() {
+        run_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify() {
+        run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+}
@@ -1,18 +1,23 @@
 use crate::{
     pcs::{
-        multilinear::{additive, quotients},
+        multilinear::{
+            additive, prove_multi_point_reduction, quotients, verify_multi_point_reduction,
+        },
         univariate::{
-            err_too_large_deree, UnivariateKzg, UnivariateKzgProverParam,
-            UnivariateKzgVerifierParam,
+            err_too_large_deree, UnivariateFri, UnivariateFriCommitment, UnivariateFriParam,
+            UnivariateKzg, UnivariateKzgProverParam, UnivariateKzgVerifierParam,
         },
-        Evaluation, Point, PolynomialCommitmentScheme,
+        DeferredKzg, Evaluation, KzgAccumulator, Point, PolynomialCommitmentScheme,
     },
     poly::{multilinear::MultilinearPolynomial, univariate::UnivariatePolynomial},
     util::{
         arithmetic::{
-            powers, squares, variable_base_msm, BatchInvert, Curve, Field, MultiMillerLoop,
+            horner, inner_product, powers, squares, variable_base_msm, BatchInvert, Field,
+            MultiMillerLoop, PrimeField,
         },
-        chain, izip,
+        chain,
+        hash::{Hash, Output},
+        izip,
         parallel::parallelize,
         transcript::{TranscriptRead, TranscriptWrite},
         Deserialize, DeserializeOwned, Itertools, Serialize,
@@ -20,7 +25,7 @@ use crate::{
     Error,
 };
 use rand::RngCore;
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
 #[derive(Clone, Debug)]
 pub struct Zeromorph<Pcs>(PhantomData<Pcs>);
@@ -113,7 +118,10 @@ where
             return Err(err_too_large_deree("commit", pp.degree(), got));
         }
 
-        Ok(UnivariateKzg::commit_monomial(&pp.commit_pp, poly.evals()))
+        Ok(UnivariateKzg::<M>::commit_monomial(
+            &pp.commit_pp,
+            poly.evals(),
+        ))
     }
 
     fn batch_commit<'a>(
@@ -147,7 +155,7 @@ where
 
         let (quotients, remainder) =
             quotients(poly, point, |_, q| UnivariatePolynomial::monomial(q));
-        UnivariateKzg::batch_commit_and_write(&pp.commit_pp, &quotients, transcript)?;
+        UnivariateKzg::<M>::batch_commit_and_write(&pp.commit_pp, &quotients, transcript)?;
 
         if cfg!(feature = "sanity-check") {
             assert_eq!(&remainder, eval);
@@ -166,7 +174,7 @@ where
             }
             UnivariatePolynomial::monomial(q_hat)
         };
-        UnivariateKzg::commit_and_write(&pp.commit_pp, &q_hat, transcript)?;
+        UnivariateKzg::<M>::commit_and_write(&pp.commit_pp, &q_hat, transcript)?;
 
         let x = transcript.squeeze_challenge();
         let z = transcript.squeeze_challenge();
@@ -182,7 +190,7 @@ where
         let comm = if cfg!(feature = "sanity-check") {
             assert_eq!(f.evaluate(&x), M::Scalar::ZERO);
 
-            UnivariateKzg::commit_monomial(&pp.open_pp, f.coeffs())
+            UnivariateKzg::<M>::commit_monomial(&pp.open_pp, f.coeffs())
         } else {
             Default::default()
         };
@@ -212,7 +220,7 @@ where
         num_polys: usize,
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
     ) -> Result<Vec<Self::Commitment>, Error> {
-        UnivariateKzg::read_commitments(&vp.vp, num_polys, transcript)
+        UnivariateKzg::<M>::read_commitments(&vp.vp, num_polys, transcript)
     }
 
     fn verify(
@@ -222,6 +230,42 @@ where
         eval: &M::Scalar,
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
     ) -> Result<(), Error> {
+        let accumulator = Self::verify_deferred(vp, comm, point, eval, transcript)?;
+        Self::decide(vp, &accumulator)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<M::Scalar, Self::Polynomial>],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<(), Error> {
+        let accumulator = Self::batch_verify_deferred(vp, comms, points, evals, transcript)?;
+        Self::decide(vp, &accumulator)
+    }
+}
+
+impl<M> DeferredKzg<M> for Zeromorph<UnivariateKzg<M>>
+where
+    M: MultiMillerLoop,
+    M::Scalar: Serialize + DeserializeOwned,
+    M::G1Affine: Serialize + DeserializeOwned,
+    M::G2Affine: Serialize + DeserializeOwned,
+{
+    fn accumulator_g2s(vp: &Self::VerifierParam) -> Vec<M::G2Affine> {
+        vec![vp.g2(), vp.s_g2(), vp.s_offset_g2]
+    }
+
+    /// Returns accumulator of `e(x * π, g2) = e(π, s * g2) * e(-C, s^offset * g2)`,
+    /// which is rearranged from `e(C, s^offset * g2) = e(π, s * g2 - x * g2)`.
+    fn verify_deferred(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<M::Scalar, Self::Polynomial>,
+        eval: &M::Scalar,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
         let num_vars = point.len();
 
         let q_comms = transcript.read_commitments(num_vars)?;
@@ -237,29 +281,294 @@ where
 
         let scalars = chain![[M::Scalar::ONE, z, eval_scalar * eval], q_scalars].collect_vec();
         let bases = chain![[q_hat_comm, comm.0, vp.g1()], q_comms].collect_vec();
-        let c = variable_base_msm(&scalars, &bases).into();
+        let c = variable_base_msm(&scalars, &bases);
 
         let pi = transcript.read_commitment()?;
 
-        M::pairings_product_is_identity(&[
-            (&c, &(-vp.s_offset_g2).into()),
-            (&pi, &(vp.s_g2() - (vp.g2() * x).into()).to_affine().into()),
-        ])
-        .then_some(())
-        .ok_or_else(|| Error::InvalidPcsOpen("Invalid Zeromorph KZG open".to_string()))
+        Ok(KzgAccumulator::new((pi * x).into(), vec![pi, (-c).into()]))
     }
 
-    fn batch_verify<'a>(
+    fn batch_verify_deferred<'a>(
         vp: &Self::VerifierParam,
         comms: impl IntoIterator<Item = &'a Self::Commitment>,
         points: &[Point<M::Scalar, Self::Polynomial>],
         evals: &[Evaluation<M::Scalar>],
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
+        let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+        let comms = comms.into_iter().collect_vec();
+        additive::batch_verify_deferred::<M, Self>(vp, num_vars, comms, points, evals, transcript)
+    }
+}
+
+impl<F, H> PolynomialCommitmentScheme<F> for Zeromorph<UnivariateFri<F, H>>
+where
+    F: PrimeField + Serialize + DeserializeOwned,
+    H: Hash,
+{
+    type Param = UnivariateFriParam;
+    type ProverParam = UnivariateFriParam;
+    type VerifierParam = UnivariateFriParam;
+    type Polynomial = MultilinearPolynomial<F>;
+    type Commitment = UnivariateFriCommitment<F, H>;
+    type CommitmentChunk = Output<H>;
+
+    fn setup(poly_size: usize, batch_size: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
+        UnivariateFri::<F, H>::setup(poly_size, batch_size, rng)
+    }
+
+    fn trim(
+        param: &Self::Param,
+        poly_size: usize,
+        batch_size: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        UnivariateFri::<F, H>::trim(param, poly_size, batch_size)
+    }
+
+    fn commit(pp: &Self::ProverParam, poly: &Self::Polynomial) -> Result<Self::Commitment, Error> {
+        if pp.degree() + 1 < poly.evals().len() {
+            let got = poly.evals().len() - 1;
+            return Err(err_too_large_deree("commit", pp.degree(), got));
+        }
+
+        Ok(UnivariateFri::commit_monomial(pp, poly.evals()))
+    }
+
+    fn batch_commit<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        polys
+            .into_iter()
+            .map(|poly| Self::commit(pp, poly))
+            .collect()
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
     ) -> Result<(), Error> {
+        if pp.degree() + 1 < poly.evals().len() {
+            let got = poly.evals().len() - 1;
+            return Err(err_too_large_deree("open", pp.degree(), got));
+        }
+
+        if cfg!(feature = "sanity-check") {
+            assert_eq!(Self::commit(pp, poly).unwrap().root(), comm.root());
+            assert_eq!(poly.evaluate(point), *eval);
+        }
+
+        open_fri(pp, &[poly], &[comm], point, transcript)
+    }
+
+    fn batch_open<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let polys = polys.into_iter().collect_vec();
+        let comms = comms.into_iter().collect_vec();
         let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+        for eval in evals {
+            let poly = &polys[eval.poly()];
+            if pp.degree() + 1 < poly.evals().len() {
+                let got = poly.evals().len() - 1;
+                return Err(err_too_large_deree("batch open", pp.degree(), got));
+            }
+        }
+
+        if cfg!(feature = "sanity-check") {
+            for eval in evals {
+                let (poly, point) = (&polys[eval.poly()], &points[eval.point()]);
+                assert_eq!(poly.evaluate(point), *eval.value());
+            }
+        }
+
+        let (indices, point) =
+            prove_multi_point_reduction(num_vars, &polys, points, evals, transcript)?;
+
+        let polys = indices.iter().map(|idx| polys[*idx]).collect_vec();
+        let comms = indices.iter().map(|idx| comms[*idx]).collect_vec();
+        open_fri(pp, &polys, &comms, &point, transcript)
+    }
+
+    fn read_commitments(
+        vp: &Self::VerifierParam,
+        num_polys: usize,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        UnivariateFri::<F, H>::read_commitments(vp, num_polys, transcript)
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        verify_fri(vp, &[comm], point, &[*eval], transcript)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
         let comms = comms.into_iter().collect_vec();
-        additive::batch_verify::<_, Self>(vp, num_vars, comms, points, evals, transcript)
+        let num_vars = points.first().map(|point| point.len()).unwrap_or_default();
+
+        let (indices, point, poly_evals) =
+            verify_multi_point_reduction(num_vars, points, evals, transcript)?;
+
+        let comms = indices.iter().map(|idx| comms[*idx]).collect_vec();
+        verify_fri(vp, &comms, &point, &poly_evals, transcript)
+    }
+}
+
+/// Proves evaluations of `polys` on the same `point`, where `polys` are
+/// batched by powers of `α` before computing quotients, then each of `polys`,
+/// quotients and `q_hat` is opened on `x` by a single FRI, which also bounds
+/// degree of quotients through `q_hat` by `2^num_vars - 1` exactly thanks to
+/// the degree correction in [`UnivariateFri::batch_open_committed`]. The `α`
+/// is only squeezed when there are multiple polys.
+fn open_fri<F: PrimeField, H: Hash>(
+    pp: &UnivariateFriParam,
+    polys: &[&MultilinearPolynomial<F>],
+    comms: &[&UnivariateFriCommitment<F, H>],
+    point: &[F],
+    transcript: &mut impl TranscriptWrite<Output<H>, F>,
+) -> Result<(), Error> {
+    let num_vars = point.len();
+
+    let g = if polys.len() == 1 {
+        Cow::Borrowed(polys[0])
+    } else {
+        let alphas = powers(transcript.squeeze_challenge()).take(polys.len());
+        Cow::Owned(izip!(alphas, polys.iter().copied()).sum::<MultilinearPolynomial<_>>())
+    };
+
+    let (quotients, remainder) = quotients(&*g, point, |_, q| q);
+    let mut q_comms = quotients
+        .iter()
+        .map(|q| {
+            let comm = UnivariateFri::<F, H>::commit_monomial(pp, q);
+            transcript.write_commitment(comm.root())?;
+            Ok(comm)
+        })
+        .try_collect::<_, Vec<_>, Error>()?;
+
+    let y = transcript.squeeze_challenge();
+
+    let q_hat = q_hat(num_vars, &quotients, y);
+    let q_hat_comm = UnivariateFri::<F, H>::commit_monomial(pp, &q_hat);
+    transcript.write_commitment(q_hat_comm.root())?;
+    q_comms.push(q_hat_comm);
+
+    let x = transcript.squeeze_challenge();
+
+    let evals = chain![
+        polys.iter().map(|poly| poly.evals()),
+        quotients.iter().map(Vec::as_slice),
+        [q_hat.as_slice()]
+    ]
+    .enumerate()
+    .map(|(idx, coeffs)| Evaluation::new(idx, 0, horner(coeffs, &x)))
+    .collect_vec();
+    transcript.write_field_elements(evals.iter().map(Evaluation::value))?;
+
+    let z = transcript.squeeze_challenge();
+
+    if cfg!(feature = "sanity-check") {
+        let (eval_scalar, q_scalars) = eval_and_quotient_scalars(y, x, z, point);
+        let q_evals = quotients.iter().map(|q| horner(q, &x)).collect_vec();
+        assert_eq!(
+            horner(&q_hat, &x)
+                + z * horner(g.evals(), &x)
+                + eval_scalar * remainder
+                + inner_product(&q_scalars, &q_evals),
+            F::ZERO
+        );
     }
+
+    let comms = chain![comms.iter().copied(), &q_comms].collect_vec();
+    UnivariateFri::batch_open_committed(pp, &comms, &[x], &evals, transcript)
+}
+
+fn verify_fri<F: PrimeField, H: Hash>(
+    vp: &UnivariateFriParam,
+    comms: &[&UnivariateFriCommitment<F, H>],
+    point: &[F],
+    evals: &[F],
+    transcript: &mut impl TranscriptRead<Output<H>, F>,
+) -> Result<(), Error> {
+    let num_vars = point.len();
+
+    let alphas = if comms.len() == 1 {
+        vec![F::ONE]
+    } else {
+        powers(transcript.squeeze_challenge())
+            .take(comms.len())
+            .collect_vec()
+    };
+
+    let q_comms = transcript.read_commitments(num_vars)?;
+
+    let y = transcript.squeeze_challenge();
+
+    let q_hat_comm = transcript.read_commitment()?;
+
+    let x = transcript.squeeze_challenge();
+
+    let fri_evals = transcript.read_field_elements(comms.len() + num_vars + 1)?;
+    let (poly_evals, q_evals) = fri_evals.split_at(comms.len());
+    let (q_evals, q_hat_eval) = q_evals.split_at(num_vars);
+
+    let z = transcript.squeeze_challenge();
+
+    let (eval_scalar, q_scalars) = eval_and_quotient_scalars(y, x, z, point);
+    if q_hat_eval[0]
+        + z * inner_product(&alphas, poly_evals)
+        + eval_scalar * inner_product(&alphas, evals)
+        + inner_product(&q_scalars, q_evals)
+        != F::ZERO
+    {
+        return Err(Error::InvalidPcsOpen(
+            "Invalid Zeromorph FRI open".to_string(),
+        ));
+    }
+
+    let evals = fri_evals
+        .iter()
+        .enumerate()
+        .map(|(idx, eval)| Evaluation::new(idx, 0, *eval))
+        .collect_vec();
+    let q_comms = chain![q_comms, [q_hat_comm]]
+        .map(UnivariateFriCommitment::from_root)
+        .collect_vec();
+    let comms = chain![comms.iter().copied(), &q_comms].collect_vec();
+    UnivariateFri::<F, H>::batch_verify(vp, comms, &[x], &evals, transcript)
+}
+
+/// Returns `q_hat(X) = Σ_k y^k * X^(2^num_vars - 2^k) * q_k(X)`.
+fn q_hat<F: Field>(num_vars: usize, quotients: &[Vec<F>], y: F) -> Vec<F> {
+    let mut q_hat = vec![F::ZERO; 1 << num_vars];
+    for (idx, (power_of_y, q)) in izip!(powers(y), quotients).enumerate() {
+        let offset = (1 << num_vars) - (1 << idx);
+        parallelize(&mut q_hat[offset..], |(q_hat, start)| {
+            izip!(q_hat, q.iter().skip(start)).for_each(|(q_hat, q)| *q_hat += power_of_y * q)
+        });
+    }
+    q_hat
 }
 
 fn eval_and_quotient_scalars<F: Field>(y: F, x: F, z: F, u: &[F]) -> (F, Vec<F>) {
@@ -304,16 +613,32 @@ fn eval_and_quotient_scalars<F: Field>(y: F, x: F, z: F, u: &[F]) -> (F, Vec<F>)
 mod test {
     use crate::{
         pcs::{
-            multilinear::zeromorph::Zeromorph,
-            test::{run_batch_commit_open_verify, run_commit_open_verify},
-            univariate::UnivariateKzg,
+            multilinear::{
+                quotients,
+                zeromorph::{q_hat, Zeromorph},
+            },
+            test::{
+                run_batch_commit_open_verify, run_commit_open_verify,
+                run_verify_deferred_fold_decide,
+            },
+            univariate::{UnivariateFri, UnivariateKzg},
+            Evaluation, PolynomialCommitmentScheme,
+        },
+        poly::multilinear::MultilinearPolynomial,
+        util::{
+            arithmetic::{horner, Field},
+            hash::Keccak256,
+            transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptWrite},
+            Itertools,
         },
-        util::transcript::Keccak256Transcript,
     };
-    use halo2_curves::bn256::Bn256;
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
 
     type Pcs = Zeromorph<UnivariateKzg<Bn256>>;
 
+    type FriPcs = Zeromorph<UnivariateFri<Fr, Keccak256>>;
+
     #[test]
     fn commit_open_verify() {
         run_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
@@ -323,4 +648,53 @@ mod test {
     fn batch_commit_open_verify() {
         run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
     }
+
+    #[test]
+    fn verify_deferred_fold_decide() {
+        run_verify_deferred_fold_decide::<Bn256, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn commit_open_verify_fri() {
+        run_commit_open_verify::<_, FriPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify_fri() {
+        run_batch_commit_open_verify::<_, FriPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    #[cfg(not(feature = "sanity-check"))]
+    fn reject_over_degree_q_hat_fri() {
+        type Fri = UnivariateFri<Fr, Keccak256>;
+
+        let num_vars = 4;
+        let (pp, vp) = {
+            let param = Fri::setup(1 << num_vars, 0, OsRng).unwrap();
+            Fri::trim(&param, 1 << num_vars, 0).unwrap()
+        };
+        let poly = MultilinearPolynomial::rand(num_vars, OsRng);
+        let point = (0..num_vars).map(|_| Fr::random(OsRng)).collect_vec();
+        let (quotients, _) = quotients(&poly, &point, |_, q| q);
+        // `q_hat` of degree `2^num_vars` as if `q_0` had degree 1
+        let mut q_hat = q_hat(num_vars, &quotients, Fr::random(OsRng));
+        q_hat.push(Fr::random(OsRng));
+
+        let x = Fr::random(OsRng);
+        let evals = [Evaluation::new(0, 0, horner(&q_hat, &x))];
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            let comm = Fri::commit_monomial(&pp, &q_hat);
+            transcript.write_commitment(comm.root()).unwrap();
+            Fri::batch_open_committed(&pp, &[&comm], &[x], &evals, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        let result = {
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            let comm = Fri::read_commitment(&vp, &mut transcript).unwrap();
+            Fri::batch_verify(&vp, [&comm], &[x], &evals, &mut transcript)
+        };
+        assert!(result.is_err());
+    }
 }
@@ -2,7 +2,7 @@ use crate::{
     poly::univariate::{UnivariateBasis::*, UnivariatePolynomial},
     util::{
         arithmetic::{
-            batch_projective_to_affine, radix2_fft, root_of_unity_inv, squares, CurveAffine, Field,
+            batch_projective_to_affine, radix2_fft, root_of_unity_inv, CurveAffine, Field,
             PrimeField,
         },
         parallel::parallelize,
@@ -11,10 +11,12 @@ use crate::{
     Error,
 };
 
+mod fri;
 mod hyrax;
 pub(super) mod ipa;
 mod kzg;
 
+pub use fri::{UnivariateFri, UnivariateFriCommitment, UnivariateFriParam};
 pub use hyrax::{
     UnivariateHyrax, UnivariateHyraxCommitment, UnivariateHyraxParam, UnivariateHyraxVerifierParam,
 };
@@ -22,15 +24,15 @@ pub use ipa::{
     UnivariateIpa, UnivariateIpaCommitment, UnivariateIpaParam, UnivariateIpaVerifierParam,
 };
 pub use kzg::{
-    UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
-    UnivariateKzgVerifierParam,
+    AdditiveBatch, KzgBatchOpening, ShplonkBatch, UnivariateKzg, UnivariateKzgCommitment,
+    UnivariateKzgParam, UnivariateKzgProverParam, UnivariateKzgVerifierParam,
 };
 
 fn monomial_g_to_lagrange_g<C: CurveAffine>(monomial_g: &[C]) -> Vec<C> {
     assert!(monomial_g.len().is_power_of_two());
 
     let k = monomial_g.len().ilog2() as usize;
-    let n_inv = squares(C::Scalar::TWO_INV).nth(k).unwrap();
+    let n_inv = C::Scalar::TWO_INV.pow_vartime([k as u64]);
     let omega_inv = root_of_unity_inv(k);
 
     let mut lagrange = monomial_g.iter().map(C::to_curve).collect_vec();
@@ -56,8 +58,9 @@ fn validate_input<'a, F: Field>(
                 }
             }
             Lagrange => {
-                if param_degree + 1 != poly.coeffs().len() {
-                    return Err(err_invalid_evals_len(param_degree, poly.coeffs().len() - 1));
+                let n = lagrange_domain_size(param_degree);
+                if n != poly.coeffs().len() {
+                    return Err(err_invalid_evals_len(n, poly.coeffs().len()));
                 }
             }
         }
@@ -65,6 +68,12 @@ fn validate_input<'a, F: Field>(
     Ok(())
 }
 
+/// Returns size of the next power-of-two domain of `param_degree`, which is
+/// the only domain Lagrange polynomials are committed on.
+fn lagrange_domain_size(param_degree: usize) -> usize {
+    (param_degree + 1).next_power_of_two()
+}
+
 pub(super) fn err_too_large_deree(function: &str, upto: usize, got: usize) -> Error {
     Error::InvalidPcsParam(if function == "trim" {
         format!("Too large degree to {function} (param supports degree up to {upto} but got {got})")
@@ -83,12 +92,14 @@ fn err_invalid_evals_len(expected: usize, got: usize) -> Error {
 
 mod additive {
     use crate::{
-        pcs::{Additive, Evaluation, Point, PolynomialCommitmentScheme},
+        pcs::{
+            Additive, DeferredKzg, Evaluation, KzgAccumulator, Point, PolynomialCommitmentScheme,
+        },
         poly::univariate::UnivariatePolynomial,
         util::{
             arithmetic::{
                 barycentric_interpolate, barycentric_weights, fe_to_bytes, inner_product, powers,
-                Field, PrimeField,
+                Field, MultiMillerLoop, PrimeField,
             },
             chain, izip, izip_eq,
             transcript::{TranscriptRead, TranscriptWrite},
@@ -122,7 +133,7 @@ mod additive {
             }
         }
 
-        let (sets, superset) = eval_sets(evals);
+        let (sets, superset) = eval_sets(evals)?;
 
         let beta = transcript.squeeze_challenge();
         let gamma = transcript.squeeze_challenge();
@@ -178,7 +189,47 @@ mod additive {
         Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
         Pcs::Commitment: Additive<F>,
     {
-        let (sets, superset) = eval_sets(evals);
+        let (scalars, q_comm, z, eval) =
+            batch_verify_reduction::<_, Pcs>(vp, comms.len(), points, evals, transcript)?;
+        let f = Pcs::Commitment::msm(&scalars, chain![comms, [&q_comm]]);
+        Pcs::verify(vp, &f, &z, &eval, transcript)
+    }
+
+    /// Same as [`batch_verify`] but returns accumulator of the pairing check.
+    pub fn batch_verify_deferred<M, Pcs>(
+        vp: &Pcs::VerifierParam,
+        comms: Vec<&Pcs::Commitment>,
+        points: &[Point<M::Scalar, Pcs::Polynomial>],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error>
+    where
+        M: MultiMillerLoop,
+        Pcs: DeferredKzg<M, Polynomial = UnivariatePolynomial<M::Scalar>>,
+        Pcs::Commitment: Additive<M::Scalar>,
+    {
+        let (scalars, q_comm, z, eval) =
+            batch_verify_reduction::<_, Pcs>(vp, comms.len(), points, evals, transcript)?;
+        let f = Pcs::Commitment::msm(&scalars, chain![comms, [&q_comm]]);
+        Pcs::verify_deferred(vp, &f, &z, &eval, transcript)
+    }
+
+    /// Reads the commitment of quotient `q` of [`batch_verify`], and returns
+    /// scalars of `num_comms` commitments followed by the one of `q`, such that
+    /// the commitment of `f` is linear combination of them by the scalars, with
+    /// the point and evaluation of `f` to be opened.
+    pub fn batch_verify_reduction<F, Pcs>(
+        vp: &Pcs::VerifierParam,
+        num_comms: usize,
+        points: &[Point<F, Pcs::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+    ) -> Result<(Vec<F>, Pcs::Commitment, F, F), Error>
+    where
+        F: PrimeField,
+        Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+    {
+        let (sets, superset) = eval_sets(evals)?;
 
         let beta = transcript.squeeze_challenge();
         let gamma = transcript.squeeze_challenge();
@@ -192,11 +243,11 @@ mod additive {
         let powers_of_gamma = powers(gamma).take(sets.len()).collect_vec();
 
         let (normalized_scalars, normalizer) = set_scalars(&sets, &powers_of_gamma, points, &z);
-        let f = {
-            let scalars = comm_scalars(comms.len(), &sets, &powers_of_beta, &normalized_scalars);
+        let scalars = {
+            let scalars = comm_scalars(num_comms, &sets, &powers_of_beta, &normalized_scalars);
             let superset_eval = vanishing_eval(superset.iter().map(|idx| &points[*idx]), &z);
             let q_scalar = -superset_eval * normalizer;
-            Pcs::Commitment::msm(chain![&scalars, [&q_scalar]], chain![comms, [&q_comm]])
+            chain![scalars, [q_scalar]].collect_vec()
         };
         let eval = inner_product(
             &normalized_scalars,
@@ -205,30 +256,32 @@ mod additive {
                 .map(|set| set.r_eval(points, &z, &powers_of_beta))
                 .collect_vec(),
         );
-        Pcs::verify(vp, &f, &z, &eval, transcript)
+        Ok((scalars, q_comm, z, eval))
     }
 
+    /// Polys opened on the same set of points, with `diffs` being the points
+    /// not in the set but in the superset.
     #[derive(Debug)]
-    struct EvaluationSet<F: Field> {
-        polys: Vec<usize>,
-        points: Vec<usize>,
-        diffs: Vec<usize>,
-        evals: Vec<Vec<F>>,
+    pub(super) struct EvaluationSet<F: Field> {
+        pub(super) polys: Vec<usize>,
+        pub(super) points: Vec<usize>,
+        pub(super) diffs: Vec<usize>,
+        pub(super) evals: Vec<Vec<F>>,
     }
 
     impl<F: Field> EvaluationSet<F> {
-        fn vanishing_diff_eval(&self, points: &[F], z: &F) -> F {
+        pub(super) fn vanishing_diff_eval(&self, points: &[F], z: &F) -> F {
             self.diffs
                 .iter()
                 .map(|idx| points[*idx])
                 .fold(F::ONE, |eval, point| eval * (*z - point))
         }
 
-        fn vanishing_poly(&self, points: &[F]) -> UnivariatePolynomial<F> {
+        pub(super) fn vanishing_poly(&self, points: &[F]) -> UnivariatePolynomial<F> {
             UnivariatePolynomial::vanishing(self.points.iter().map(|point| &points[*point]), F::ONE)
         }
 
-        fn r_eval(&self, points: &[F], z: &F, powers_of_beta: &[F]) -> F {
+        pub(super) fn r_eval(&self, points: &[F], z: &F, powers_of_beta: &[F]) -> F {
             let points = self.points.iter().map(|idx| points[*idx]).collect_vec();
             let weights = barycentric_weights(&points);
             let r_evals = self
@@ -240,8 +293,13 @@ mod additive {
         }
     }
 
-    fn eval_sets<F: Field>(evals: &[Evaluation<F>]) -> (Vec<EvaluationSet<F>>, BTreeSet<usize>) {
-        let (poly_shifts, superset) = evals.iter().fold(
+    /// Returns evaluation sets of polys opened on the same set of points and
+    /// the superset of all opened points, or error if a poly is opened on the
+    /// same point with conflicting values.
+    pub(super) fn eval_sets<F: Field>(
+        evals: &[Evaluation<F>],
+    ) -> Result<(Vec<EvaluationSet<F>>, BTreeSet<usize>), Error> {
+        let (poly_shifts, superset) = evals.iter().try_fold(
             (Vec::<(usize, Vec<usize>, Vec<F>)>::new(), BTreeSet::new()),
             |(mut poly_shifts, mut superset), eval| {
                 if let Some(pos) = poly_shifts
@@ -249,17 +307,26 @@ mod additive {
                     .position(|(poly, _, _)| *poly == eval.poly)
                 {
                     let (_, points, evals) = &mut poly_shifts[pos];
-                    if !points.contains(&eval.point) {
-                        points.push(eval.point);
-                        evals.push(*eval.value());
+                    match points.iter().position(|point| *point == eval.point) {
+                        Some(idx) if evals[idx] != *eval.value() => {
+                            return Err(Error::InvalidPcsOpen(format!(
+                                "Conflicting evaluations of poly {} on point {}",
+                                eval.poly, eval.point
+                            )));
+                        }
+                        Some(_) => {}
+                        None => {
+                            points.push(eval.point);
+                            evals.push(*eval.value());
+                        }
                     }
                 } else {
                     poly_shifts.push((eval.poly, vec![eval.point], vec![*eval.value()]));
                 }
                 superset.insert(eval.point());
-                (poly_shifts, superset)
+                Ok((poly_shifts, superset))
             },
-        );
+        )?;
 
         let sets = poly_shifts.into_iter().fold(
             Vec::<EvaluationSet<_>>::new(),
@@ -297,7 +364,7 @@ mod additive {
             },
         );
 
-        (sets, superset)
+        Ok((sets, superset))
     }
 
     fn set_scalars<F: Field>(
@@ -321,7 +388,10 @@ mod additive {
         (normalized_scalars, normalizer)
     }
 
-    fn vanishing_eval<'a, F: Field>(points: impl IntoIterator<Item = &'a F>, z: &F) -> F {
+    pub(super) fn vanishing_eval<'a, F: Field>(
+        points: impl IntoIterator<Item = &'a F>,
+        z: &F,
+    ) -> F {
         points
             .into_iter()
             .fold(F::ONE, |eval, point| eval * (*z - point))
@@ -343,3 +413,82 @@ mod additive {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        pcs::{
+            test::{
+                run_batch_commit_open_verify_with_poly_size, run_commit_open_verify_with_poly_size,
+            },
+            PolynomialCommitmentScheme,
+        },
+        poly::univariate::UnivariatePolynomial,
+        util::{
+            arithmetic::{radix2_fft, root_of_unity, PrimeField},
+            test::rand_vec,
+            transcript::{InMemoryTranscript, TranscriptRead, TranscriptWrite},
+        },
+    };
+    use rand::rngs::OsRng;
+
+    /// Pairs of setup and trimmed poly sizes for degree `1000` and `3 * 2^k`,
+    /// with trimmed size smaller than or equal to the setup one.
+    const POLY_SIZES: [(usize, usize); 4] = [
+        (1001, 1001),
+        ((3 << 10) + 1, (3 << 10) + 1),
+        ((3 << 10) + 1, 1001),
+        ((3 << 10) + 1, (3 << 8) + 1),
+    ];
+
+    pub(super) fn run_commit_open_verify_arbitrary_degree<F, Pcs, T>()
+    where
+        F: PrimeField,
+        Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+        T: TranscriptRead<Pcs::CommitmentChunk, F>
+            + TranscriptWrite<Pcs::CommitmentChunk, F>
+            + InMemoryTranscript<Param = ()>,
+    {
+        for (param_size, poly_size) in POLY_SIZES {
+            run_commit_open_verify_with_poly_size::<F, Pcs, T>(param_size, poly_size);
+        }
+    }
+
+    pub(super) fn run_batch_commit_open_verify_arbitrary_degree<F, Pcs, T>()
+    where
+        F: PrimeField,
+        Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+        T: TranscriptRead<Pcs::CommitmentChunk, F>
+            + TranscriptWrite<Pcs::CommitmentChunk, F>
+            + InMemoryTranscript<Param = ()>,
+    {
+        for (param_size, poly_size) in POLY_SIZES {
+            run_batch_commit_open_verify_with_poly_size::<F, Pcs, T>(param_size, poly_size);
+        }
+    }
+
+    /// Checks commitment of Lagrange polynomial of `n` evaluations is the same
+    /// as the one of its monomial form of degree `poly_size - 1`, with `Pcs`
+    /// setup for `param_size` and trimmed to `poly_size`.
+    pub(super) fn run_commit_lagrange<F, Pcs>(param_size: usize, poly_size: usize, n: usize)
+    where
+        F: PrimeField,
+        Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+        Pcs::CommitmentChunk: PartialEq,
+    {
+        let param = Pcs::setup(param_size, 1, OsRng).unwrap();
+        let (pp, _) = Pcs::trim(&param, poly_size, 1).unwrap();
+
+        let coeffs = rand_vec(poly_size, OsRng);
+        let evals = {
+            let k = n.ilog2() as usize;
+            let mut evals = coeffs.clone();
+            evals.resize(n, F::ZERO);
+            radix2_fft(&mut evals, root_of_unity(k), k);
+            evals
+        };
+        let lagrange = Pcs::commit(&pp, &UnivariatePolynomial::lagrange(evals)).unwrap();
+        let monomial = Pcs::commit(&pp, &UnivariatePolynomial::monomial(coeffs)).unwrap();
+        assert_eq!(lagrange.as_ref(), monomial.as_ref());
+    }
+}
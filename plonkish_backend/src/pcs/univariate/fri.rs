@@ -0,0 +1,542 @@
+//! Implementation of univariate polynomial commitment scheme with FRI [BBHR18]
+//! as low-degree test, where evaluations on multiple points are proven by a
+//! single FRI on the batched DEEP quotient [BGKS19].
+//!
+//! [BBHR18]: https://eccc.weizmann.ac.il/report/2017/134
+//! [BGKS19]: https://arxiv.org/abs/1903.12243
+
+use crate::{
+    pcs::{
+        squeeze_challenge_idx,
+        univariate::{err_too_large_deree, validate_input},
+        Evaluation, Point, PolynomialCommitmentScheme,
+    },
+    poly::univariate::{UnivariateBasis::*, UnivariatePolynomial},
+    util::{
+        arithmetic::{
+            powers, radix2_fft, root_of_unity, root_of_unity_inv, squares, BatchInvert, PrimeField,
+        },
+        end_timer,
+        hash::{Hash, MerkleTree, Output},
+        izip,
+        parallel::{par_map_collect, parallelize},
+        start_timer,
+        transcript::{TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+use rand::RngCore;
+use std::{collections::HashMap, marker::PhantomData, slice};
+
+const LOG_RATE: usize = 3;
+
+const NUM_QUERIES: usize = 120;
+
+#[derive(Debug)]
+pub struct UnivariateFri<F: PrimeField, H: Hash>(PhantomData<(F, H)>);
+
+impl<F: PrimeField, H: Hash> Clone for UnivariateFri<F, H> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: PrimeField, H: Hash> UnivariateFri<F, H> {
+    /// Commits to `coeffs` by evaluating it on subgroup of order
+    /// `2^(k + log_rate)`.
+    pub(crate) fn commit_monomial(
+        pp: &UnivariateFriParam,
+        coeffs: &[F],
+    ) -> UnivariateFriCommitment<F, H> {
+        let log_n = pp.k + pp.log_rate;
+        let mut codeword = vec![F::ZERO; 1 << log_n];
+        codeword[..coeffs.len()].copy_from_slice(coeffs);
+        radix2_fft(&mut codeword, root_of_unity(log_n), log_n);
+        UnivariateFriCommitment::new(codeword)
+    }
+
+    pub(crate) fn commit_lagrange(
+        pp: &UnivariateFriParam,
+        evals: &[F],
+    ) -> UnivariateFriCommitment<F, H> {
+        let k = evals.len().ilog2() as usize;
+        let n_inv = F::TWO_INV.pow_vartime([k as u64]);
+        let mut coeffs = evals.to_vec();
+        radix2_fft(&mut coeffs, root_of_unity_inv(k), k);
+        parallelize(&mut coeffs, |(coeffs, _)| {
+            coeffs.iter_mut().for_each(|coeff| *coeff *= n_inv)
+        });
+        Self::commit_monomial(pp, &coeffs)
+    }
+
+    /// Proves `evals` of polynomials committed in `comms` by FRI on the
+    /// batched DEEP quotient
+    /// `Q(X) = Σ_i γ^i * (f_{poly_i}(X) - v_i) / (X - z_{point_i})`, whose
+    /// codeword is derived from the committed ones, so only codewords of the
+    /// folded polynomials are committed.
+    ///
+    /// Since FRI with `k` rounds only bounds degree by `2^k - 1` but `Q` should
+    /// have degree at most `2^k - 2`, FRI is done on `Q(X) * (1 + r * X)`
+    /// instead as degree correction, otherwise `f_{poly_i}` of degree `2^k`
+    /// would also be accepted.
+    pub(crate) fn batch_open_committed(
+        pp: &UnivariateFriParam,
+        comms: &[&UnivariateFriCommitment<F, H>],
+        points: &[F],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptWrite<Output<H>, F>,
+    ) -> Result<(), Error> {
+        let log_n = pp.k + pp.log_rate;
+        let indices = evals.iter().map(Evaluation::poly).unique().collect_vec();
+
+        let gamma = transcript.squeeze_challenge();
+        let r = transcript.squeeze_challenge();
+
+        let timer = start_timer(|| format!("fri_quotient-{}", evals.len()));
+        let quotient = {
+            let omega = root_of_unity::<F>(log_n);
+            let mut domain = vec![F::ZERO; 1 << log_n];
+            parallelize(&mut domain, |(domain, start)| {
+                izip!(domain, powers(omega.pow_vartime([start as u64])))
+                    .for_each(|(x, power_of_omega)| *x = power_of_omega);
+            });
+
+            let gammas = powers(gamma).take(evals.len()).collect_vec();
+            let mut quotient = vec![F::ZERO; 1 << log_n];
+            for (idx, point) in points.iter().enumerate() {
+                let evals = izip!(&gammas, evals)
+                    .filter(|(_, eval)| eval.point() == idx)
+                    .collect_vec();
+                if evals.is_empty() {
+                    continue;
+                }
+                parallelize(&mut quotient, |(quotient, start)| {
+                    let mut denom_invs = domain[start..start + quotient.len()]
+                        .iter()
+                        .map(|x| *x - point)
+                        .collect_vec();
+                    denom_invs.batch_invert();
+                    for (offset, (quotient, denom_inv)) in izip!(quotient, denom_invs).enumerate() {
+                        let numer = evals
+                            .iter()
+                            .map(|(gamma, eval)| {
+                                let value = comms[eval.poly()].codeword()[start + offset];
+                                **gamma * (value - eval.value())
+                            })
+                            .sum::<F>();
+                        *quotient += numer * denom_inv;
+                    }
+                });
+            }
+            parallelize(&mut quotient, |(quotient, start)| {
+                izip!(quotient, &domain[start..])
+                    .for_each(|(quotient, x)| *quotient *= F::ONE + r * x);
+            });
+            quotient
+        };
+        end_timer(timer);
+
+        let timer = start_timer(|| format!("fri_commit_phase-{}", pp.k));
+        let mut oracles = Vec::<UnivariateFriCommitment<F, H>>::with_capacity(pp.k);
+        for round in 0..pp.k {
+            let beta = transcript.squeeze_challenge();
+            let prev = oracles
+                .last()
+                .map(UnivariateFriCommitment::codeword)
+                .unwrap_or(&quotient);
+            let folded = fold_codeword(log_n - round, prev, &beta);
+
+            if round + 1 < pp.k {
+                let oracle = UnivariateFriCommitment::new(folded);
+                transcript.write_commitment(oracle.root())?;
+                oracles.push(oracle);
+            } else {
+                if cfg!(feature = "sanity-check") {
+                    assert!(folded.iter().all_equal());
+                }
+                transcript.write_field_element(&folded[0])?;
+            }
+        }
+        end_timer(timer);
+
+        let timer = start_timer(|| format!("fri_query_phase-{}", pp.num_queries()));
+        let half = pp.codeword_len() >> 1;
+        for _ in 0..pp.num_queries() {
+            let idx = squeeze_challenge_idx(transcript, half);
+            for poly in indices.iter() {
+                comms[*poly].write_opening(idx, transcript)?;
+            }
+            for oracle in oracles.iter() {
+                oracle.write_opening(idx % (oracle.codeword().len() >> 1), transcript)?;
+            }
+        }
+        end_timer(timer);
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnivariateFriParam {
+    k: usize,
+    log_rate: usize,
+    num_queries: usize,
+}
+
+impl UnivariateFriParam {
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn degree(&self) -> usize {
+        (1 << self.k) - 1
+    }
+
+    pub fn log_rate(&self) -> usize {
+        self.log_rate
+    }
+
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    pub fn codeword_len(&self) -> usize {
+        1 << (self.k + self.log_rate)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct UnivariateFriCommitment<F, H: Hash> {
+    codeword: Vec<F>,
+    tree: MerkleTree<H>,
+}
+
+impl<F: PrimeField, H: Hash> UnivariateFriCommitment<F, H> {
+    /// Merklizes `codeword` with leaves being hashes of pairs
+    /// `(codeword[j], codeword[j + n/2])`, which are evaluations on `x` and
+    /// `-x` and always opened together.
+    fn new(codeword: Vec<F>) -> Self {
+        let (lo, hi) = codeword.split_at(codeword.len() >> 1);
+        let leaves = par_map_collect(0..lo.len(), |idx| hash_pair::<F, H>(&lo[idx], &hi[idx]));
+        let tree = MerkleTree::new(leaves);
+        Self { codeword, tree }
+    }
+
+    pub(crate) fn from_root(root: Output<H>) -> Self {
+        Self {
+            codeword: Vec::new(),
+            tree: MerkleTree::from_root(root),
+        }
+    }
+
+    pub fn codeword(&self) -> &[F] {
+        &self.codeword
+    }
+
+    pub fn tree(&self) -> &MerkleTree<H> {
+        &self.tree
+    }
+
+    pub fn root(&self) -> &Output<H> {
+        self.tree.root()
+    }
+
+    fn write_opening(
+        &self,
+        leaf: usize,
+        transcript: &mut impl TranscriptWrite<Output<H>, F>,
+    ) -> Result<(), Error> {
+        let half = self.codeword.len() >> 1;
+        transcript.write_field_elements([&self.codeword[leaf], &self.codeword[leaf + half]])?;
+        self.tree.write_path(leaf, transcript)
+    }
+}
+
+impl<F: PrimeField, H: Hash> AsRef<[Output<H>]> for UnivariateFriCommitment<F, H> {
+    fn as_ref(&self) -> &[Output<H>] {
+        slice::from_ref(self.tree.root())
+    }
+}
+
+impl<F, H> PolynomialCommitmentScheme<F> for UnivariateFri<F, H>
+where
+    F: PrimeField + Serialize + DeserializeOwned,
+    H: Hash,
+{
+    type Param = UnivariateFriParam;
+    type ProverParam = UnivariateFriParam;
+    type VerifierParam = UnivariateFriParam;
+    type Polynomial = UnivariatePolynomial<F>;
+    type Commitment = UnivariateFriCommitment<F, H>;
+    type CommitmentChunk = Output<H>;
+
+    fn setup(poly_size: usize, _: usize, _: impl RngCore) -> Result<Self::Param, Error> {
+        assert!(poly_size.is_power_of_two());
+        assert!(poly_size.ilog2() as usize + LOG_RATE <= F::S as usize);
+
+        Ok(UnivariateFriParam {
+            k: poly_size.ilog2() as usize,
+            log_rate: LOG_RATE,
+            num_queries: NUM_QUERIES,
+        })
+    }
+
+    fn trim(
+        param: &Self::Param,
+        poly_size: usize,
+        _: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        assert!(poly_size.is_power_of_two());
+
+        if param.degree() + 1 < poly_size {
+            return Err(err_too_large_deree("trim", param.degree(), poly_size - 1));
+        }
+
+        let param = UnivariateFriParam {
+            k: poly_size.ilog2() as usize,
+            log_rate: param.log_rate,
+            num_queries: param.num_queries,
+        };
+        Ok((param.clone(), param))
+    }
+
+    fn commit(pp: &Self::ProverParam, poly: &Self::Polynomial) -> Result<Self::Commitment, Error> {
+        validate_input("commit", pp.degree(), [poly])?;
+
+        match poly.basis() {
+            Monomial => Ok(Self::commit_monomial(pp, poly.coeffs())),
+            Lagrange => Ok(Self::commit_lagrange(pp, poly.coeffs())),
+        }
+    }
+
+    fn batch_commit<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        polys
+            .into_iter()
+            .map(|poly| Self::commit(pp, poly))
+            .collect()
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        validate_input("open", pp.degree(), [poly])?;
+
+        if cfg!(feature = "sanity-check") {
+            assert_eq!(Self::commit(pp, poly).unwrap().root(), comm.root());
+            assert_eq!(poly.evaluate(point), *eval);
+        }
+
+        let evals = [Evaluation::new(0, 0, *eval)];
+        Self::batch_open_committed(pp, &[comm], slice::from_ref(point), &evals, transcript)
+    }
+
+    fn batch_open<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let polys = polys.into_iter().collect_vec();
+        let comms = comms.into_iter().collect_vec();
+        validate_input("batch open", pp.degree(), polys.clone())?;
+
+        if cfg!(feature = "sanity-check") {
+            for eval in evals {
+                let (poly, point) = (&polys[eval.poly()], &points[eval.point()]);
+                assert_eq!(poly.evaluate(point), *eval.value());
+            }
+        }
+
+        Self::batch_open_committed(pp, &comms, points, evals, transcript)
+    }
+
+    fn read_commitments(
+        _: &Self::VerifierParam,
+        num_polys: usize,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        transcript.read_commitments(num_polys).map(|roots| {
+            roots
+                .into_iter()
+                .map(UnivariateFriCommitment::from_root)
+                .collect_vec()
+        })
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let evals = [Evaluation::new(0, 0, *eval)];
+        Self::batch_verify(vp, [comm], slice::from_ref(point), &evals, transcript)
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let comms = comms.into_iter().collect_vec();
+        let log_n = vp.k + vp.log_rate;
+        let indices = evals.iter().map(Evaluation::poly).unique().collect_vec();
+
+        let gammas = powers(transcript.squeeze_challenge())
+            .take(evals.len())
+            .collect_vec();
+        let r = transcript.squeeze_challenge();
+
+        let mut betas = Vec::with_capacity(vp.k);
+        let mut roots = Vec::with_capacity(vp.k);
+        for round in 0..vp.k {
+            betas.push(transcript.squeeze_challenge());
+            if round + 1 < vp.k {
+                roots.push(transcript.read_commitment()?);
+            }
+        }
+        let final_value = transcript.read_field_element()?;
+
+        let omega = root_of_unity::<F>(log_n);
+        let omega_invs = squares(root_of_unity_inv::<F>(log_n))
+            .take(vp.k)
+            .collect_vec();
+        let half = vp.codeword_len() >> 1;
+        let depth = log_n - 1;
+        for _ in 0..vp.num_queries() {
+            let idx = squeeze_challenge_idx(transcript, half);
+            let openings = indices
+                .iter()
+                .map(|poly| {
+                    let opening =
+                        read_opening::<F, H>(comms[*poly].root(), depth, idx, transcript)?;
+                    Ok((*poly, opening))
+                })
+                .try_collect::<_, HashMap<_, _>, Error>()?;
+
+            let (mut lo, mut hi) = {
+                let x = omega.pow_vartime([idx as u64]);
+                let mut denom_invs = points
+                    .iter()
+                    .flat_map(|point| [x - point, -x - point])
+                    .collect_vec();
+                denom_invs.batch_invert();
+                let (lo, hi) =
+                    izip!(&gammas, evals).fold((F::ZERO, F::ZERO), |(lo, hi), (gamma, eval)| {
+                        let (f_lo, f_hi) = openings[&eval.poly()];
+                        let denom_invs = &denom_invs[2 * eval.point()..];
+                        (
+                            lo + *gamma * (f_lo - eval.value()) * denom_invs[0],
+                            hi + *gamma * (f_hi - eval.value()) * denom_invs[1],
+                        )
+                    });
+                (lo * (F::ONE + r * x), hi * (F::ONE - r * x))
+            };
+
+            for (round, (beta, omega_inv)) in izip!(&betas, &omega_invs).enumerate() {
+                let half = half >> round;
+                let leaf = idx % half;
+                let folded = fold(&lo, &hi, &omega_inv.pow_vartime([leaf as u64]), beta);
+                let expected = if round + 1 < vp.k {
+                    let next_half = half >> 1;
+                    (lo, hi) = read_opening::<F, H>(
+                        &roots[round],
+                        depth - round - 1,
+                        leaf % next_half,
+                        transcript,
+                    )?;
+                    if leaf < next_half {
+                        lo
+                    } else {
+                        hi
+                    }
+                } else {
+                    final_value
+                };
+                if folded != expected {
+                    return Err(Error::InvalidPcsOpen("Folding failure".to_string()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns codeword of `f_e(X) + β * f_o(X)` on subgroup of order `2^(log_n - 1)`
+/// from codeword of `f(X) = f_e(X^2) + X * f_o(X^2)` on subgroup of order
+/// `2^log_n`.
+fn fold_codeword<F: PrimeField>(log_n: usize, codeword: &[F], beta: &F) -> Vec<F> {
+    let (lo, hi) = codeword.split_at(codeword.len() >> 1);
+    let omega_inv = root_of_unity_inv::<F>(log_n);
+    let mut folded = vec![F::ZERO; lo.len()];
+    parallelize(&mut folded, |(folded, start)| {
+        let x_invs = powers(omega_inv.pow_vartime([start as u64]));
+        izip!(folded, &lo[start..], &hi[start..], x_invs)
+            .for_each(|(folded, lo, hi, x_inv)| *folded = fold(lo, hi, &x_inv, beta));
+    });
+    folded
+}
+
+/// Returns `f_e(x^2) + β * f_o(x^2)` from `(f(x), f(-x))`.
+fn fold<F: PrimeField>(lo: &F, hi: &F, x_inv: &F, beta: &F) -> F {
+    (*lo + hi + (*lo - hi) * x_inv * beta) * F::TWO_INV
+}
+
+fn hash_pair<F: PrimeField, H: Hash>(lo: &F, hi: &F) -> Output<H> {
+    let mut hasher = H::new();
+    hasher.update_field_element(lo);
+    hasher.update_field_element(hi);
+    hasher.finalize_fixed()
+}
+
+fn read_opening<F: PrimeField, H: Hash>(
+    root: &Output<H>,
+    depth: usize,
+    leaf: usize,
+    transcript: &mut impl TranscriptRead<Output<H>, F>,
+) -> Result<(F, F), Error> {
+    let pair = transcript.read_field_elements(2)?;
+    let leaf_hash = hash_pair::<F, H>(&pair[0], &pair[1]);
+    MerkleTree::<H>::read_and_verify_path(root, depth, leaf, leaf_hash, transcript)?;
+    Ok((pair[0], pair[1]))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        pcs::{
+            test::{run_batch_commit_open_verify, run_commit_open_verify},
+            univariate::fri::UnivariateFri,
+        },
+        util::{hash::Keccak256, transcript::Keccak256Transcript},
+    };
+    use halo2_curves::bn256::Fr;
+
+    type Pcs = UnivariateFri<Fr, Keccak256>;
+
+    #[test]
+    fn commit_open_verify() {
+        run_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify() {
+        run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+}
@@ -3,8 +3,8 @@ use crate::{
         univariate::{
             additive, err_too_large_deree,
             ipa::{
-                UnivariateIpa, UnivariateIpaCommitment, UnivariateIpaParam,
-                UnivariateIpaVerifierParam,
+                verify_bulletproof_reduction_msm, UnivariateIpa, UnivariateIpaCommitment,
+                UnivariateIpaParam, UnivariateIpaVerifierParam,
             },
             validate_input,
         },
@@ -19,7 +19,7 @@ use crate::{
         chain, izip,
         parallel::parallelize,
         transcript::{TranscriptRead, TranscriptWrite},
-        Deserialize, DeserializeOwned, Itertools, Serialize,
+        Deserialize, DeserializeOwned, Either, Itertools, Serialize,
     },
     Error,
 };
@@ -31,7 +31,7 @@ pub struct UnivariateHyrax<C: CurveAffine>(PhantomData<C>);
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UnivariateHyraxParam<C: CurveAffine> {
-    k: usize,
+    poly_size: usize,
     batch_k: usize,
     row_k: usize,
     ipa: UnivariateIpaParam<C>,
@@ -39,11 +39,11 @@ pub struct UnivariateHyraxParam<C: CurveAffine> {
 
 impl<C: CurveAffine> UnivariateHyraxParam<C> {
     pub fn k(&self) -> usize {
-        self.k
+        self.poly_size.next_power_of_two().ilog2() as usize
     }
 
     pub fn degree(&self) -> usize {
-        (1 << self.k) - 1
+        self.poly_size - 1
     }
 
     pub fn batch_k(&self) -> usize {
@@ -59,7 +59,7 @@ impl<C: CurveAffine> UnivariateHyraxParam<C> {
     }
 
     pub fn num_chunks(&self) -> usize {
-        1 << (self.k - self.row_k)
+        div_ceil(self.poly_size, self.row_len())
     }
 
     pub fn monomial(&self) -> &[C] {
@@ -77,7 +77,7 @@ impl<C: CurveAffine> UnivariateHyraxParam<C> {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UnivariateHyraxVerifierParam<C: CurveAffine> {
-    k: usize,
+    poly_size: usize,
     batch_k: usize,
     row_k: usize,
     ipa: UnivariateIpaVerifierParam<C>,
@@ -85,7 +85,7 @@ pub struct UnivariateHyraxVerifierParam<C: CurveAffine> {
 
 impl<C: CurveAffine> UnivariateHyraxVerifierParam<C> {
     pub fn k(&self) -> usize {
-        self.k
+        self.poly_size.next_power_of_two().ilog2() as usize
     }
 
     pub fn row_k(&self) -> usize {
@@ -93,7 +93,7 @@ impl<C: CurveAffine> UnivariateHyraxVerifierParam<C> {
     }
 
     pub fn num_chunks(&self) -> usize {
-        1 << (self.k - self.row_k)
+        div_ceil(self.poly_size, 1 << self.row_k)
     }
 }
 
@@ -152,18 +152,15 @@ where
     type CommitmentChunk = C;
 
     fn setup(poly_size: usize, batch_size: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
-        // TODO: Support arbitrary degree.
-        assert!(poly_size.is_power_of_two());
         assert!(batch_size > 0 && batch_size <= poly_size);
 
-        let k = poly_size.ilog2() as usize;
         let batch_k = (poly_size * batch_size).next_power_of_two().ilog2() as usize;
         let row_k = div_ceil(batch_k, 2);
 
         let ipa = UnivariateIpa::setup(1 << row_k, 0, rng)?;
 
         Ok(Self::Param {
-            k,
+            poly_size,
             batch_k,
             row_k,
             ipa,
@@ -175,10 +172,8 @@ where
         poly_size: usize,
         batch_size: usize,
     ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
-        assert!(poly_size.is_power_of_two());
         assert!(batch_size > 0 && batch_size <= poly_size);
 
-        let k = poly_size.ilog2() as usize;
         let batch_k = (poly_size * batch_size).next_power_of_two().ilog2() as usize;
         let row_k = div_ceil(batch_k, 2);
         if param.row_k() < row_k {
@@ -188,13 +183,13 @@ where
         let (ipa_pp, ipa_vp) = UnivariateIpa::trim(&param.ipa, 1 << row_k, 0)?;
 
         let pp = Self::ProverParam {
-            k,
+            poly_size,
             batch_k,
             row_k,
             ipa: ipa_pp,
         };
         let vp = Self::VerifierParam {
-            k,
+            poly_size,
             batch_k,
             row_k,
             ipa: ipa_vp,
@@ -216,7 +211,8 @@ where
             let mut comm = vec![C::CurveExt::identity(); pp.num_chunks()];
             parallelize(&mut comm, |(comm, start)| {
                 for (comm, offset) in comm.iter_mut().zip((start * row_len..).step_by(row_len)) {
-                    let row = &scalars[offset..(offset + row_len).min(scalars.len())];
+                    let row =
+                        &scalars[offset.min(scalars.len())..(offset + row_len).min(scalars.len())];
                     *comm = variable_base_msm(row, &bases[..row.len()]);
                 }
             });
@@ -349,20 +345,7 @@ where
         eval: &C::Scalar,
         transcript: &mut impl TranscriptRead<C, C::Scalar>,
     ) -> Result<(), Error> {
-        assert_eq!(comm.0.len(), vp.num_chunks());
-
-        let comm = {
-            UnivariateIpaCommitment(if vp.num_chunks() == 1 {
-                comm.0[0]
-            } else {
-                let scalars = powers(squares(*point).nth(vp.row_k()).unwrap())
-                    .take(vp.num_chunks())
-                    .collect_vec();
-                variable_base_msm(&scalars, &comm.0).into()
-            })
-        };
-
-        UnivariateIpa::verify(&vp.ipa, &comm, point, eval, transcript)
+        verify_msm(vp, &[C::Scalar::ONE], &[comm], point, eval, transcript)
     }
 
     fn batch_verify<'a>(
@@ -373,16 +356,99 @@ where
         transcript: &mut impl TranscriptRead<C, C::Scalar>,
     ) -> Result<(), Error> {
         let comms = comms.into_iter().collect_vec();
-        additive::batch_verify::<_, Self>(vp, comms, points, evals, transcript)
+        let (scalars, q_comm, z, eval) = additive::batch_verify_reduction::<_, Self>(
+            vp,
+            comms.len(),
+            points,
+            evals,
+            transcript,
+        )?;
+        let comms = chain![comms, [&q_comm]].collect_vec();
+        verify_msm(vp, &scalars, &comms, &z, &eval, transcript)
     }
 }
 
+#[cfg(feature = "benchmark")]
+impl<C> UnivariateHyrax<C>
+where
+    C: CurveAffine + Serialize + DeserializeOwned,
+    C::ScalarExt: Serialize + DeserializeOwned,
+{
+    /// Same as [`PolynomialCommitmentScheme::batch_verify`] but combines
+    /// commitments per chunk and then rows by separate MSMs before verifying
+    /// IPA, which is only kept to benchmark against the merged MSM.
+    pub fn batch_verify_per_chunk<'a>(
+        vp: &UnivariateHyraxVerifierParam<C>,
+        comms: impl IntoIterator<Item = &'a UnivariateHyraxCommitment<C>>,
+        points: &[C::Scalar],
+        evals: &[Evaluation<C::Scalar>],
+        transcript: &mut impl TranscriptRead<C, C::Scalar>,
+    ) -> Result<(), Error> {
+        let comms = comms.into_iter().collect_vec();
+        let (scalars, q_comm, z, eval) = additive::batch_verify_reduction::<_, Self>(
+            vp,
+            comms.len(),
+            points,
+            evals,
+            transcript,
+        )?;
+        let f_comm = UnivariateHyraxCommitment::msm(&scalars, chain![comms, [&q_comm]]);
+        let comm = UnivariateIpaCommitment(if vp.num_chunks() == 1 {
+            f_comm.0[0]
+        } else {
+            let scalars = powers(squares(z).nth(vp.row_k()).unwrap())
+                .take(vp.num_chunks())
+                .collect_vec();
+            variable_base_msm(&scalars, &f_comm.0).into()
+        });
+        UnivariateIpa::verify(&vp.ipa, &comm, &z, &eval, transcript)
+    }
+}
+
+/// Verifies opening of linear combination of `comms` by `scalars`, where all
+/// MSMs, including the ones combining commitments and rows, are merged into
+/// the single MSM of IPA verifier.
+fn verify_msm<C: CurveAffine>(
+    vp: &UnivariateHyraxVerifierParam<C>,
+    scalars: &[C::Scalar],
+    comms: &[&UnivariateHyraxCommitment<C>],
+    point: &C::Scalar,
+    eval: &C::Scalar,
+    transcript: &mut impl TranscriptRead<C, C::Scalar>,
+) -> Result<(), Error> {
+    let row_scalars = powers(squares(*point).nth(vp.row_k()).unwrap())
+        .take(vp.num_chunks())
+        .collect_vec();
+    let (comm_scalars, comm_bases) = izip!(scalars, comms)
+        .filter(|(_, comm)| !comm.0.is_empty())
+        .flat_map(|(scalar, comm)| {
+            assert_eq!(comm.0.len(), vp.num_chunks());
+            izip!(&row_scalars, &comm.0).map(move |(row_scalar, base)| (*scalar * row_scalar, base))
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    verify_bulletproof_reduction_msm(
+        vp.ipa.monomial(),
+        vp.ipa.h(),
+        &comm_scalars,
+        &comm_bases,
+        Either::Left(point),
+        eval,
+        transcript,
+    )
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
         pcs::{
             test::{run_batch_commit_open_verify, run_commit_open_verify},
-            univariate::hyrax::UnivariateHyrax,
+            univariate::{
+                hyrax::UnivariateHyrax,
+                test::{
+                    run_batch_commit_open_verify_arbitrary_degree,
+                    run_commit_open_verify_arbitrary_degree,
+                },
+            },
         },
         util::transcript::Keccak256Transcript,
     };
@@ -399,4 +465,14 @@ mod test {
     fn batch_commit_open_verify() {
         run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
     }
+
+    #[test]
+    fn commit_open_verify_arbitrary_degree() {
+        run_commit_open_verify_arbitrary_degree::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify_arbitrary_degree() {
+        run_batch_commit_open_verify_arbitrary_degree::<_, Pcs, Keccak256Transcript<_>>();
+    }
 }
@@ -128,14 +128,13 @@ where
     type CommitmentChunk = C;
 
     fn setup(poly_size: usize, _: usize, _: impl RngCore) -> Result<Self::Param, Error> {
-        // TODO: Support arbitrary degree.
-        assert!(poly_size.is_power_of_two());
-        assert!(poly_size.ilog2() <= C::Scalar::S);
-
-        let k = poly_size.ilog2() as usize;
+        // Bulletproof reduction halves bases in each round, so bases are always
+        // generated for the next power-of-two size.
+        let k = poly_size.next_power_of_two().ilog2() as usize;
+        assert!(k <= C::Scalar::S as usize);
 
         let monomial = {
-            let mut g = vec![C::Curve::identity(); poly_size];
+            let mut g = vec![C::Curve::identity(); 1 << k];
             parallelize(&mut g, |(g, start)| {
                 let hasher = C::CurveExt::hash_to_curve("UnivariateIpa::setup");
                 for (g, idx) in g.iter_mut().zip(start as u32..) {
@@ -165,16 +164,14 @@ where
         poly_size: usize,
         _: usize,
     ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
-        assert!(poly_size.is_power_of_two());
-
-        let k = poly_size.ilog2() as usize;
+        let k = poly_size.next_power_of_two().ilog2() as usize;
 
-        if param.monomial.len() < poly_size {
+        if param.monomial.len() < 1 << k {
             return Err(err_too_large_deree("trim", param.degree(), poly_size - 1));
         }
 
-        let monomial = param.monomial[..poly_size].to_vec();
-        let lagrange = if param.lagrange.len() == poly_size {
+        let monomial = param.monomial[..1 << k].to_vec();
+        let lagrange = if param.lagrange.len() == 1 << k {
             param.lagrange.clone()
         } else {
             monomial_g_to_lagrange_g(&monomial)
@@ -358,6 +355,32 @@ pub(crate) fn verify_bulletproof_reduction<C: CurveAffine>(
     eval: &C::Scalar,
     transcript: &mut impl TranscriptRead<C, C::Scalar>,
 ) -> Result<(), Error> {
+    let comm_scalars = [C::Scalar::ONE];
+    let comm_bases = [comm.as_ref()];
+    verify_bulletproof_reduction_msm(
+        bases,
+        h,
+        &comm_scalars,
+        &comm_bases,
+        point,
+        eval,
+        transcript,
+    )
+}
+
+/// Same as [`verify_bulletproof_reduction`] but with commitment given as linear
+/// combination of `comm_bases` by `comm_scalars`, which is merged into the
+/// final MSM of the verifier.
+pub(crate) fn verify_bulletproof_reduction_msm<C: CurveAffine>(
+    bases: &[C],
+    h: &C,
+    comm_scalars: &[C::Scalar],
+    comm_bases: &[&C],
+    point: Either<&C::Scalar, &[C::Scalar]>,
+    eval: &C::Scalar,
+    transcript: &mut impl TranscriptRead<C, C::Scalar>,
+) -> Result<(), Error> {
+    assert_eq!(comm_scalars.len(), comm_bases.len());
     assert!(bases.len().is_power_of_two());
     if let Either::Right(point) = point {
         assert_eq!(1 << point.len(), bases.len());
@@ -391,9 +414,9 @@ pub(crate) fn verify_bulletproof_reduction<C: CurveAffine>(
         Either::Right(point) => ("multivariate", multilinear::evaluate(&neg_c_h, point)),
     };
     let u = xi_0 * (neg_c_h_eval + eval);
-    let scalars = chain![&xi_invs, &xis, &neg_c_h, [&u]];
-    let bases = chain![&ls, &rs, bases, [h]];
-    bool::from((variable_base_msm(scalars, bases) + comm.as_ref()).is_identity())
+    let scalars = chain![&xi_invs, &xis, &neg_c_h, [&u], comm_scalars];
+    let bases = chain![&ls, &rs, bases, [h], comm_bases.iter().copied()];
+    bool::from(variable_base_msm(scalars, bases).is_identity())
         .then_some(())
         .ok_or_else(|| Error::InvalidPcsOpen(format!("Invalid {kind} IPA open")))
 }
@@ -429,7 +452,13 @@ mod test {
     use crate::{
         pcs::{
             test::{run_batch_commit_open_verify, run_commit_open_verify},
-            univariate::ipa::UnivariateIpa,
+            univariate::{
+                ipa::UnivariateIpa,
+                test::{
+                    run_batch_commit_open_verify_arbitrary_degree, run_commit_lagrange,
+                    run_commit_open_verify_arbitrary_degree,
+                },
+            },
         },
         util::transcript::Keccak256Transcript,
     };
@@ -446,4 +475,21 @@ mod test {
     fn batch_commit_open_verify() {
         run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
     }
+
+    #[test]
+    fn commit_open_verify_arbitrary_degree() {
+        run_commit_open_verify_arbitrary_degree::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify_arbitrary_degree() {
+        run_batch_commit_open_verify_arbitrary_degree::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn commit_lagrange_arbitrary_degree() {
+        run_commit_lagrange::<_, Pcs>(1001, 1001, 1024);
+        run_commit_lagrange::<_, Pcs>((3 << 10) + 1, 1001, 1024);
+        run_commit_lagrange::<_, Pcs>((3 << 10) + 1, 3 << 10, 4096);
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     pcs::{
         univariate::{additive, err_too_large_deree, monomial_g_to_lagrange_g, validate_input},
-        Additive, Evaluation, Point, PolynomialCommitmentScheme,
+        Additive, DeferredKzg, Evaluation, KzgAccumulator, Point, PolynomialCommitmentScheme,
     },
     poly::univariate::{UnivariateBasis::*, UnivariatePolynomial},
     util::{
@@ -16,12 +16,76 @@ use crate::{
     Error,
 };
 use rand::RngCore;
-use std::{marker::PhantomData, ops::Neg, slice};
+use std::{fmt::Debug, marker::PhantomData, ops::Neg, slice};
 
+mod shplonk;
+
+pub use shplonk::ShplonkBatch;
+
+/// Univariate KZG with batch opening strategy `B`, which is [`AdditiveBatch`]
+/// by default.
+#[derive(Clone, Debug)]
+pub struct UnivariateKzg<M: MultiMillerLoop, B = AdditiveBatch>(PhantomData<(M, B)>);
+
+/// Strategy of [`UnivariateKzg`] to batch open polynomials on multiple points.
+pub trait KzgBatchOpening<M: MultiMillerLoop>: Clone + Debug {
+    fn batch_open(
+        pp: &UnivariateKzgProverParam<M>,
+        polys: Vec<&UnivariatePolynomial<M::Scalar>>,
+        comms: Vec<&UnivariateKzgCommitment<M::G1Affine>>,
+        points: &[M::Scalar],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptWrite<M::G1Affine, M::Scalar>,
+    ) -> Result<(), Error>;
+
+    fn batch_verify_deferred(
+        vp: &UnivariateKzgVerifierParam<M>,
+        comms: Vec<&UnivariateKzgCommitment<M::G1Affine>>,
+        points: &[M::Scalar],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<M::G1Affine, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error>;
+}
+
+/// Batch opening by reducing to a single opening of a linear combination of
+/// commitments, which is generic over additive univariate PCSs.
 #[derive(Clone, Debug)]
-pub struct UnivariateKzg<M: MultiMillerLoop>(PhantomData<M>);
+pub struct AdditiveBatch;
 
-impl<M: MultiMillerLoop> UnivariateKzg<M> {
+impl<M> KzgBatchOpening<M> for AdditiveBatch
+where
+    M: MultiMillerLoop,
+    M::Scalar: Serialize + DeserializeOwned,
+    M::G1Affine: Serialize + DeserializeOwned,
+    M::G2Affine: Serialize + DeserializeOwned,
+{
+    fn batch_open(
+        pp: &UnivariateKzgProverParam<M>,
+        polys: Vec<&UnivariatePolynomial<M::Scalar>>,
+        comms: Vec<&UnivariateKzgCommitment<M::G1Affine>>,
+        points: &[M::Scalar],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptWrite<M::G1Affine, M::Scalar>,
+    ) -> Result<(), Error> {
+        additive::batch_open::<_, UnivariateKzg<M, Self>>(
+            pp, polys, comms, points, evals, transcript,
+        )
+    }
+
+    fn batch_verify_deferred(
+        vp: &UnivariateKzgVerifierParam<M>,
+        comms: Vec<&UnivariateKzgCommitment<M::G1Affine>>,
+        points: &[M::Scalar],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<M::G1Affine, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
+        additive::batch_verify_deferred::<M, UnivariateKzg<M, Self>>(
+            vp, comms, points, evals, transcript,
+        )
+    }
+}
+
+impl<M: MultiMillerLoop, B> UnivariateKzg<M, B> {
     pub(crate) fn commit_monomial(
         pp: &UnivariateKzgProverParam<M>,
         coeffs: &[M::Scalar],
@@ -189,12 +253,13 @@ impl<C: CurveAffine> Additive<C::Scalar> for UnivariateKzgCommitment<C> {
     }
 }
 
-impl<M> PolynomialCommitmentScheme<M::Scalar> for UnivariateKzg<M>
+impl<M, B> PolynomialCommitmentScheme<M::Scalar> for UnivariateKzg<M, B>
 where
     M: MultiMillerLoop,
     M::Scalar: Serialize + DeserializeOwned,
     M::G1Affine: Serialize + DeserializeOwned,
     M::G2Affine: Serialize + DeserializeOwned,
+    B: KzgBatchOpening<M>,
 {
     type Param = UnivariateKzgParam<M>;
     type ProverParam = UnivariateKzgProverParam<M>;
@@ -204,21 +269,24 @@ where
     type CommitmentChunk = M::G1Affine;
 
     fn setup(poly_size: usize, _: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
-        // TODO: Support arbitrary degree.
-        assert!(poly_size.is_power_of_two());
-        assert!(poly_size.ilog2() <= M::Scalar::S);
+        // Lagrange bases are on the next power-of-two domain as other
+        // univariate PCSs, while monomial bases are only up to `poly_size`.
+        let k = poly_size.next_power_of_two().ilog2() as usize;
+        assert!(poly_size > 0 && k <= M::Scalar::S as usize);
 
         let s = M::Scalar::random(rng);
 
         let g1 = M::G1Affine::generator();
         let (monomial_g1, lagrange_g1) = {
-            let window_size = window_size(poly_size);
+            let window_size = window_size(1 << k);
             let window_table = window_table(window_size, g1);
-            let monomial = powers(s).take(poly_size).collect_vec();
-            let monomial_g1 =
-                batch_projective_to_affine(&fixed_base_msm(window_size, &window_table, &monomial));
+            let monomial = powers(s).take(1 << k).collect_vec();
+            let monomial_g1 = batch_projective_to_affine(&fixed_base_msm(
+                window_size,
+                &window_table,
+                &monomial[..poly_size],
+            ));
             let lagrange_g1 = {
-                let k = poly_size.ilog2() as usize;
                 let n_inv = M::Scalar::TWO_INV.pow_vartime([k as u64]);
                 let mut lagrange = monomial;
                 radix2_fft(&mut lagrange, root_of_unity_inv(k), k);
@@ -237,7 +305,7 @@ where
         };
 
         Ok(Self::Param {
-            k: poly_size.ilog2() as usize,
+            k,
             monomial_g1,
             lagrange_g1,
             powers_of_s_g2,
@@ -249,20 +317,20 @@ where
         poly_size: usize,
         _: usize,
     ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
-        assert!(poly_size.is_power_of_two());
-
         if param.monomial_g1.len() < poly_size {
             return Err(err_too_large_deree("trim", param.degree(), poly_size - 1));
         }
 
+        // Smaller domain than the one of `param` is always within `param.degree()`.
+        let k = poly_size.next_power_of_two().ilog2() as usize;
         let monomial_g1 = param.monomial_g1[..poly_size].to_vec();
-        let lagrange_g1 = if param.lagrange_g1.len() == poly_size {
+        let lagrange_g1 = if param.lagrange_g1.len() == 1 << k {
             param.lagrange_g1.clone()
         } else {
-            monomial_g_to_lagrange_g(&monomial_g1)
+            monomial_g_to_lagrange_g(&param.monomial_g1[..1 << k])
         };
 
-        let pp = Self::ProverParam::new(poly_size.ilog2() as usize, monomial_g1, lagrange_g1);
+        let pp = Self::ProverParam::new(k, monomial_g1, lagrange_g1);
         let vp = Self::VerifierParam {
             g1: param.g1(),
             g2: param.g2(),
@@ -334,7 +402,7 @@ where
         let polys = polys.into_iter().collect_vec();
         let comms = comms.into_iter().collect_vec();
         validate_input("batch open", pp.degree(), polys.clone())?;
-        additive::batch_open::<_, Self>(pp, polys, comms, points, evals, transcript)
+        B::batch_open(pp, polys, comms, points, evals, transcript)
     }
 
     fn read_commitments(
@@ -353,11 +421,8 @@ where
         eval: &M::Scalar,
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
     ) -> Result<(), Error> {
-        let pi = transcript.read_commitment()?;
-        let c = (pi * point + comm.0 - vp.g1 * eval).into();
-        M::pairings_product_is_identity(&[(&c, &(-vp.g2).into()), (&pi, &vp.s_g2.into())])
-            .then_some(())
-            .ok_or_else(|| Error::InvalidPcsOpen("Invalid univariate KZG open".to_string()))
+        let accumulator = Self::verify_deferred(vp, comm, point, eval, transcript)?;
+        Self::decide(vp, &accumulator)
     }
 
     fn batch_verify<'a>(
@@ -367,8 +432,44 @@ where
         evals: &[Evaluation<M::Scalar>],
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
     ) -> Result<(), Error> {
+        let accumulator = Self::batch_verify_deferred(vp, comms, points, evals, transcript)?;
+        Self::decide(vp, &accumulator)
+    }
+}
+
+impl<M, B> DeferredKzg<M> for UnivariateKzg<M, B>
+where
+    M: MultiMillerLoop,
+    M::Scalar: Serialize + DeserializeOwned,
+    M::G1Affine: Serialize + DeserializeOwned,
+    M::G2Affine: Serialize + DeserializeOwned,
+    B: KzgBatchOpening<M>,
+{
+    fn accumulator_g2s(vp: &Self::VerifierParam) -> Vec<M::G2Affine> {
+        vec![vp.g2, vp.s_g2]
+    }
+
+    fn verify_deferred(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<M::Scalar, Self::Polynomial>,
+        eval: &M::Scalar,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
+        let pi = transcript.read_commitment()?;
+        let c = (pi * point + comm.0 - vp.g1 * eval).into();
+        Ok(KzgAccumulator::new(c, vec![pi]))
+    }
+
+    fn batch_verify_deferred<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<M::Scalar, Self::Polynomial>],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
         let comms = comms.into_iter().collect_vec();
-        additive::batch_verify::<_, Self>(vp, comms, points, evals, transcript)
+        B::batch_verify_deferred(vp, comms, points, evals, transcript)
     }
 }
 
@@ -376,15 +477,32 @@ where
 mod test {
     use crate::{
         pcs::{
-            test::{run_batch_commit_open_verify, run_commit_open_verify},
-            univariate::kzg::UnivariateKzg,
+            test::{
+                run_batch_commit_open_verify, run_commit_open_verify,
+                run_verify_deferred_fold_decide,
+            },
+            univariate::{
+                kzg::{ShplonkBatch, UnivariateKzg},
+                test::{
+                    run_batch_commit_open_verify_arbitrary_degree, run_commit_lagrange,
+                    run_commit_open_verify_arbitrary_degree,
+                },
+            },
+            Evaluation, PolynomialCommitmentScheme,
+        },
+        poly::{univariate::UnivariatePolynomial, Polynomial},
+        util::{
+            arithmetic::Field,
+            transcript::{InMemoryTranscript, Keccak256Transcript},
         },
-        util::transcript::Keccak256Transcript,
     };
-    use halo2_curves::bn256::Bn256;
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
 
     type Pcs = UnivariateKzg<Bn256>;
 
+    type ShplonkPcs = UnivariateKzg<Bn256, ShplonkBatch>;
+
     #[test]
     fn commit_open_verify() {
         run_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
@@ -394,4 +512,70 @@ mod test {
     fn batch_commit_open_verify() {
         run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
     }
+
+    #[test]
+    fn verify_deferred_fold_decide() {
+        run_verify_deferred_fold_decide::<Bn256, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn commit_open_verify_shplonk() {
+        run_commit_open_verify::<_, ShplonkPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_verify_shplonk_conflicting_evals() {
+        let (pp, vp) = {
+            let param = ShplonkPcs::setup(1 << 4, 1, OsRng).unwrap();
+            ShplonkPcs::trim(&param, 1 << 4, 1).unwrap()
+        };
+        let poly = UnivariatePolynomial::rand(1 << 4, OsRng);
+        let points = vec![Fr::random(OsRng)];
+        let eval = poly.evaluate(&points[0]);
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            let comm = ShplonkPcs::commit_and_write(&pp, &poly, &mut transcript).unwrap();
+            let evals = [Evaluation::new(0, 0, eval)];
+            ShplonkPcs::batch_open(&pp, [&poly], [&comm], &points, &evals, &mut transcript)
+                .unwrap();
+            transcript.into_proof()
+        };
+        let result = {
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            let comm = ShplonkPcs::read_commitment(&vp, &mut transcript).unwrap();
+            let evals = [
+                Evaluation::new(0, 0, eval),
+                Evaluation::new(0, 0, eval + Fr::ONE),
+            ];
+            ShplonkPcs::batch_verify(&vp, [&comm], &points, &evals, &mut transcript)
+        };
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn batch_commit_open_verify_shplonk() {
+        run_batch_commit_open_verify::<_, ShplonkPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn commit_open_verify_arbitrary_degree() {
+        run_commit_open_verify_arbitrary_degree::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify_arbitrary_degree() {
+        run_batch_commit_open_verify_arbitrary_degree::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify_shplonk_arbitrary_degree() {
+        run_batch_commit_open_verify_arbitrary_degree::<_, ShplonkPcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn commit_lagrange_arbitrary_degree() {
+        run_commit_lagrange::<_, Pcs>(1001, 1001, 1024);
+        run_commit_lagrange::<_, Pcs>((3 << 10) + 1, 1001, 1024);
+        run_commit_lagrange::<_, Pcs>((3 << 10) + 1, 3 << 10, 4096);
+    }
 }
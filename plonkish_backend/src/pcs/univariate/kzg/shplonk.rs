@@ -0,0 +1,168 @@
+use crate::{
+    pcs::{
+        univariate::{
+            additive::{eval_sets, vanishing_eval, EvaluationSet},
+            kzg::{
+                KzgBatchOpening, UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgProverParam,
+                UnivariateKzgVerifierParam,
+            },
+        },
+        Evaluation, KzgAccumulator,
+    },
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{fe_to_bytes, powers, variable_base_msm, Field, MultiMillerLoop},
+        chain, izip,
+        transcript::{TranscriptRead, TranscriptWrite},
+        Itertools,
+    },
+    Error,
+};
+use std::collections::BTreeSet;
+
+/// Batch opening of [BDFG20] (SHPLONK), which always has 2 commitments in
+/// proof no matter how many points are opened, and is verified by a single
+/// MSM and a single pairing check.
+///
+/// [BDFG20]: https://eprint.iacr.org/2020/081.pdf
+#[derive(Clone, Debug)]
+pub struct ShplonkBatch;
+
+impl<M: MultiMillerLoop> KzgBatchOpening<M> for ShplonkBatch {
+    fn batch_open(
+        pp: &UnivariateKzgProverParam<M>,
+        polys: Vec<&UnivariatePolynomial<M::Scalar>>,
+        _: Vec<&UnivariateKzgCommitment<M::G1Affine>>,
+        points: &[M::Scalar],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptWrite<M::G1Affine, M::Scalar>,
+    ) -> Result<(), Error> {
+        if cfg!(feature = "sanity-check") {
+            assert_eq!(
+                points.iter().map(fe_to_bytes::<M::Scalar>).unique().count(),
+                points.len()
+            );
+            for eval in evals {
+                let (poly, point) = (&polys[eval.poly()], &points[eval.point()]);
+                assert_eq!(poly.evaluate(point), *eval.value());
+            }
+        }
+
+        let (sets, superset) = eval_sets(evals)?;
+
+        let gamma = transcript.squeeze_challenge();
+        let powers_of_gamma = set_powers_of_gamma(&sets, gamma);
+
+        let h = izip!(&sets, &powers_of_gamma)
+            .flat_map(|(set, powers_of_gamma)| {
+                let vanishing_poly = set.vanishing_poly(points);
+                izip!(powers_of_gamma, &set.polys)
+                    .map(|(power_of_gamma, poly)| {
+                        (power_of_gamma, polys[*poly].div_rem(&vanishing_poly).0)
+                    })
+                    .collect_vec()
+            })
+            .sum::<UnivariatePolynomial<_>>();
+        let h_comm = UnivariateKzg::<M>::commit_monomial(pp, h.coeffs());
+        transcript.write_commitment(&h_comm.0)?;
+
+        let z = transcript.squeeze_challenge();
+
+        let (scalars, h_scalar) = set_scalars(&sets, &superset, &powers_of_gamma, points, &z);
+        let l = {
+            let mut l = izip!(&scalars, sets.iter().flat_map(|set| &set.polys))
+                .map(|(scalar, poly)| (scalar, polys[*poly]))
+                .sum::<UnivariatePolynomial<_>>();
+            l += (&h_scalar, &h);
+            l
+        };
+        let divisor = UnivariatePolynomial::monomial(vec![-z, M::Scalar::ONE]);
+        let (quotient, remainder) = l.div_rem(&divisor);
+
+        if cfg!(feature = "sanity-check") {
+            let eval = set_r_eval(&sets, &powers_of_gamma, points, &z);
+            assert_eq!(remainder.evaluate(&z), eval);
+        }
+
+        let quotient_comm = UnivariateKzg::<M>::commit_monomial(pp, quotient.coeffs());
+        transcript.write_commitment(&quotient_comm.0)?;
+
+        Ok(())
+    }
+
+    fn batch_verify_deferred(
+        vp: &UnivariateKzgVerifierParam<M>,
+        comms: Vec<&UnivariateKzgCommitment<M::G1Affine>>,
+        points: &[M::Scalar],
+        evals: &[Evaluation<M::Scalar>],
+        transcript: &mut impl TranscriptRead<M::G1Affine, M::Scalar>,
+    ) -> Result<KzgAccumulator<M::G1Affine>, Error> {
+        let (sets, superset) = eval_sets(evals)?;
+
+        let gamma = transcript.squeeze_challenge();
+        let h_comm = transcript.read_commitment()?;
+
+        let z = transcript.squeeze_challenge();
+        let quotient_comm = transcript.read_commitment()?;
+
+        let powers_of_gamma = set_powers_of_gamma(&sets, gamma);
+        let (scalars, h_scalar) = set_scalars(&sets, &superset, &powers_of_gamma, points, &z);
+        let neg_eval = -set_r_eval(&sets, &powers_of_gamma, points, &z);
+
+        let c = variable_base_msm(
+            chain![&scalars, [&h_scalar, &neg_eval, &z]],
+            chain![
+                sets.iter()
+                    .flat_map(|set| &set.polys)
+                    .map(|poly| &comms[*poly].0),
+                [&h_comm, &vp.g1(), &quotient_comm]
+            ],
+        )
+        .into();
+        Ok(KzgAccumulator::new(c, vec![quotient_comm]))
+    }
+}
+
+/// Returns consecutive powers of `γ` for polys of each set, so each opened poly
+/// has its own power of `γ`.
+fn set_powers_of_gamma<F: Field>(sets: &[EvaluationSet<F>], gamma: F) -> Vec<Vec<F>> {
+    let mut powers_of_gamma = powers(gamma);
+    sets.iter()
+        .map(|set| powers_of_gamma.by_ref().take(set.polys.len()).collect())
+        .collect()
+}
+
+/// Returns scalar `γ^i * Z_{T \ S_i}(z)` of each opened poly and scalar
+/// `-Z_T(z)` of `h`, where `T` is the superset.
+fn set_scalars<F: Field>(
+    sets: &[EvaluationSet<F>],
+    superset: &BTreeSet<usize>,
+    powers_of_gamma: &[Vec<F>],
+    points: &[F],
+    z: &F,
+) -> (Vec<F>, F) {
+    let scalars = izip!(sets, powers_of_gamma)
+        .flat_map(|(set, powers_of_gamma)| {
+            let vanishing_diff_eval = set.vanishing_diff_eval(points, z);
+            powers_of_gamma
+                .iter()
+                .map(move |power_of_gamma| *power_of_gamma * vanishing_diff_eval)
+        })
+        .collect_vec();
+    let h_scalar = -vanishing_eval(superset.iter().map(|idx| &points[*idx]), z);
+    (scalars, h_scalar)
+}
+
+/// Returns `Σ_i γ^i * Z_{T \ S_i}(z) * r_i(z)`.
+fn set_r_eval<F: Field>(
+    sets: &[EvaluationSet<F>],
+    powers_of_gamma: &[Vec<F>],
+    points: &[F],
+    z: &F,
+) -> F {
+    izip!(sets, powers_of_gamma)
+        .map(|(set, powers_of_gamma)| {
+            set.vanishing_diff_eval(points, z) * set.r_eval(points, z, powers_of_gamma)
+        })
+        .sum()
+}
@@ -1,3 +1,5 @@
 mod fractional_sum_check;
+mod grand_product;
 
 pub use fractional_sum_check::{prove_fractional_sum_check, verify_fractional_sum_check};
+pub use grand_product::{prove_grand_product, verify_grand_product};
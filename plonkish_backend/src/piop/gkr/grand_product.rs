@@ -0,0 +1,310 @@
+//! Implementation of GKR for grand products with layered binary product
+//! circuit, which is the same as the fractional sum-check in
+//! [`fractional_sum_check`] but with only the denominators.
+//!
+//! [`fractional_sum_check`]: crate::piop::gkr::fractional_sum_check
+
+use crate::{
+    piop::sum_check::{
+        classic::{ClassicSumCheck, EvaluationsProver},
+        evaluate, SumCheck as _, VirtualPolynomial,
+    },
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::{div_ceil, inner_product, powers, PrimeField},
+        expression::{Expression, Query, Rotation},
+        izip,
+        parallel::{num_threads, parallelize_iter},
+        transcript::{FieldTranscriptRead, FieldTranscriptWrite},
+        Itertools,
+    },
+    Error,
+};
+use std::{array, iter};
+
+type SumCheck<F> = ClassicSumCheck<EvaluationsProver<F>>;
+
+struct Layer<F> {
+    v_l: MultilinearPolynomial<F>,
+    v_r: MultilinearPolynomial<F>,
+}
+
+impl<F> From<[Vec<F>; 2]> for Layer<F> {
+    fn from(values: [Vec<F>; 2]) -> Self {
+        let [v_l, v_r] = values.map(MultilinearPolynomial::new);
+        Self { v_l, v_r }
+    }
+}
+
+impl<F: PrimeField> Layer<F> {
+    fn bottom(v: &&MultilinearPolynomial<F>) -> Self {
+        let mid = v.evals().len() >> 1;
+        [&v[..mid], &v[mid..]].map(ToOwned::to_owned).into()
+    }
+
+    fn num_vars(&self) -> usize {
+        self.v_l.num_vars()
+    }
+
+    fn polys(&self) -> [&MultilinearPolynomial<F>; 2] {
+        [&self.v_l, &self.v_r]
+    }
+
+    fn up(&self) -> Self {
+        assert!(self.num_vars() != 0);
+
+        let len = 1 << self.num_vars();
+        let chunk_size = div_ceil(len >> 1, num_threads()).next_power_of_two();
+
+        let mut outputs: [_; 2] = array::from_fn(|_| vec![F::ZERO; len >> 1]);
+        let [v_l, v_r] = self.polys().map(|poly| poly.evals().chunks(chunk_size));
+        parallelize_iter(
+            izip!(
+                outputs.iter_mut().flat_map(|v| v.chunks_mut(chunk_size)),
+                v_l,
+                v_r
+            ),
+            |(v, v_l, v_r)| {
+                izip!(v, v_l, v_r).for_each(|(v, v_l, v_r)| *v = *v_l * v_r);
+            },
+        );
+
+        outputs.into()
+    }
+}
+
+pub fn prove_grand_product<'a, F: PrimeField>(
+    claimed_v_0s: impl IntoIterator<Item = Option<F>>,
+    vs: impl IntoIterator<Item = &'a MultilinearPolynomial<F>>,
+    transcript: &mut impl FieldTranscriptWrite<F>,
+) -> Result<(Vec<F>, Vec<F>), Error> {
+    let claimed_v_0s = claimed_v_0s.into_iter().collect_vec();
+    let vs = vs.into_iter().collect_vec();
+    let num_batching = claimed_v_0s.len();
+
+    assert!(num_batching != 0);
+    assert_eq!(num_batching, vs.len());
+    for poly in &vs {
+        assert_eq!(poly.num_vars(), vs[0].num_vars());
+    }
+
+    let bottom_layers = vs.iter().map(Layer::bottom).collect_vec();
+    let layers = iter::successors(bottom_layers.into(), |layers| {
+        (layers[0].num_vars() > 0).then(|| layers.iter().map(Layer::up).collect())
+    })
+    .collect_vec();
+
+    let claimed_v_0s = {
+        let v_0s = layers
+            .last()
+            .unwrap()
+            .iter()
+            .map(|layer| layer.v_l[0] * layer.v_r[0])
+            .collect_vec();
+
+        izip!(claimed_v_0s, v_0s)
+            .map(|(claimed, computed)| match claimed {
+                Some(claimed) => {
+                    if cfg!(feature = "sanity-check") {
+                        assert_eq!(claimed, computed)
+                    }
+                    transcript.common_field_element(&computed).map(|_| computed)
+                }
+                None => transcript.write_field_element(&computed).map(|_| computed),
+            })
+            .try_collect::<_, Vec<_>, _>()?
+    };
+
+    let expression = sum_check_expression(num_batching);
+
+    let (v_xs, x) =
+        layers
+            .iter()
+            .rev()
+            .try_fold((claimed_v_0s, Vec::new()), |result, layers| {
+                let (claimed_v_ys, y) = result;
+
+                let num_vars = layers[0].num_vars();
+                let polys = layers.iter().flat_map(|layer| layer.polys());
+
+                let (mut x, evals) = if num_vars == 0 {
+                    (vec![], polys.map(|poly| poly[0]).collect_vec())
+                } else {
+                    let gamma = transcript.squeeze_challenge();
+
+                    let (_, x, evals) = {
+                        let claim = sum_check_claim(&claimed_v_ys, gamma);
+                        SumCheck::prove(
+                            &(),
+                            num_vars,
+                            VirtualPolynomial::new(&expression, polys, &[gamma], &[y]),
+                            claim,
+                            transcript,
+                        )?
+                    };
+
+                    (x, evals.into_values().collect_vec())
+                };
+
+                transcript.write_field_elements(&evals)?;
+
+                let mu = transcript.squeeze_challenge();
+
+                let v_xs = layer_down_claim(&evals, mu);
+                x.push(mu);
+
+                Ok((v_xs, x))
+            })?;
+
+    if cfg!(feature = "sanity-check") {
+        izip!(vs, &v_xs).for_each(|(poly, eval)| assert_eq!(poly.evaluate(&x), *eval));
+    }
+
+    Ok((v_xs, x))
+}
+
+pub fn verify_grand_product<F: PrimeField>(
+    num_vars: usize,
+    claimed_v_0s: impl IntoIterator<Item = Option<F>>,
+    transcript: &mut impl FieldTranscriptRead<F>,
+) -> Result<(Vec<F>, Vec<F>), Error> {
+    let claimed_v_0s = claimed_v_0s.into_iter().collect_vec();
+    let num_batching = claimed_v_0s.len();
+
+    assert!(num_batching != 0);
+
+    let claimed_v_0s = claimed_v_0s
+        .into_iter()
+        .map(|claimed| match claimed {
+            Some(claimed) => transcript.common_field_element(&claimed).map(|_| claimed),
+            None => transcript.read_field_element(),
+        })
+        .try_collect::<_, Vec<_>, _>()?;
+
+    let expression = sum_check_expression(num_batching);
+
+    let (v_xs, x) = (0..num_vars).try_fold((claimed_v_0s, Vec::new()), |result, num_vars| {
+        let (claimed_v_ys, y) = result;
+
+        let (mut x, evals) = if num_vars == 0 {
+            let evals = transcript.read_field_elements(2 * num_batching)?;
+
+            for (claimed_v, (&v_l, &v_r)) in izip!(claimed_v_ys, evals.iter().tuples()) {
+                if claimed_v != v_l * v_r {
+                    return Err(err_unmatched_sum_check_output());
+                }
+            }
+
+            (Vec::new(), evals)
+        } else {
+            let gamma = transcript.squeeze_challenge();
+
+            let (x_eval, x) = {
+                let claim = sum_check_claim(&claimed_v_ys, gamma);
+                SumCheck::verify(&(), num_vars, expression.degree(), claim, transcript)?
+            };
+
+            let evals = transcript.read_field_elements(2 * num_batching)?;
+
+            let query_eval = {
+                let queries = (0..).map(|idx| Query::new(idx, Rotation::cur()));
+                let evals = izip!(queries, evals.iter().cloned()).collect();
+                evaluate::<_, usize>(&expression, num_vars, &evals, &[gamma], &[&y], &x)
+            };
+            if x_eval != query_eval {
+                return Err(err_unmatched_sum_check_output());
+            }
+
+            (x, evals)
+        };
+
+        let mu = transcript.squeeze_challenge();
+
+        let v_xs = layer_down_claim(&evals, mu);
+        x.push(mu);
+
+        Ok((v_xs, x))
+    })?;
+
+    Ok((v_xs, x))
+}
+
+fn sum_check_expression<F: PrimeField>(num_batching: usize) -> Expression<F> {
+    let exprs = &(0..2 * num_batching)
+        .map(|idx| Expression::<F>::Polynomial(Query::new(idx, Rotation::cur())))
+        .tuples()
+        .map(|(ref v_l, ref v_r)| v_l * v_r)
+        .collect_vec();
+    let eq_xy = &Expression::eq_xy(0);
+    let gamma = &Expression::Challenge(0);
+    Expression::distribute_powers(exprs, gamma) * eq_xy
+}
+
+fn sum_check_claim<F: PrimeField>(claimed_v_ys: &[F], gamma: F) -> F {
+    inner_product(
+        claimed_v_ys,
+        &powers(gamma).take(claimed_v_ys.len()).collect_vec(),
+    )
+}
+
+fn layer_down_claim<F: PrimeField>(evals: &[F], mu: F) -> Vec<F> {
+    evals
+        .iter()
+        .tuples()
+        .map(|(&v_l, &v_r)| v_l + mu * (v_r - v_l))
+        .collect()
+}
+
+fn err_unmatched_sum_check_output() -> Error {
+    Error::InvalidSumcheck("Unmatched between sum_check output and query evaluation".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        piop::gkr::grand_product::{prove_grand_product, verify_grand_product},
+        poly::multilinear::MultilinearPolynomial,
+        util::{
+            izip_eq,
+            test::{rand_vec, seeded_std_rng},
+            transcript::{InMemoryTranscript, Keccak256Transcript},
+            Itertools,
+        },
+    };
+    use halo2_curves::bn256::Fr;
+    use std::iter;
+
+    #[test]
+    fn grand_product() {
+        let num_batching = 3;
+        for num_vars in 1..16 {
+            let mut rng = seeded_std_rng();
+
+            let vs = iter::repeat_with(|| rand_vec(1 << num_vars, &mut rng))
+                .map(MultilinearPolynomial::new)
+                .take(num_batching)
+                .collect_vec();
+            let v_0s = vs
+                .iter()
+                .map(|v| Some(v.evals().iter().product()))
+                .collect_vec();
+
+            let proof = {
+                let mut transcript = Keccak256Transcript::new(());
+                prove_grand_product::<Fr>(v_0s.clone(), &vs, &mut transcript).unwrap();
+                transcript.into_proof()
+            };
+
+            let result = {
+                let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+                verify_grand_product::<Fr>(num_vars, v_0s, &mut transcript)
+            };
+            assert_eq!(result.as_ref().map(|_| ()), Ok(()));
+
+            let (v_xs, x) = result.unwrap();
+            for (poly, eval) in izip_eq!(&vs, v_xs) {
+                assert_eq!(poly.evaluate(&x), eval);
+            }
+        }
+    }
+}
@@ -145,6 +145,28 @@ pub fn verify_multilinear_eval<'a, F, Pcs>(
     evals: &[(Query, F)],
     transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
 ) -> Result<(), Error>
+where
+    F: WithSmallOrderMulGroup<3>,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+    Pcs::Commitment: 'a + Additive<F>,
+{
+    let (comms, points, evals) =
+        verify_multilinear_eval_reduction::<_, Pcs>(vp, num_vars, comms, point, evals, transcript)?;
+    Pcs::batch_verify(vp, &comms, &points, &evals, transcript)
+}
+
+/// Verifies everything of [`verify_multilinear_eval`] but the final batch
+/// opening, and returns commitments, points and evaluations to be batch
+/// verified by caller.
+#[allow(clippy::type_complexity)]
+pub fn verify_multilinear_eval_reduction<'a, F, Pcs>(
+    vp: &Pcs::VerifierParam,
+    num_vars: usize,
+    comms: impl IntoIterator<Item = &'a Pcs::Commitment>,
+    point: &[F],
+    evals: &[(Query, F)],
+    transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+) -> Result<(Vec<Pcs::Commitment>, Vec<F>, Vec<Evaluation<F>>), Error>
 where
     F: WithSmallOrderMulGroup<3>,
     Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
@@ -192,9 +214,9 @@ where
         (comm, -constant)
     };
 
-    let comms = chain![&eq_u_comm, [&lin_comm]];
+    let comms = chain![eq_u_comm, [lin_comm]].collect_vec();
     let (points, evals) = points_evals(domain, x, &evals, lin_eval);
-    Pcs::batch_verify(vp, comms, &points, &evals, transcript)
+    Ok((comms, points, evals))
 }
 
 #[derive(Clone, Debug)]
@@ -8,7 +8,10 @@ use crate::{
     },
     Error,
 };
-use std::{collections::BTreeMap, fmt::Debug};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+};
 
 pub mod classic;
 
@@ -18,6 +21,7 @@ pub struct VirtualPolynomial<'a, F> {
     polys: Vec<&'a MultilinearPolynomial<F>>,
     challenges: &'a [F],
     ys: &'a [Vec<F>],
+    sparse_polys: HashMap<usize, &'a [usize]>,
 }
 
 impl<'a, F: PrimeField> VirtualPolynomial<'a, F> {
@@ -32,8 +36,20 @@ impl<'a, F: PrimeField> VirtualPolynomial<'a, F> {
             polys: polys.into_iter().collect(),
             challenges,
             ys,
+            sparse_polys: HashMap::new(),
         }
     }
+
+    /// Hints polynomials that are zero on most of the hypercube, each with
+    /// sorted indices of its non-zero evaluations, so prover could skip points
+    /// where they vanish.
+    pub fn with_sparse_polys(
+        mut self,
+        sparse_polys: impl IntoIterator<Item = (usize, &'a [usize])>,
+    ) -> Self {
+        self.sparse_polys.extend(sparse_polys);
+        self
+    }
 }
 
 pub trait SumCheck<F: Field>: Clone + Debug {
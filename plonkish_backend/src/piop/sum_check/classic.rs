@@ -21,11 +21,19 @@ use std::{
     marker::PhantomData,
 };
 
+mod batch;
 mod coeff;
 mod eval;
+mod mask;
+mod zero_check;
 
+pub use batch::BatchLoading;
 pub use coeff::CoefficientsProver;
 pub use eval::EvaluationsProver;
+pub use mask::SumCheckMask;
+pub use zero_check::is_eq_xy_factored;
+
+use zero_check::EqSplit;
 
 #[derive(Debug)]
 pub struct ProverState<'a, F: Field> {
@@ -35,7 +43,9 @@ pub struct ProverState<'a, F: Field> {
     sum: F,
     lagranges: HashMap<i32, (usize, F)>,
     identity: F,
+    sparse_bs: HashMap<usize, Vec<usize>>,
     eq_xys: Vec<MultilinearPolynomial<F>>,
+    eq_split: Option<EqSplit<F>>,
     polys: HashMap<Query, Cow<'a, MultilinearPolynomial<F>>>,
     challenges: &'a [F],
     buf: MultilinearPolynomial<F>,
@@ -48,6 +58,40 @@ impl<'a, F: PrimeField> ProverState<'a, F> {
         num_vars: usize,
         sum: F,
         virtual_poly: VirtualPolynomial<'a, F>,
+    ) -> Self {
+        let eq_xys = virtual_poly
+            .ys
+            .iter()
+            .map(|y| MultilinearPolynomial::eq_xy(y))
+            .collect_vec();
+        Self::new_with_eq_xys::<R>(num_vars, sum, virtual_poly, eq_xys)
+    }
+
+    /// Returns state of zero-check with `eq_xys[0]` being the suffix table
+    /// `eq(b, y_{>round})` split by [`EqSplit`], instead of the full one.
+    fn new_zero_check<R: Rotatable + From<usize>>(
+        num_vars: usize,
+        virtual_poly: VirtualPolynomial<'a, F>,
+    ) -> Self {
+        assert_eq!(virtual_poly.ys.len(), 1);
+
+        let y = virtual_poly.ys[0].clone();
+        let eq_xys = if num_vars > 1 {
+            vec![MultilinearPolynomial::eq_xy(&y[1..])]
+        } else {
+            vec![MultilinearPolynomial::new(vec![F::ONE])]
+        };
+        let mut state = Self::new_with_eq_xys::<R>(num_vars, F::ZERO, virtual_poly, eq_xys);
+        state.degree -= 1;
+        state.eq_split = Some(EqSplit::new(y));
+        state
+    }
+
+    fn new_with_eq_xys<R: Rotatable + From<usize>>(
+        num_vars: usize,
+        sum: F,
+        virtual_poly: VirtualPolynomial<'a, F>,
+        eq_xys: Vec<MultilinearPolynomial<F>>,
     ) -> Self {
         let rotatable = Box::new(R::from(num_vars));
         assert!(virtual_poly.expression.max_used_rotation_distance() <= rotatable.max_rotation());
@@ -60,11 +104,11 @@ impl<'a, F: PrimeField> ProverState<'a, F> {
                 .map(|i| (i, (rotatable.nth(i), F::ONE)))
                 .collect()
         };
-        let eq_xys = virtual_poly
-            .ys
+        let sparse_bs = virtual_poly
+            .sparse_polys
             .iter()
-            .map(|y| MultilinearPolynomial::eq_xy(y))
-            .collect_vec();
+            .map(|(poly, bs)| (*poly, bs.to_vec()))
+            .collect();
         let polys = izip!(0.., virtual_poly.polys)
             .map(|(idx, poly)| ((idx, 0).into(), Cow::Borrowed(poly)))
             .collect();
@@ -75,7 +119,9 @@ impl<'a, F: PrimeField> ProverState<'a, F> {
             sum,
             lagranges,
             identity: F::ZERO,
+            sparse_bs,
             eq_xys,
+            eq_split: None,
             polys,
             challenges: virtual_poly.challenges,
             buf: MultilinearPolynomial::new(vec![F::ZERO; 1 << (num_vars - 1)]),
@@ -99,9 +145,17 @@ impl<'a, F: PrimeField> ProverState<'a, F> {
             }
             *b >>= 1;
         });
-        self.eq_xys
-            .iter_mut()
-            .for_each(|eq_xy| eq_xy.fix_var_in_place(challenge, &mut self.buf));
+        self.sparse_bs.values_mut().for_each(|bs| {
+            *bs = bs.iter().map(|b| b >> 1).dedup().collect();
+        });
+        if let Some(eq_split) = self.eq_split.as_mut() {
+            eq_split.next_round(self.round, challenge);
+            self.eq_xys[0] = EqSplit::fold_suffix(&self.eq_xys[0]);
+        } else {
+            self.eq_xys
+                .iter_mut()
+                .for_each(|eq_xy| eq_xy.fix_var_in_place(challenge, &mut self.buf));
+        }
         if self.round == 0 {
             let rotation_maps = self
                 .expression
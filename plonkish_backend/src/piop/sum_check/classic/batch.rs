@@ -0,0 +1,305 @@
+use crate::{
+    piop::sum_check::{
+        classic::{
+            eval::Evaluations, ClassicSumCheck, ClassicSumCheckProver, ClassicSumCheckRoundMessage,
+            EvaluationsProver, ProverState,
+        },
+        VirtualPolynomial,
+    },
+    util::{
+        arithmetic::{inner_product, powers, PrimeField},
+        end_timer,
+        expression::{rotate::Rotatable, Query},
+        izip, start_timer,
+        transcript::{FieldTranscriptRead, FieldTranscriptWrite},
+        Itertools,
+    },
+    Error,
+};
+use std::collections::BTreeMap;
+
+/// Placement of variables of claims having fewer variables than the batched
+/// sum-check, where the missing variables are padded and the claimed
+/// polynomial is treated as constant in them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchLoading {
+    /// Claims start from the first round, so point of claim with `n_i`
+    /// variables is the first `n_i` challenges.
+    Front,
+    /// Claims end at the last round, so point of claim with `n_i` variables is
+    /// the last `n_i` challenges.
+    Back,
+}
+
+impl BatchLoading {
+    /// Returns the first round where claim of `num_vars` starts.
+    fn offset(&self, max_num_vars: usize, num_vars: usize) -> usize {
+        match self {
+            Self::Front => 0,
+            Self::Back => max_num_vars - num_vars,
+        }
+    }
+}
+
+/// Scalar of claim of `num_vars` with `offset` in `round`, which is the number
+/// of points on hypercube of unbound padded variables after `round`, with
+/// whether the claim is active in `round`.
+fn padding_scalar<F: PrimeField>(
+    max_num_vars: usize,
+    num_vars: usize,
+    offset: usize,
+    round: usize,
+) -> (F, bool) {
+    let num_padded_vars = if round < offset {
+        max_num_vars - num_vars - round - 1
+    } else if round < offset + num_vars {
+        max_num_vars - offset - num_vars
+    } else {
+        max_num_vars - round - 1
+    };
+    let is_active = (offset..offset + num_vars).contains(&round);
+    (F::from(1 << num_padded_vars), is_active)
+}
+
+impl<F, R> ClassicSumCheck<EvaluationsProver<F>, R>
+where
+    F: PrimeField,
+    R: Rotatable + From<usize>,
+{
+    /// Proves claims `Σ_{b ∈ {0, 1}^{n_i}} f_i(b) = sum_i` of different
+    /// `n_i` in a single sum-check of `max(n_i)` rounds, batched by powers of
+    /// a squeezed challenge, with variables of each claim placed by `loading`.
+    /// Evaluation of each `f_i` on its point is written after the rounds, and
+    /// the same as [`SumCheck::prove`] is returned for each claim.
+    ///
+    /// [`SumCheck::prove`]: crate::piop::sum_check::SumCheck::prove
+    #[allow(clippy::type_complexity)]
+    pub fn batch_prove(
+        loading: BatchLoading,
+        claims: Vec<(usize, VirtualPolynomial<F>, F)>,
+        transcript: &mut impl FieldTranscriptWrite<F>,
+    ) -> Result<Vec<(F, Vec<F>, BTreeMap<Query, F>)>, Error> {
+        assert!(!claims.is_empty());
+        assert!(claims.iter().all(|(num_vars, _, _)| *num_vars > 0));
+
+        let num_vars = claims
+            .iter()
+            .map(|(num_vars, _, _)| *num_vars)
+            .max()
+            .unwrap();
+        let _timer = start_timer(|| format!("sum_check_batch_prove-{num_vars}-{}", claims.len()));
+
+        let rhos = powers(transcript.squeeze_challenge())
+            .take(claims.len())
+            .collect_vec();
+
+        let mut states = claims
+            .into_iter()
+            .map(|(num_vars, virtual_poly, sum)| ProverState::new::<R>(num_vars, sum, virtual_poly))
+            .collect_vec();
+        let provers = states.iter().map(EvaluationsProver::new).collect_vec();
+        let offsets = states
+            .iter()
+            .map(|state| loading.offset(num_vars, state.num_vars))
+            .collect_vec();
+        let auxs = states
+            .iter()
+            .map(|state| Evaluations::<F>::auxiliary(state.degree))
+            .collect_vec();
+        let degree = states.iter().map(|state| state.degree).max().unwrap();
+        let points = Evaluations::<F>::points(degree);
+
+        let mut challenges = Vec::with_capacity(num_vars);
+        for round in 0..num_vars {
+            let timer = start_timer(|| format!("sum_check_batch_prove_round-{round}"));
+            let mut msg = Evaluations(vec![F::ZERO; degree + 1]);
+            let mut claim_msgs = Vec::with_capacity(states.len());
+            for (state, prover, aux, offset, rho) in
+                izip!(&states, &provers, &auxs, &offsets, &rhos)
+            {
+                let (scalar, is_active) =
+                    padding_scalar::<F>(num_vars, state.num_vars, *offset, round);
+                let scalar = scalar * rho;
+                if is_active {
+                    let claim_msg = prover.prove_round(state);
+                    izip!(&mut msg.0, &points)
+                        .for_each(|(eval, point)| *eval += scalar * claim_msg.evaluate(aux, point));
+                    claim_msgs.push(Some(claim_msg));
+                } else {
+                    msg.0
+                        .iter_mut()
+                        .for_each(|eval| *eval += scalar * state.sum);
+                    claim_msgs.push(None);
+                }
+            }
+            end_timer(timer);
+            msg.write(transcript)?;
+
+            let challenge = transcript.squeeze_challenge();
+            challenges.push(challenge);
+
+            let timer = start_timer(|| format!("sum_check_batch_next_round-{round}"));
+            for (state, claim_msg, aux) in izip!(&mut states, claim_msgs, &auxs) {
+                if let Some(claim_msg) = claim_msg {
+                    state.next_round::<R>(claim_msg.evaluate(aux, &challenge), &challenge);
+                }
+            }
+            end_timer(timer);
+        }
+
+        izip!(states, offsets)
+            .map(|(state, offset)| {
+                transcript.write_field_element(&state.sum)?;
+                let x = challenges[offset..offset + state.num_vars].to_vec();
+                Ok((state.sum, x, state.into_evals()))
+            })
+            .try_collect()
+    }
+
+    /// Verifies proof generated by [`Self::batch_prove`] for claims of
+    /// `(num_vars, degree, sum)`, and returns evaluation and point of each
+    /// claim to be checked by caller.
+    pub fn batch_verify(
+        loading: BatchLoading,
+        claims: &[(usize, usize, F)],
+        transcript: &mut impl FieldTranscriptRead<F>,
+    ) -> Result<Vec<(F, Vec<F>)>, Error> {
+        assert!(!claims.is_empty());
+        assert!(claims.iter().all(|(num_vars, _, _)| *num_vars > 0));
+
+        let num_vars = claims
+            .iter()
+            .map(|(num_vars, _, _)| *num_vars)
+            .max()
+            .unwrap();
+        let degree = claims.iter().map(|(_, degree, _)| *degree).max().unwrap();
+
+        let rhos = powers(transcript.squeeze_challenge())
+            .take(claims.len())
+            .collect_vec();
+        let sum = inner_product(
+            &rhos,
+            &claims
+                .iter()
+                .map(|(claim_num_vars, _, sum)| F::from(1 << (num_vars - claim_num_vars)) * sum)
+                .collect_vec(),
+        );
+
+        let (msgs, challenges) = {
+            let mut msgs = Vec::with_capacity(num_vars);
+            let mut challenges = Vec::with_capacity(num_vars);
+            for _ in 0..num_vars {
+                msgs.push(Evaluations::read(degree, transcript)?);
+                challenges.push(transcript.squeeze_challenge());
+            }
+            (msgs, challenges)
+        };
+        let x_eval = Evaluations::verify_consistency(degree, sum, &msgs, &challenges)?;
+
+        let evals = transcript.read_field_elements(claims.len())?;
+        if inner_product(&rhos, &evals) != x_eval {
+            return Err(Error::InvalidSumcheck(
+                "Unmatched between batched sum-check output and claimed evaluations".to_string(),
+            ));
+        }
+
+        Ok(izip!(claims, evals)
+            .map(|((claim_num_vars, _, _), eval)| {
+                let offset = loading.offset(num_vars, *claim_num_vars);
+                (eval, challenges[offset..offset + claim_num_vars].to_vec())
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::hyperplonk::util::{rand_vanilla_plonk_assignment, vanilla_plonk_expression},
+        piop::sum_check::{
+            classic::{BatchLoading, ClassicSumCheck, EvaluationsProver},
+            evaluate, VirtualPolynomial,
+        },
+        poly::multilinear::MultilinearPolynomial,
+        util::{
+            arithmetic::Field,
+            expression::{rotate::BinaryField, Expression, Query, Rotation},
+            izip,
+            test::{rand_vec, seeded_std_rng},
+            transcript::{InMemoryTranscript, Keccak256Transcript},
+            Itertools,
+        },
+    };
+    use halo2_curves::bn256::Fr;
+
+    type Sc = ClassicSumCheck<EvaluationsProver<Fr>, BinaryField>;
+
+    #[test]
+    fn batch_sum_check() {
+        let mut rng = seeded_std_rng();
+        let claims = [8, 16, 8]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, num_vars)| {
+                if idx % 2 == 0 {
+                    let expression = vanilla_plonk_expression::<Fr>(num_vars);
+                    let (polys, challenges) = rand_vanilla_plonk_assignment::<_, BinaryField>(
+                        num_vars,
+                        seeded_std_rng(),
+                        seeded_std_rng(),
+                    );
+                    let ys = vec![rand_vec(num_vars, &mut rng)];
+                    (num_vars, expression, polys, challenges, ys, Fr::ZERO)
+                } else {
+                    let [lhs, rhs] = [0, 1]
+                        .map(|idx| Expression::<Fr>::Polynomial(Query::new(idx, Rotation::cur())));
+                    let polys = [(); 2]
+                        .map(|_| MultilinearPolynomial::new(rand_vec(1 << num_vars, &mut rng)))
+                        .to_vec();
+                    let sum = (0..1 << num_vars)
+                        .map(|b| polys[0][b] * polys[1][b])
+                        .sum::<Fr>();
+                    (num_vars, lhs * rhs, polys, Vec::new(), Vec::new(), sum)
+                }
+            })
+            .collect_vec();
+
+        for loading in [BatchLoading::Front, BatchLoading::Back] {
+            let (evals, proof) = {
+                let claims = claims
+                    .iter()
+                    .map(|(num_vars, expression, polys, challenges, ys, sum)| {
+                        let virtual_poly =
+                            VirtualPolynomial::new(expression, polys, challenges, ys);
+                        (*num_vars, virtual_poly, *sum)
+                    })
+                    .collect_vec();
+                let mut transcript = Keccak256Transcript::default();
+                let outputs = Sc::batch_prove(loading, claims, &mut transcript).unwrap();
+                let evals = outputs.into_iter().map(|(_, _, evals)| evals).collect_vec();
+                (evals, transcript.into_proof())
+            };
+
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            let outputs = Sc::batch_verify(
+                loading,
+                &claims
+                    .iter()
+                    .map(|(num_vars, expression, _, _, _, sum)| {
+                        (*num_vars, expression.degree(), *sum)
+                    })
+                    .collect_vec(),
+                &mut transcript,
+            )
+            .unwrap();
+            for ((num_vars, expression, _, challenges, ys, _), evals, (x_eval, x)) in
+                izip!(&claims, &evals, outputs)
+            {
+                let ys = ys.iter().map(Vec::as_slice).collect_vec();
+                let eval =
+                    evaluate::<_, BinaryField>(expression, *num_vars, evals, challenges, &ys, &x);
+                assert_eq!(x_eval, eval);
+            }
+        }
+    }
+}
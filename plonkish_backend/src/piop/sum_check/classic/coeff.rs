@@ -12,7 +12,11 @@ use crate::{
     },
     Error,
 };
-use std::{array, fmt::Debug, ops::AddAssign};
+use num_integer::Integer;
+use std::{
+    fmt::Debug,
+    ops::{AddAssign, Range},
+};
 
 #[derive(Debug)]
 pub struct Coefficients<F>(Vec<F>);
@@ -135,9 +139,15 @@ where
         coeffs += &(F::from(state.size() as u64) * &self.constant);
 
         for (scalar, products) in self.products.iter() {
-            match products.len() {
-                2 => coeffs += (scalar, &self.karatsuba::<true>(state, products)),
-                _ => unimplemented!(),
+            let items = products
+                .iter()
+                .map(|expr| Item::new(state, expr))
+                .collect_vec();
+            match items.as_slice() {
+                [Item::Poly(lhs), Item::Poly(rhs)] => {
+                    coeffs += (scalar, &self.karatsuba::<true>(state, [*lhs, *rhs]))
+                }
+                _ => coeffs += (scalar, &self.product(state, &items)),
             }
         }
 
@@ -150,11 +160,8 @@ impl<F: PrimeField> CoefficientsProver<F> {
     fn karatsuba<const LAZY: bool>(
         &self,
         state: &ProverState<F>,
-        items: &[Expression<F>],
+        [lhs, rhs]: [&MultilinearPolynomial<F>; 2],
     ) -> Coefficients<F> {
-        debug_assert_eq!(items.len(), 2);
-
-        let [lhs, rhs] = array::from_fn(|idx| poly(state, &items[idx]));
         let evaluate_serial = |coeffs: &mut [F; 3], start: usize, n: usize| {
             izip_eq!(
                 zip_self!(lhs.iter(), 2, start),
@@ -197,17 +204,175 @@ impl<F: PrimeField> CoefficientsProver<F> {
 
         Coefficients(coeffs.to_vec())
     }
+
+    /// Returns coefficients of `sum_b prod_i item_i(X, b)` by multiplying the
+    /// linear polynomials of items one by one for each `b`.
+    fn product(&self, state: &ProverState<F>, items: &[Item<F>]) -> Coefficients<F> {
+        let degree = items.len();
+        let evaluate_serial = |coeffs: &mut [F], bs: Range<usize>| {
+            let mut product = vec![F::ZERO; degree + 1];
+            for b in bs {
+                product[0] = F::ONE;
+                product[1..].iter_mut().for_each(|coeff| *coeff = F::ZERO);
+                for (deg, item) in items.iter().enumerate() {
+                    let (eval_0, eval_1) = item.evals(state, b);
+                    if eval_0 == F::ZERO && eval_1 == F::ZERO {
+                        product.iter_mut().for_each(|coeff| *coeff = F::ZERO);
+                        break;
+                    }
+                    let step = eval_1 - eval_0;
+                    for idx in (0..=deg).rev() {
+                        product[idx + 1] += product[idx] * step;
+                        product[idx] *= eval_0;
+                    }
+                }
+                izip_eq!(coeffs.iter_mut(), &product).for_each(|(lhs, rhs)| *lhs += rhs);
+            }
+        };
+
+        let mut coeffs = vec![F::ZERO; degree + 1];
+
+        let size = state.size();
+        if let Some(bs) = items.iter().find_map(|item| item.sparse_bs()) {
+            evaluate_serial(&mut coeffs, bs);
+        } else if size < 16 {
+            evaluate_serial(&mut coeffs, 0..size);
+        } else {
+            let chunk_size = div_ceil(size, num_threads());
+            let mut partials = vec![vec![F::ZERO; degree + 1]; div_ceil(size, chunk_size)];
+            parallelize_iter(
+                partials.iter_mut().zip((0..).step_by(chunk_size)),
+                |(partial, start)| {
+                    evaluate_serial(partial, start..(start + chunk_size).min(size));
+                },
+            );
+            partials.iter().for_each(|partial| {
+                izip_eq!(coeffs.iter_mut(), partial).for_each(|(lhs, rhs)| *lhs += rhs)
+            });
+        }
+
+        Coefficients(coeffs)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Item<'a, F: PrimeField> {
+    Poly(&'a MultilinearPolynomial<F>),
+    RotatedPoly(&'a MultilinearPolynomial<F>, Rotation),
+    Lagrange(usize, F),
+    Identity,
 }
 
-fn poly<'a, F: PrimeField>(
-    state: &'a ProverState<F>,
-    expr: &Expression<F>,
-) -> &'a MultilinearPolynomial<F> {
-    match expr {
-        Expression::CommonPolynomial(CommonPolynomial::EqXY(idx)) => &state.eq_xys[*idx],
-        Expression::Polynomial(query) if query.rotation() == Rotation::cur() => {
-            &state.polys[&query]
+impl<'a, F: PrimeField> Item<'a, F> {
+    fn new(state: &'a ProverState<F>, expr: &Expression<F>) -> Self {
+        match expr {
+            Expression::CommonPolynomial(CommonPolynomial::EqXY(idx)) => {
+                Self::Poly(&state.eq_xys[*idx])
+            }
+            Expression::CommonPolynomial(CommonPolynomial::Lagrange(i)) => {
+                let (b, value) = state.lagranges[i];
+                Self::Lagrange(b, value)
+            }
+            Expression::CommonPolynomial(CommonPolynomial::Identity) => Self::Identity,
+            Expression::Polynomial(query) if state.round == 0 => {
+                let poly = &state.polys[&(query.poly(), 0).into()];
+                if query.rotation() == Rotation::cur() {
+                    Self::Poly(poly)
+                } else {
+                    Self::RotatedPoly(poly, query.rotation())
+                }
+            }
+            Expression::Polynomial(query) => Self::Poly(&state.polys[query]),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns `b` in the only range where the item could be non-zero.
+    fn sparse_bs(&self) -> Option<Range<usize>> {
+        match self {
+            Self::Lagrange(b, _) => Some(b >> 1..(b >> 1) + 1),
+            _ => None,
+        }
+    }
+
+    /// Returns evaluations on `(b, 0)` and `(b, 1)`.
+    fn evals(&self, state: &ProverState<F>, b: usize) -> (F, F) {
+        match self {
+            Self::Poly(poly) => (poly[b << 1], poly[(b << 1) + 1]),
+            Self::RotatedPoly(poly, rotation) => {
+                let [b_0, b_1] =
+                    [b << 1, (b << 1) + 1].map(|b| state.rotatable.rotate(b, *rotation));
+                (poly[b_0], poly[b_1])
+            }
+            Self::Lagrange(lagrange_b, value) => match (b == lagrange_b >> 1, lagrange_b.is_even())
+            {
+                (false, _) => (F::ZERO, F::ZERO),
+                (true, true) => (*value, F::ZERO),
+                (true, false) => (F::ZERO, *value),
+            },
+            Self::Identity => {
+                let eval_0 = state.identity + F::from((b << (state.round + 1)) as u64);
+                (eval_0, eval_0 + F::from(1 << state.round))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::hyperplonk::util::{
+            rand_vanilla_plonk_w_lookup_assignment, vanilla_plonk_w_lookup_expression,
+        },
+        piop::sum_check::{
+            classic::{
+                self, eval::Evaluations, ClassicSumCheckProver, ClassicSumCheckRoundMessage,
+                CoefficientsProver, EvaluationsProver, ProverState,
+            },
+            test::tests,
+            VirtualPolynomial,
+        },
+        util::{
+            arithmetic::Field,
+            expression::rotate::{BinaryField, Lexical},
+            izip_eq,
+            test::{rand_vec, seeded_std_rng},
+        },
+    };
+    use halo2_curves::bn256::Fr;
+
+    type ClassicSumCheck<F, R> = classic::ClassicSumCheck<CoefficientsProver<F>, R>;
+
+    tests!(binary_field, ClassicSumCheck<Fr, BinaryField>, BinaryField);
+    tests!(lexical, ClassicSumCheck<Fr, Lexical>, Lexical);
+
+    #[test]
+    fn equivalent_to_evaluations_prover() {
+        let mut rng = seeded_std_rng();
+        for num_vars in 2..10 {
+            let expression = vanilla_plonk_w_lookup_expression(num_vars);
+            let (polys, challenges) = rand_vanilla_plonk_w_lookup_assignment::<_, BinaryField>(
+                num_vars,
+                seeded_std_rng(),
+                seeded_std_rng(),
+            );
+            let ys = [rand_vec(num_vars, &mut rng)];
+            let virtual_poly = VirtualPolynomial::new(&expression, &polys, &challenges, &ys);
+            let mut state = ProverState::new::<BinaryField>(num_vars, Fr::ZERO, virtual_poly);
+
+            let coeffs_prover = CoefficientsProver::new(&state);
+            let evals_prover = EvaluationsProver::new(&state);
+            let points = Evaluations::<Fr>::points(state.degree);
+            for _ in 0..num_vars {
+                let coeffs = coeffs_prover.prove_round(&state);
+                let evals = evals_prover.prove_round(&state);
+                for (point, eval) in izip_eq!(&points, &evals.0) {
+                    assert_eq!(coeffs.evaluate(&(), point), *eval);
+                }
+
+                let challenge = Fr::random(&mut rng);
+                state.next_round::<BinaryField>(coeffs.evaluate(&(), &challenge), &challenge);
+            }
         }
-        _ => unimplemented!(),
     }
 }
@@ -5,11 +5,12 @@ use crate::{
         chain,
         expression::{
             evaluator::{ExpressionRegistry, Offsets},
-            CommonPolynomial, Expression,
+            CommonPolynomial, Expression, Rotation,
         },
         impl_index,
         parallel::{num_threads, parallelize_iter},
         transcript::{FieldTranscriptRead, FieldTranscriptWrite},
+        Itertools,
     },
     Error,
 };
@@ -17,14 +18,14 @@ use num_integer::Integer;
 use std::{collections::BTreeSet, fmt::Debug, iter, ops::AddAssign};
 
 #[derive(Clone, Debug)]
-pub struct Evaluations<F>(Vec<F>);
+pub struct Evaluations<F>(pub(super) Vec<F>);
 
 impl<F: PrimeField> Evaluations<F> {
     fn new(degree: usize) -> Self {
         Self(vec![F::ZERO; degree + 1])
     }
 
-    fn points(degree: usize) -> Vec<F> {
+    pub(super) fn points(degree: usize) -> Vec<F> {
         steps(F::ZERO).take(degree + 1).collect()
     }
 }
@@ -103,10 +104,16 @@ impl<F: PrimeField> EvaluationsProver<F> {
         let mut partials = vec![Evaluations::new(state.degree); div_ceil(size, chunk_size)];
         for ev in self.0.iter() {
             if let Some(sparse_bs) = ev.sparse_bs(state) {
-                let mut cache = ev.cache(state);
-                sparse_bs.into_iter().for_each(|b| {
-                    ev.evaluate::<IS_FIRST_ROUND>(&mut partials[0], &mut cache, state, b)
-                })
+                let chunk_size = div_ceil(sparse_bs.len(), partials.len()).max(1);
+                parallelize_iter(
+                    partials.iter_mut().zip(sparse_bs.chunks(chunk_size)),
+                    |(partials, bs)| {
+                        let mut cache = ev.cache(state);
+                        bs.iter().for_each(|b| {
+                            ev.evaluate::<IS_FIRST_ROUND>(partials, &mut cache, state, *b)
+                        })
+                    },
+                );
             } else {
                 parallelize_iter(
                     partials.iter_mut().zip((0..).step_by(chunk_size)),
@@ -145,39 +152,43 @@ impl<F: PrimeField> SumCheckEvaluator<F> {
     }
 
     fn sparse_bs(&self, state: &ProverState<F>) -> Option<Vec<usize>> {
-        self.sparse.as_ref().map(|sparse| {
-            sparse
-                .evaluate(
-                    &|_| None,
-                    &|poly| match poly {
-                        CommonPolynomial::Identity => unimplemented!(),
-                        CommonPolynomial::Lagrange(i) => Some(vec![state.lagranges[&i].0 >> 1]),
-                        _ => None,
-                    },
-                    &|_| None,
-                    &|_| None,
-                    &|bs| bs,
-                    &|lhs, rhs| match (lhs, rhs) {
-                        (None, None) => None,
-                        (Some(bs), None) | (None, Some(bs)) => Some(bs),
-                        (Some(mut lhs), Some(rhs)) => {
-                            lhs.extend(rhs);
-                            Some(lhs)
-                        }
-                    },
-                    &|lhs, rhs| match (lhs, rhs) {
-                        (None, None) => None,
-                        (Some(bs), None) | (None, Some(bs)) => Some(bs),
-                        (Some(lhs), Some(rhs)) => Some(
-                            BTreeSet::from_iter(lhs)
-                                .intersection(&BTreeSet::from_iter(rhs))
-                                .cloned()
-                                .collect(),
-                        ),
-                    },
-                    &|bs, _| bs,
-                )
-                .unwrap()
+        self.sparse.as_ref().and_then(|sparse| {
+            sparse.evaluate(
+                &|_| None,
+                &|poly| match poly {
+                    CommonPolynomial::Lagrange(i) => Some(vec![state.lagranges[&i].0 >> 1]),
+                    _ => None,
+                },
+                &|query| {
+                    (query.rotation() == Rotation::cur())
+                        .then(|| state.sparse_bs.get(&query.poly()))
+                        .flatten()
+                        .map(|bs| bs.iter().map(|b| b >> 1).dedup().collect())
+                },
+                &|_| None,
+                &|bs| bs,
+                &|lhs, rhs| match (lhs, rhs) {
+                    (Some(lhs), Some(rhs)) => Some(
+                        BTreeSet::from_iter(lhs)
+                            .union(&BTreeSet::from_iter(rhs))
+                            .cloned()
+                            .collect(),
+                    ),
+                    // Sum with a dense side could be non-zero anywhere
+                    _ => None,
+                },
+                &|lhs, rhs| match (lhs, rhs) {
+                    (None, None) => None,
+                    (Some(bs), None) | (None, Some(bs)) => Some(bs),
+                    (Some(lhs), Some(rhs)) => Some(
+                        BTreeSet::from_iter(lhs)
+                            .intersection(&BTreeSet::from_iter(rhs))
+                            .cloned()
+                            .collect(),
+                    ),
+                },
+                &|bs, _| bs,
+            )
         })
     }
 
@@ -240,8 +251,13 @@ impl<F: PrimeField> SumCheckEvaluator<F> {
                 .eq_xy_iter_mut()
                 .zip(self.reg.eq_xys())
                 .for_each(|((eval, step), idx)| {
-                    *eval = state.eq_xys[*idx][b_1];
-                    *step = state.eq_xys[*idx][b_1] - &state.eq_xys[*idx][b_0];
+                    if state.eq_split.is_some() {
+                        *eval = state.eq_xys[*idx][b];
+                        *step = F::ZERO;
+                    } else {
+                        *eval = state.eq_xys[*idx][b_1];
+                        *step = state.eq_xys[*idx][b_1] - &state.eq_xys[*idx][b_0];
+                    }
                 });
             cache.poly_iter_mut().zip(self.reg.polys()).for_each(
                 |(((eval, step), bs), (query, rotation))| {
@@ -298,7 +314,7 @@ impl<F: PrimeField> SumCheckEvaluator<F> {
         state: &ProverState<F>,
         b: usize,
     ) {
-        debug_assert!(evals.0.len() > 2);
+        debug_assert!(evals.0.len() > 1);
 
         self.evaluate_next::<IS_FIRST_ROUND, true>(&mut evals[1], state, cache, b);
         for eval in evals[2..].iter_mut() {
@@ -350,8 +366,11 @@ fn split_sparse<F: PrimeField>(state: &ProverState<F>) -> (Expression<F>, Vec<Ex
             }
         },
         &|query| {
-            // TODO: Recognize sparse selectors
-            (query.into(), Vec::new())
+            if query.rotation() == Rotation::cur() && state.sparse_bs.contains_key(&query.poly()) {
+                (Expression::zero(), vec![query.into()])
+            } else {
+                (query.into(), Vec::new())
+            }
         },
         &|challenge| (Expression::Challenge(challenge), Vec::new()),
         &|(dense, sparse)| (-dense, sparse.iter().map(|sparse| -sparse).collect()),
@@ -383,15 +402,117 @@ fn split_sparse<F: PrimeField>(state: &ProverState<F>) -> (Expression<F>, Vec<Ex
 #[cfg(test)]
 mod test {
     use crate::{
+        backend::hyperplonk::util::{
+            rand_vanilla_plonk_w_lookup_assignment, vanilla_plonk_w_lookup_expression,
+        },
         piop::sum_check::{
-            classic::{self, EvaluationsProver},
+            classic::{
+                self, eval::Evaluations, ClassicSumCheckProver, ClassicSumCheckRoundMessage,
+                EvaluationsProver, ProverState,
+            },
             test::tests,
+            VirtualPolynomial,
+        },
+        poly::multilinear::MultilinearPolynomial,
+        util::{
+            arithmetic::Field,
+            expression::{
+                rotate::{BinaryField, Lexical},
+                Expression, Query, Rotation,
+            },
+            test::{rand_vec, seeded_std_rng},
+            Itertools,
         },
-        util::expression::rotate::{BinaryField, Lexical},
     };
+    use halo2_curves::bn256::Fr;
+    use rand::RngCore;
+    use std::ops::Range;
 
     type ClassicSumCheck<F, R> = classic::ClassicSumCheck<EvaluationsProver<F>, R>;
 
     tests!(binary_field, ClassicSumCheck<Fr, BinaryField>, BinaryField);
     tests!(lexical, ClassicSumCheck<Fr, Lexical>, Lexical);
+
+    fn run_sparse_polys(
+        num_vars: usize,
+        expression: &Expression<Fr>,
+        polys: &[MultilinearPolynomial<Fr>],
+        challenges: &[Fr],
+        sparse_polys: Range<usize>,
+    ) {
+        let mut rng = seeded_std_rng();
+        let ys = [rand_vec(num_vars, &mut rng)];
+        let sparse_bs = sparse_polys
+            .map(|idx| {
+                let bs = (0..1 << num_vars)
+                    .filter(|b| polys[idx][*b] != Fr::ZERO)
+                    .collect_vec();
+                (idx, bs)
+            })
+            .collect_vec();
+
+        let [mut dense_state, mut sparse_state] = [false, true].map(|is_sparse| {
+            let virtual_poly = VirtualPolynomial::new(expression, polys, challenges, &ys);
+            let virtual_poly = if is_sparse {
+                virtual_poly
+                    .with_sparse_polys(sparse_bs.iter().map(|(idx, bs)| (*idx, bs.as_slice())))
+            } else {
+                virtual_poly
+            };
+            ProverState::new::<BinaryField>(num_vars, Fr::ZERO, virtual_poly)
+        });
+        let dense_prover = EvaluationsProver::new(&dense_state);
+        let sparse_prover = EvaluationsProver::new(&sparse_state);
+        for _ in 0..num_vars {
+            let dense_evals = dense_prover.prove_round(&dense_state);
+            let sparse_evals = sparse_prover.prove_round(&sparse_state);
+            assert_eq!(dense_evals.0, sparse_evals.0);
+
+            let challenge = Fr::random(&mut rng);
+            let sum = dense_evals.evaluate(
+                &Evaluations::<Fr>::auxiliary(dense_state.degree),
+                &challenge,
+            );
+            dense_state.next_round::<BinaryField>(sum, &challenge);
+            sparse_state.next_round::<BinaryField>(sum, &challenge);
+        }
+    }
+
+    #[test]
+    fn sparse_polys() {
+        for num_vars in 2..10 {
+            let expression = vanilla_plonk_w_lookup_expression(num_vars);
+            let (polys, challenges) = rand_vanilla_plonk_w_lookup_assignment::<_, BinaryField>(
+                num_vars,
+                seeded_std_rng(),
+                seeded_std_rng(),
+            );
+            run_sparse_polys(num_vars, &expression, &polys, &challenges, 1..10);
+        }
+    }
+
+    #[test]
+    fn sparse_polys_w_dense_summand() {
+        let mut rng = seeded_std_rng();
+        for num_vars in 2..10 {
+            // `s_0 * (s_1 + w)` is kept dense as whole, then multiplied by `s_2`
+            // to be sparse again with a sum of sparse and dense inside.
+            let [s_0, s_1, s_2, w] = &[0, 1, 2, 3]
+                .map(|poly| Expression::<Fr>::Polynomial(Query::new(poly, Rotation::cur())));
+            let expression = s_2 * (s_0 * (s_1 + w));
+            let polys = (0..4)
+                .map(|idx| {
+                    let evals = (0..1 << num_vars)
+                        .map(|_| match idx {
+                            3 => Fr::random(&mut rng),
+                            _ if rng.next_u32() % 2 == 0 => Fr::random(&mut rng),
+                            _ => Fr::ZERO,
+                        })
+                        .collect();
+                    MultilinearPolynomial::new(evals)
+                })
+                .collect_vec();
+            run_sparse_polys(num_vars, &expression, &polys, &[], 0..3);
+        }
+    }
 }
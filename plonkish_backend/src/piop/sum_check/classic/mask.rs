@@ -0,0 +1,239 @@
+use crate::{
+    piop::sum_check::{
+        classic::{
+            eval::Evaluations, ClassicSumCheck, ClassicSumCheckProver, ClassicSumCheckRoundMessage,
+            EvaluationsProver, ProverState,
+        },
+        VirtualPolynomial,
+    },
+    util::{
+        arithmetic::{horner, PrimeField},
+        end_timer,
+        expression::{rotate::Rotatable, Query},
+        start_timer,
+        transcript::FieldTranscriptWrite,
+        Itertools,
+    },
+    Error,
+};
+use rand::RngCore;
+use std::{collections::BTreeMap, iter};
+
+/// Masking polynomial `g(X_0, ..., X_{n-1}) = Σ_i g_i(X_i)` of Libra's
+/// zero-knowledge sum-check, where each `g_i` is an univariate polynomial in
+/// coefficient form.
+#[derive(Clone, Debug)]
+pub struct SumCheckMask<F> {
+    coeffs: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> SumCheckMask<F> {
+    pub fn new(coeffs: Vec<Vec<F>>) -> Self {
+        assert!(!coeffs.is_empty() && !coeffs[0].is_empty());
+        assert!(coeffs.iter().map(Vec::len).all_equal());
+        Self { coeffs }
+    }
+
+    pub fn rand(num_vars: usize, degree: usize, mut rng: impl RngCore) -> Self {
+        let coeffs = iter::repeat_with(|| {
+            iter::repeat_with(|| F::random(&mut rng))
+                .take(degree + 1)
+                .collect_vec()
+        })
+        .take(num_vars)
+        .collect_vec();
+        Self::new(coeffs)
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coeffs[0].len() - 1
+    }
+
+    pub fn coeffs(&self) -> &[Vec<F>] {
+        &self.coeffs
+    }
+
+    /// Returns `Σ_{b ∈ {0, 1}^n} g(b)`.
+    pub fn sum(&self) -> F {
+        let scalar = F::from(1 << (self.num_vars() - 1));
+        scalar
+            * self
+                .coeffs
+                .iter()
+                .map(Vec::as_slice)
+                .map(boolean_sum)
+                .sum::<F>()
+    }
+
+    pub fn evaluate(&self, x: &[F]) -> F {
+        assert_eq!(x.len(), self.num_vars());
+        self.coeffs
+            .iter()
+            .zip(x)
+            .map(|(g_i, x_i)| horner(g_i, x_i))
+            .sum()
+    }
+
+    /// Returns evaluations of round polynomial
+    /// `Σ_{b ∈ {0, 1}^{n-round-1}} g(r_0, ..., r_{round-1}, X, b)` on `points`.
+    fn round_evals(&self, round: usize, challenges: &[F], points: &[F]) -> Vec<F> {
+        let num_vars = self.num_vars();
+        let fixed = self.coeffs[..round]
+            .iter()
+            .zip(challenges)
+            .map(|(g_i, r_i)| horner(g_i, r_i))
+            .sum::<F>();
+        let remaining = if round + 1 < num_vars {
+            let scalar = F::from(1 << (num_vars - round - 2));
+            scalar
+                * self.coeffs[round + 1..]
+                    .iter()
+                    .map(Vec::as_slice)
+                    .map(boolean_sum)
+                    .sum::<F>()
+        } else {
+            F::ZERO
+        };
+        let scalar = F::from(1 << (num_vars - round - 1));
+        points
+            .iter()
+            .map(|point| scalar * (fixed + horner(&self.coeffs[round], point)) + remaining)
+            .collect()
+    }
+}
+
+impl<F, R> ClassicSumCheck<EvaluationsProver<F>, R>
+where
+    F: PrimeField,
+    R: Rotatable + From<usize>,
+{
+    /// Same as [`SumCheck::prove`] but every round message is added with `rho`
+    /// times the round message of `mask`, so the verifier should verify it
+    /// with claimed sum `sum + rho * mask.sum()` and subtract
+    /// `rho * mask.evaluate(x)` from the output. The returned evaluation is
+    /// the unmasked one.
+    ///
+    /// [`SumCheck::prove`]: crate::piop::sum_check::SumCheck::prove
+    #[allow(clippy::type_complexity)]
+    pub fn prove_masked(
+        num_vars: usize,
+        virtual_poly: VirtualPolynomial<F>,
+        sum: F,
+        mask: &SumCheckMask<F>,
+        rho: &F,
+        transcript: &mut impl FieldTranscriptWrite<F>,
+    ) -> Result<(F, Vec<F>, BTreeMap<Query, F>), Error> {
+        let _timer = start_timer(|| {
+            let degree = virtual_poly.expression.degree();
+            format!("sum_check_prove_masked-{num_vars}-{degree}")
+        });
+
+        let mut state = ProverState::new::<R>(num_vars, sum, virtual_poly);
+        assert_eq!(mask.num_vars(), num_vars);
+        assert!(mask.degree() <= state.degree);
+
+        let mut challenges = Vec::with_capacity(num_vars);
+        let prover = EvaluationsProver::<F>::new(&state);
+        let aux = Evaluations::<F>::auxiliary(state.degree);
+        let points = Evaluations::<F>::points(state.degree);
+
+        for round in 0..num_vars {
+            let timer = start_timer(|| format!("sum_check_prove_round-{round}"));
+            let msg = prover.prove_round(&state);
+            end_timer(timer);
+
+            let mut masked_msg = Evaluations(
+                mask.round_evals(round, &challenges, &points)
+                    .into_iter()
+                    .map(|eval| eval * rho)
+                    .collect(),
+            );
+            masked_msg += &msg;
+            masked_msg.write(transcript)?;
+
+            let challenge = transcript.squeeze_challenge();
+            challenges.push(challenge);
+
+            let timer = start_timer(|| format!("sum_check_next_round-{round}"));
+            state.next_round::<R>(msg.evaluate(&aux, &challenge), &challenge);
+            end_timer(timer);
+        }
+
+        Ok((state.sum, challenges, state.into_evals()))
+    }
+}
+
+fn boolean_sum<F: PrimeField>(g_i: &[F]) -> F {
+    g_i[0] + g_i.iter().sum::<F>()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        piop::sum_check::{
+            classic::{ClassicSumCheck, EvaluationsProver, SumCheckMask},
+            evaluate, SumCheck, VirtualPolynomial,
+        },
+        util::{
+            arithmetic::Field,
+            expression::rotate::BinaryField,
+            test::seeded_std_rng,
+            transcript::{InMemoryTranscript, Keccak256Transcript},
+        },
+    };
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn masked_vanilla_plonk() {
+        use crate::backend::hyperplonk::util::{
+            rand_vanilla_plonk_assignment, vanilla_plonk_expression,
+        };
+
+        type Sc = ClassicSumCheck<EvaluationsProver<Fr>, BinaryField>;
+
+        for num_vars in 2..10 {
+            let expression = vanilla_plonk_expression::<Fr>(num_vars);
+            let (polys, challenges) = rand_vanilla_plonk_assignment::<_, BinaryField>(
+                num_vars,
+                seeded_std_rng(),
+                seeded_std_rng(),
+            );
+            let ys = [vec![Fr::from(3); num_vars]];
+            let mask = SumCheckMask::<Fr>::rand(num_vars, expression.degree(), seeded_std_rng());
+            let rho = Fr::from(5);
+
+            let (evals, proof) = {
+                let virtual_poly = VirtualPolynomial::new(&expression, &polys, &challenges, &ys);
+                let mut transcript = Keccak256Transcript::default();
+                let (_, _, evals) = Sc::prove_masked(
+                    num_vars,
+                    virtual_poly,
+                    Fr::ZERO,
+                    &mask,
+                    &rho,
+                    &mut transcript,
+                )
+                .unwrap();
+                (evals, transcript.into_proof())
+            };
+
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            let (x_eval, x) = Sc::verify(
+                &(),
+                num_vars,
+                expression.degree(),
+                rho * mask.sum(),
+                &mut transcript,
+            )
+            .unwrap();
+            let ys = [ys[0].as_slice()];
+            let eval =
+                evaluate::<_, BinaryField>(&expression, num_vars, &evals, &challenges, &ys, &x);
+            assert_eq!(x_eval, eval + rho * mask.evaluate(&x));
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{
+    piop::sum_check::{
+        classic::{
+            eval::Evaluations, ClassicSumCheck, ClassicSumCheckProver, ClassicSumCheckRoundMessage,
+            EvaluationsProver, ProverState,
+        },
+        eq_xy_eval, VirtualPolynomial,
+    },
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::PrimeField,
+        end_timer,
+        expression::{rotate::Rotatable, CommonPolynomial, Expression, Query},
+        izip,
+        parallel::par_map_collect,
+        start_timer,
+        transcript::{FieldTranscriptRead, FieldTranscriptWrite},
+    },
+    Error,
+};
+use std::collections::BTreeMap;
+
+/// Returns whether `expression` is in form of `g * eq_xy(0)` where `g` doesn't
+/// contain any `eq_xy`, which is required by
+/// [`ClassicSumCheck::prove_zero_check`].
+pub fn is_eq_xy_factored<F: PartialEq + Clone>(expression: &Expression<F>) -> bool {
+    let contains_eq_xy = |expression: &Expression<F>| {
+        expression.evaluate(
+            &|_| false,
+            &|poly| matches!(poly, CommonPolynomial::EqXY(_)),
+            &|_| false,
+            &|_| false,
+            &|value| value,
+            &|lhs, rhs| lhs || rhs,
+            &|lhs, rhs| lhs || rhs,
+            &|value, _| value,
+        )
+    };
+    let eq_xy = Expression::eq_xy(0);
+    match expression {
+        Expression::Product(lhs, rhs) => {
+            (**rhs == eq_xy && !contains_eq_xy(lhs)) || (**lhs == eq_xy && !contains_eq_xy(rhs))
+        }
+        _ => false,
+    }
+}
+
+/// Polynomial `eq(X, y)` split as
+/// `eq(r_{<i}, y_{<i}) * eq(X_i, y_i) * eq(X_{>i}, y_{>i})` in round `i`, where
+/// only the scalar prefix and the linear middle factor are tracked here, and
+/// the suffix table is kept in [`ProverState`] in place of the full one.
+#[derive(Clone, Debug)]
+pub(super) struct EqSplit<F> {
+    y: Vec<F>,
+    prefix: F,
+}
+
+impl<F: PrimeField> EqSplit<F> {
+    pub(super) fn new(y: Vec<F>) -> Self {
+        Self { y, prefix: F::ONE }
+    }
+
+    /// Returns `eq(r_{<round}, y_{<round}) * eq(X, y_round)` on `X = 0` and
+    /// `X = 1`.
+    fn evals(&self, round: usize) -> [F; 2] {
+        let y_i = self.y[round];
+        [self.prefix * (F::ONE - y_i), self.prefix * y_i]
+    }
+
+    pub(super) fn next_round(&mut self, round: usize, challenge: &F) {
+        self.prefix *= eq_xy_eval(&[*challenge], &self.y[round..round + 1]);
+    }
+
+    /// Returns `eq(X_{>i+1}, y_{>i+1})` from `eq(X_{>i}, y_{>i})` by summing
+    /// pairs, since `eq(0, y_{i+1}) + eq(1, y_{i+1}) = 1`.
+    pub(super) fn fold_suffix(suffix: &MultilinearPolynomial<F>) -> MultilinearPolynomial<F> {
+        if suffix.evals().len() == 1 {
+            return suffix.clone();
+        }
+        let evals = suffix.evals();
+        MultilinearPolynomial::new(par_map_collect(0..evals.len() >> 1, |idx| {
+            evals[idx << 1] + evals[(idx << 1) + 1]
+        }))
+    }
+}
+
+impl<F, R> ClassicSumCheck<EvaluationsProver<F>, R>
+where
+    F: PrimeField,
+    R: Rotatable + From<usize>,
+{
+    /// Same as [`SumCheck::prove`] with zero sum, but for `virtual_poly` in
+    /// form of `g * eq_xy(0)` where `g` doesn't contain any `eq_xy`, with
+    /// Gruen's optimization. In each round `i` it sends
+    /// `q_i(X) = Σ_b eq(b, y_{>i}) * g(r_{<i}, X, b)` which has degree one less
+    /// than the round polynomial, which is then recovered by verifier as
+    /// `eq(r_{<i}, y_{<i}) * eq(X, y_i) * q_i(X)`. The eq table is only folded
+    /// over suffix by summing pairs.
+    ///
+    /// [`SumCheck::prove`]: crate::piop::sum_check::SumCheck::prove
+    #[allow(clippy::type_complexity)]
+    pub fn prove_zero_check(
+        num_vars: usize,
+        virtual_poly: VirtualPolynomial<F>,
+        transcript: &mut impl FieldTranscriptWrite<F>,
+    ) -> Result<(F, Vec<F>, BTreeMap<Query, F>), Error> {
+        assert!(is_eq_xy_factored(virtual_poly.expression));
+
+        let _timer = start_timer(|| {
+            let degree = virtual_poly.expression.degree();
+            format!("zero_check_prove-{num_vars}-{degree}")
+        });
+
+        let mut state = ProverState::new_zero_check::<R>(num_vars, virtual_poly);
+        let mut challenges = Vec::with_capacity(num_vars);
+        let prover = EvaluationsProver::<F>::new(&state);
+        let aux = Evaluations::<F>::auxiliary(state.degree);
+
+        for round in 0..num_vars {
+            let timer = start_timer(|| format!("zero_check_prove_round-{round}"));
+            let mut msg = prover.prove_round(&state);
+            let [l_0, l_1] = state.eq_split.as_ref().unwrap().evals(round);
+            msg[0] = (state.sum - l_1 * msg[1]) * l_0.invert().unwrap();
+            end_timer(timer);
+            msg.write(transcript)?;
+
+            let challenge = transcript.squeeze_challenge();
+            challenges.push(challenge);
+
+            let timer = start_timer(|| format!("zero_check_next_round-{round}"));
+            let sum = (l_0 + (l_1 - l_0) * challenge) * msg.evaluate(&aux, &challenge);
+            state.next_round::<R>(sum, &challenge);
+            end_timer(timer);
+        }
+
+        Ok((state.sum, challenges, state.into_evals()))
+    }
+
+    /// Verifies proof generated by [`Self::prove_zero_check`], where `degree`
+    /// is of `g`.
+    pub fn verify_zero_check(
+        num_vars: usize,
+        degree: usize,
+        y: &[F],
+        transcript: &mut impl FieldTranscriptRead<F>,
+    ) -> Result<(F, Vec<F>), Error> {
+        assert_eq!(y.len(), num_vars);
+
+        let (msgs, challenges) = {
+            let mut msgs = Vec::with_capacity(num_vars);
+            let mut challenges = Vec::with_capacity(num_vars);
+            for _ in 0..num_vars {
+                msgs.push(Evaluations::read(degree, transcript)?);
+                challenges.push(transcript.squeeze_challenge());
+            }
+            (msgs, challenges)
+        };
+
+        let aux = Evaluations::<F>::auxiliary(degree);
+        let mut sum = F::ZERO;
+        let mut eq_split = EqSplit::new(y.to_vec());
+        for (round, (msg, challenge)) in izip!(&msgs, &challenges).enumerate() {
+            let [l_0, l_1] = eq_split.evals(round);
+            if sum != l_0 * msg[0] + l_1 * msg[1] {
+                let msg = if round == 0 {
+                    format!(
+                        "Expect sum {sum:?} but get {:?}",
+                        l_0 * msg[0] + l_1 * msg[1]
+                    )
+                } else {
+                    format!("Consistency failure at round {round}")
+                };
+                return Err(Error::InvalidSumcheck(msg));
+            }
+            eq_split.next_round(round, challenge);
+            sum = eq_split.prefix * msg.evaluate(&aux, challenge);
+        }
+
+        Ok((sum, challenges))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::hyperplonk::util::{
+            rand_vanilla_plonk_assignment, rand_vanilla_plonk_w_lookup_assignment,
+            vanilla_plonk_expression, vanilla_plonk_w_lookup_expression,
+        },
+        piop::sum_check::{
+            classic::{ClassicSumCheck, EvaluationsProver},
+            evaluate, VirtualPolynomial,
+        },
+        poly::multilinear::MultilinearPolynomial,
+        util::{
+            expression::{rotate::BinaryField, Expression},
+            test::{rand_vec, seeded_std_rng},
+            transcript::{InMemoryTranscript, Keccak256Transcript},
+        },
+    };
+    use halo2_curves::bn256::Fr;
+
+    type ZeroCheck = ClassicSumCheck<EvaluationsProver<Fr>, BinaryField>;
+
+    fn run_zero_check(
+        expression_fn: impl Fn(usize) -> Expression<Fr>,
+        assignment_fn: impl Fn(usize) -> (Vec<MultilinearPolynomial<Fr>>, Vec<Fr>),
+    ) {
+        for num_vars in 2..16 {
+            let expression = expression_fn(num_vars);
+            let (polys, challenges) = assignment_fn(num_vars);
+            let ys = [rand_vec(num_vars, seeded_std_rng())];
+            let (evals, proof) = {
+                let virtual_poly = VirtualPolynomial::new(&expression, &polys, &challenges, &ys);
+                let mut transcript = Keccak256Transcript::default();
+                let (_, _, evals) =
+                    ZeroCheck::prove_zero_check(num_vars, virtual_poly, &mut transcript).unwrap();
+                (evals, transcript.into_proof())
+            };
+            let accept = {
+                let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+                let (x_eval, x) = ZeroCheck::verify_zero_check(
+                    num_vars,
+                    expression.degree() - 1,
+                    &ys[0],
+                    &mut transcript,
+                )
+                .unwrap();
+                x_eval
+                    == evaluate::<_, BinaryField>(
+                        &expression,
+                        num_vars,
+                        &evals,
+                        &challenges,
+                        &[ys[0].as_slice()],
+                        &x,
+                    )
+            };
+            assert!(accept);
+        }
+    }
+
+    #[test]
+    fn vanilla_plonk() {
+        run_zero_check(vanilla_plonk_expression, |num_vars| {
+            rand_vanilla_plonk_assignment::<_, BinaryField>(
+                num_vars,
+                seeded_std_rng(),
+                seeded_std_rng(),
+            )
+        });
+    }
+
+    #[test]
+    fn vanilla_plonk_w_lookup() {
+        run_zero_check(vanilla_plonk_w_lookup_expression, |num_vars| {
+            rand_vanilla_plonk_w_lookup_assignment::<_, BinaryField>(
+                num_vars,
+                seeded_std_rng(),
+                seeded_std_rng(),
+            )
+        });
+    }
+}
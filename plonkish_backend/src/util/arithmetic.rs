@@ -193,6 +193,19 @@ pub fn fe_to_fe<F1: PrimeField, F2: PrimeField>(fe: F1) -> F2 {
     F2::from_repr(repr).unwrap()
 }
 
+/// Decomposes `fe` into little-endian limbs of `num_limb_bits` bits, which is
+/// used to represent non-native field element.
+pub fn fe_to_limbs<F1: PrimeField, F2: PrimeField>(fe: F1, num_limb_bits: usize) -> Vec<F2> {
+    assert!(num_limb_bits < F2::NUM_BITS as usize);
+    let big = BigUint::from_bytes_le(fe.to_repr().as_ref());
+    let mask = &((BigUint::from(1u64) << num_limb_bits) - 1u64);
+    (0usize..)
+        .step_by(num_limb_bits)
+        .take(div_ceil(F1::NUM_BITS as usize, num_limb_bits))
+        .map(|shift| fe_from_le_bytes(((&big >> shift) & mask).to_bytes_le()))
+        .collect_vec()
+}
+
 pub fn fe_truncated<F: PrimeField>(fe: F, num_bits: usize) -> F {
     let (num_bytes, num_bits_last_byte) = div_rem(num_bits, 8);
     let mut repr = fe.to_repr();
@@ -229,11 +242,22 @@ pub fn batch_projective_to_affine<C: CurveAffine>(projectives: &[C::Curve]) -> V
 
 #[cfg(test)]
 mod test {
-    use crate::util::arithmetic;
+    use crate::util::arithmetic::{self, Field, PrimeField};
     use halo2_curves::bn256;
 
     #[test]
     fn field_size() {
         assert_eq!(arithmetic::field_size::<bn256::Fr>(), 254);
     }
+
+    #[test]
+    fn fe_to_limbs() {
+        let fe = -bn256::Fq::ONE;
+        let limbs = arithmetic::fe_to_limbs::<_, bn256::Fr>(fe, 68);
+        assert_eq!(limbs.len(), 4);
+        let recomposed = limbs.iter().rev().fold(bn256::Fq::ZERO, |acc, limb| {
+            acc * bn256::Fq::from_u128(1u128 << 68) + arithmetic::fe_to_fe::<_, bn256::Fq>(*limb)
+        });
+        assert_eq!(recomposed, fe);
+    }
 }
@@ -1,9 +1,11 @@
 mod brakedown;
+mod reed_solomon;
 
 pub use brakedown::{
     Brakedown, BrakedownSpec, BrakedownSpec1, BrakedownSpec2, BrakedownSpec3, BrakedownSpec4,
     BrakedownSpec5, BrakedownSpec6,
 };
+pub use reed_solomon::ReedSolomon;
 
 pub trait LinearCodes<F>: Sync + Send {
     fn row_len(&self) -> usize;
@@ -0,0 +1,133 @@
+//! Implementation of Reed–Solomon codes with evaluation domain being the
+//! multiplicative subgroup of order `row_len * 2^log_rate`, which is used in
+//! [AHIV17] as the linear codes.
+//!
+//! [AHIV17]: https://eprint.iacr.org/2022/1608.pdf
+
+use crate::util::{
+    arithmetic::{radix2_fft, root_of_unity, PrimeField},
+    code::LinearCodes,
+    Deserialize, Serialize,
+};
+
+const LAMBDA: f64 = 128.0;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReedSolomon<F> {
+    row_len: usize,
+    codeword_len: usize,
+    num_column_opening: usize,
+    num_proximity_testing: usize,
+    omega: F,
+}
+
+impl<F: PrimeField> ReedSolomon<F> {
+    pub fn new(row_len: usize, log_rate: usize) -> Self {
+        assert!(row_len.is_power_of_two());
+        assert!(log_rate > 0);
+
+        let log2_q = F::NUM_BITS as usize;
+        let log2_n = row_len.ilog2() as usize + log_rate;
+        assert!(log2_n <= F::S as usize);
+
+        Self {
+            row_len,
+            codeword_len: 1 << log2_n,
+            num_column_opening: Self::num_column_opening_by_rate(log_rate),
+            num_proximity_testing: ceil(LAMBDA / (log2_q - log2_n) as f64),
+            omega: root_of_unity(log2_n),
+        }
+    }
+
+    pub fn proof_size(row_len: usize, num_rows: usize, log_rate: usize) -> usize {
+        let code = Self::new(row_len, log_rate);
+        (1 + code.num_proximity_testing) * row_len + code.num_column_opening * num_rows
+    }
+
+    /// Returns codes with `row_len` that minimizes proof size for multilinear
+    /// polynomial of `num_vars` variables.
+    pub fn new_multilinear(num_vars: usize, log_rate: usize) -> Self {
+        let log2_row_len = (0..=num_vars)
+            .min_by_key(|log2_row_len| {
+                let num_rows = 1 << (num_vars - log2_row_len);
+                Self::proof_size(1 << log2_row_len, num_rows, log_rate)
+            })
+            .unwrap();
+        Self::new(1 << log2_row_len, log_rate)
+    }
+
+    /// Returns number of column openings for `λ` bits of security with
+    /// relative distance `δ = 1 - 2^-log_rate`, which is the same as
+    /// [`BrakedownSpec::num_column_opening`].
+    ///
+    /// [`BrakedownSpec::num_column_opening`]: crate::util::code::BrakedownSpec::num_column_opening
+    fn num_column_opening_by_rate(log_rate: usize) -> usize {
+        let delta = 1.0 - (-(log_rate as f64)).exp2();
+        ceil(-LAMBDA / (1.0 - delta / 3.0).log2())
+    }
+}
+
+impl<F: PrimeField> LinearCodes<F> for ReedSolomon<F> {
+    fn row_len(&self) -> usize {
+        self.row_len
+    }
+
+    fn codeword_len(&self) -> usize {
+        self.codeword_len
+    }
+
+    fn num_column_opening(&self) -> usize {
+        self.num_column_opening
+    }
+
+    fn num_proximity_testing(&self) -> usize {
+        self.num_proximity_testing
+    }
+
+    /// Encodes the first `row_len` elements as coefficients of polynomial into
+    /// its evaluations on the subgroup, so the codes are not systematic.
+    fn encode(&self, mut target: impl AsMut<[F]>) {
+        let target = target.as_mut();
+        assert_eq!(target.len(), self.codeword_len);
+
+        target[self.row_len..].fill(F::ZERO);
+        let log2_n = self.codeword_len.ilog2() as usize;
+        radix2_fft(target, self.omega, log2_n);
+    }
+}
+
+fn ceil(v: f64) -> usize {
+    v.ceil() as usize
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::{
+        arithmetic::{horner, powers, Field},
+        code::{LinearCodes, ReedSolomon},
+        test::{rand_vec, seeded_std_rng},
+        Itertools,
+    };
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn num_column_opening() {
+        for (log_rate, num_column_opening) in [(1, 487), (2, 309), (3, 258), (4, 237)] {
+            let code = ReedSolomon::<Fr>::new(1 << 10, log_rate);
+            assert_eq!(code.num_column_opening(), num_column_opening);
+        }
+    }
+
+    #[test]
+    fn encode() {
+        let code = ReedSolomon::<Fr>::new(1 << 10, 2);
+        let message = rand_vec(code.row_len(), seeded_std_rng());
+        let mut codeword = vec![Fr::ZERO; code.codeword_len()];
+        codeword[..message.len()].copy_from_slice(&message);
+        code.encode(&mut codeword);
+        let domain = powers(code.omega).take(code.codeword_len()).collect_vec();
+        for (x, value) in domain.iter().zip(codeword) {
+            assert_eq!(horner(&message, x), value);
+        }
+    }
+}
@@ -1,4 +1,12 @@
-use crate::util::arithmetic::PrimeField;
+use crate::{
+    util::{
+        arithmetic::{div_ceil, FromUniformBytes, PrimeField},
+        parallel::{num_threads, parallelize_iter},
+        transcript::{TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
 use sha3::digest::{Digest, HashMarker};
 use std::fmt::Debug;
 
@@ -31,3 +39,191 @@ impl<T: 'static + Sized + Clone + Debug + FixedOutputReset + Default + Update +
     for T
 {
 }
+
+/// Hash used by merkle tree over columns of field elements, which hashes a
+/// column into a leaf and a pair of nodes into their parent.
+pub trait MerkleHash<F>: 'static + Clone + Debug + Default + Send + Sync {
+    type Output: Clone + Debug + Default + PartialEq + Send + Sync + Serialize + DeserializeOwned;
+
+    fn hash_column<'a>(&self, column: impl IntoIterator<Item = &'a F>) -> Self::Output
+    where
+        F: 'a;
+
+    fn hash_pair(&self, lhs: &Self::Output, rhs: &Self::Output) -> Self::Output;
+}
+
+impl<F: PrimeField, H: Hash + Send + Sync> MerkleHash<F> for H {
+    type Output = Output<H>;
+
+    fn hash_column<'a>(&self, column: impl IntoIterator<Item = &'a F>) -> Self::Output
+    where
+        F: 'a,
+    {
+        let mut hasher = H::new();
+        column
+            .into_iter()
+            .for_each(|item| hasher.update_field_element(item));
+        hasher.finalize()
+    }
+
+    fn hash_pair(&self, lhs: &Self::Output, rhs: &Self::Output) -> Self::Output {
+        let mut hasher = H::new();
+        Update::update(&mut hasher, lhs);
+        Update::update(&mut hasher, rhs);
+        hasher.finalize()
+    }
+}
+
+pub(crate) const POSEIDON_T: usize = 5;
+pub(crate) const POSEIDON_RATE: usize = 4;
+pub(crate) const POSEIDON_R_F: usize = 8;
+pub(crate) const POSEIDON_R_P: usize = 60;
+
+/// [`MerkleHash`] with [`Poseidon`] sponge, which hashes field elements
+/// natively so the merkle tree is cheap to be verified in circuit.
+#[derive(Clone, Debug)]
+pub struct PoseidonHash<F: PrimeField>(Poseidon<F, POSEIDON_T, POSEIDON_RATE>);
+
+impl<F: FromUniformBytes<64>> Default for PoseidonHash<F> {
+    fn default() -> Self {
+        Self(Poseidon::new(POSEIDON_R_F, POSEIDON_R_P))
+    }
+}
+
+impl<F> MerkleHash<F> for PoseidonHash<F>
+where
+    F: FromUniformBytes<64> + Serialize + DeserializeOwned,
+{
+    type Output = F;
+
+    fn hash_column<'a>(&self, column: impl IntoIterator<Item = &'a F>) -> Self::Output
+    where
+        F: 'a,
+    {
+        let mut sponge = self.0.clone();
+        sponge.update(&column.into_iter().copied().collect_vec());
+        sponge.squeeze()
+    }
+
+    fn hash_pair(&self, lhs: &Self::Output, rhs: &Self::Output) -> Self::Output {
+        let mut sponge = self.0.clone();
+        sponge.update(&[*lhs, *rhs]);
+        sponge.squeeze()
+    }
+}
+
+/// Binary merkle tree over `2^depth` leaves, where intermediate hashes are
+/// stored layer by layer starting from leaves, and root is stored separately.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MerkleTree<H: Hash> {
+    depth: usize,
+    intermediate_hashes: Vec<Output<H>>,
+    root: Output<H>,
+}
+
+impl<H: Hash> MerkleTree<H> {
+    pub fn new(leaves: Vec<Output<H>>) -> Self {
+        assert!(leaves.len().is_power_of_two());
+
+        let depth = leaves.len().ilog2() as usize;
+        let mut hashes = leaves;
+        hashes.resize((2 << depth) - 1, Output::<H>::default());
+
+        let mut offset = 0;
+        for width in (1..=depth).rev().map(|depth| 1 << depth) {
+            let (input, output) = hashes[offset..].split_at_mut(width);
+            let chunk_size = div_ceil(output.len(), num_threads());
+            parallelize_iter(
+                input
+                    .chunks(2 * chunk_size)
+                    .zip(output.chunks_mut(chunk_size)),
+                |(input, output)| {
+                    let mut hasher = H::new();
+                    for (input, output) in input.chunks_exact(2).zip(output.iter_mut()) {
+                        Update::update(&mut hasher, &input[0]);
+                        Update::update(&mut hasher, &input[1]);
+                        FixedOutputReset::finalize_into_reset(&mut hasher, output);
+                    }
+                },
+            );
+            offset += width;
+        }
+
+        let (intermediate_hashes, root) = {
+            let mut intermediate_hashes = hashes;
+            let root = intermediate_hashes.pop().unwrap();
+            (intermediate_hashes, root)
+        };
+
+        Self {
+            depth,
+            intermediate_hashes,
+            root,
+        }
+    }
+
+    pub fn from_root(root: Output<H>) -> Self {
+        Self {
+            root,
+            ..Default::default()
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn intermediate_hashes(&self) -> &[Output<H>] {
+        &self.intermediate_hashes
+    }
+
+    pub fn root(&self) -> &Output<H> {
+        &self.root
+    }
+
+    pub fn write_path<F>(
+        &self,
+        leaf: usize,
+        transcript: &mut impl TranscriptWrite<Output<H>, F>,
+    ) -> Result<(), Error> {
+        let mut offset = 0;
+        for (idx, width) in (1..=self.depth).rev().map(|depth| 1 << depth).enumerate() {
+            let neighbor_idx = (leaf >> idx) ^ 1;
+            transcript.write_commitment(&self.intermediate_hashes[offset + neighbor_idx])?;
+            offset += width;
+        }
+        Ok(())
+    }
+
+    /// Reads path of `leaf` in tree of `depth` and verifies it against `root`.
+    pub fn read_and_verify_path<F>(
+        root: &Output<H>,
+        depth: usize,
+        leaf: usize,
+        leaf_hash: Output<H>,
+        transcript: &mut impl TranscriptRead<Output<H>, F>,
+    ) -> Result<(), Error> {
+        let path = transcript.read_commitments(depth)?;
+
+        let mut hasher = H::new();
+        let mut output = leaf_hash;
+        for (idx, neighbor) in path.iter().enumerate() {
+            if (leaf >> idx) & 1 == 0 {
+                Update::update(&mut hasher, &output);
+                Update::update(&mut hasher, neighbor);
+            } else {
+                Update::update(&mut hasher, neighbor);
+                Update::update(&mut hasher, &output);
+            }
+            output = FixedOutputReset::finalize_fixed_reset(&mut hasher);
+        }
+        if &output != root {
+            return Err(Error::InvalidPcsOpen(
+                "Invalid merkle tree opening".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
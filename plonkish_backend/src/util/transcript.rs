@@ -1,7 +1,13 @@
 use crate::{
     util::{
-        arithmetic::{fe_mod_from_le_bytes, Coordinates, CurveAffine, PrimeField},
-        hash::{Hash, Keccak256, Output, Update},
+        arithmetic::{
+            fe_from_le_bytes, fe_mod_from_le_bytes, fe_to_limbs, Coordinates, CurveAffine,
+            FromUniformBytes, PrimeField,
+        },
+        hash::{
+            Hash, Keccak256, Output, Poseidon, Update, POSEIDON_RATE, POSEIDON_R_F, POSEIDON_R_P,
+            POSEIDON_T,
+        },
         Itertools,
     },
     Error,
@@ -138,17 +144,7 @@ impl<H: Hash, F: PrimeField, S> FieldTranscript<F> for FiatShamirTranscript<H, S
 
 impl<H: Hash, F: PrimeField, R: io::Read> FieldTranscriptRead<F> for FiatShamirTranscript<H, R> {
     fn read_field_element(&mut self) -> Result<F, Error> {
-        let mut repr = <F as PrimeField>::Repr::default();
-        self.stream
-            .read_exact(repr.as_mut())
-            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
-        repr.as_mut().reverse();
-        let fe = F::from_repr_vartime(repr).ok_or_else(|| {
-            Error::Transcript(
-                io::ErrorKind::Other,
-                "Invalid field element encoding in proof".to_string(),
-            )
-        })?;
+        let fe = read_field_element(&mut self.stream)?;
         self.common_field_element(&fe)?;
         Ok(fe)
     }
@@ -157,11 +153,7 @@ impl<H: Hash, F: PrimeField, R: io::Read> FieldTranscriptRead<F> for FiatShamirT
 impl<H: Hash, F: PrimeField, W: io::Write> FieldTranscriptWrite<F> for FiatShamirTranscript<H, W> {
     fn write_field_element(&mut self, fe: &F) -> Result<(), Error> {
         self.common_field_element(fe)?;
-        let mut repr = fe.to_repr();
-        repr.as_mut().reverse();
-        self.stream
-            .write_all(repr.as_ref())
-            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))
+        write_field_element(&mut self.stream, fe)
     }
 }
 
@@ -170,13 +162,7 @@ macro_rules! impl_fs_transcript_curve_commitment {
         $(
             impl<H: Hash, S> Transcript<$curve, <$curve as CurveAffine>::ScalarExt> for FiatShamirTranscript<H, S> {
                 fn common_commitment(&mut self, comm: &$curve) -> Result<(), Error> {
-                    let coordinates =
-                        Option::<Coordinates<_>>::from(comm.coordinates()).ok_or_else(|| {
-                            Error::Transcript(
-                                io::ErrorKind::Other,
-                                "Invalid elliptic curve point encoding".to_string(),
-                            )
-                        })?;
+                    let coordinates = coordinates(comm)?;
                     self.state.update_field_element(coordinates.x());
                     self.state.update_field_element(coordinates.y());
                     Ok(())
@@ -187,24 +173,7 @@ macro_rules! impl_fs_transcript_curve_commitment {
                 for FiatShamirTranscript<H, R>
             {
                 fn read_commitment(&mut self) -> Result<$curve, Error> {
-                    let mut reprs = [<<$curve as CurveAffine>::Base as PrimeField>::Repr::default(); 2];
-                    for repr in &mut reprs {
-                        self.stream
-                            .read_exact(repr.as_mut())
-                            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
-                        repr.as_mut().reverse();
-                    }
-                    let [x, y] =
-                        reprs.map(<<$curve as CurveAffine>::Base as PrimeField>::from_repr_vartime);
-                    let ec_point = x
-                        .zip(y)
-                        .and_then(|(x, y)| CurveAffine::from_xy(x, y).into())
-                        .ok_or_else(|| {
-                            Error::Transcript(
-                                io::ErrorKind::Other,
-                                "Invalid elliptic curve point encoding in proof".to_string(),
-                            )
-                        })?;
+                    let ec_point = read_ec_point(&mut self.stream)?;
                     self.common_commitment(&ec_point)?;
                     Ok(ec_point)
                 }
@@ -215,15 +184,7 @@ macro_rules! impl_fs_transcript_curve_commitment {
             {
                 fn write_commitment(&mut self, ec_point: &$curve) -> Result<(), Error> {
                     self.common_commitment(ec_point)?;
-                    let coordinates = ec_point.coordinates().unwrap();
-                    for coordinate in [coordinates.x(), coordinates.y()] {
-                        let mut repr = coordinate.to_repr();
-                        repr.as_mut().reverse();
-                        self.stream
-                            .write_all(repr.as_ref())
-                            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
-                    }
-                    Ok(())
+                    write_ec_point(&mut self.stream, ec_point)
                 }
             }
         )*
@@ -237,6 +198,32 @@ impl_fs_transcript_curve_commitment!(
     pasta::EqAffine,
 );
 
+macro_rules! impl_fs_transcript_field_commitment {
+    ($($field:ty),*$(,)?) => {
+        $(
+            impl<H: Hash, S> Transcript<$field, $field> for FiatShamirTranscript<H, S> {
+                fn common_commitment(&mut self, comm: &$field) -> Result<(), Error> {
+                    self.common_field_element(comm)
+                }
+            }
+
+            impl<H: Hash, R: io::Read> TranscriptRead<$field, $field> for FiatShamirTranscript<H, R> {
+                fn read_commitment(&mut self) -> Result<$field, Error> {
+                    self.read_field_element()
+                }
+            }
+
+            impl<H: Hash, W: io::Write> TranscriptWrite<$field, $field> for FiatShamirTranscript<H, W> {
+                fn write_commitment(&mut self, comm: &$field) -> Result<(), Error> {
+                    self.write_field_element(comm)
+                }
+            }
+        )*
+    };
+}
+
+impl_fs_transcript_field_commitment!(bn256::Fr, bn256::Fq, pasta::Fp, pasta::Fq);
+
 impl<F: PrimeField, S> Transcript<Output<Keccak256>, F> for Keccak256Transcript<S> {
     fn common_commitment(&mut self, comm: &Output<Keccak256>) -> Result<(), Error> {
         self.state.update(comm);
@@ -246,19 +233,248 @@ impl<F: PrimeField, S> Transcript<Output<Keccak256>, F> for Keccak256Transcript<
 
 impl<F: PrimeField, R: io::Read> TranscriptRead<Output<Keccak256>, F> for Keccak256Transcript<R> {
     fn read_commitment(&mut self) -> Result<Output<Keccak256>, Error> {
-        let mut hash = Output::<Keccak256>::default();
-        self.stream
-            .read_exact(hash.as_mut())
-            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
-        Ok(hash)
+        read_hash::<Keccak256>(&mut self.stream)
     }
 }
 
 impl<F: PrimeField, W: io::Write> TranscriptWrite<Output<Keccak256>, F> for Keccak256Transcript<W> {
     fn write_commitment(&mut self, hash: &Output<Keccak256>) -> Result<(), Error> {
-        self.stream
-            .write_all(hash)
-            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
+        write_hash::<Keccak256>(&mut self.stream, hash)
+    }
+}
+
+const NUM_LIMB_BITS: usize = 68;
+
+/// Fiat-Shamir transcript with [`Poseidon`] sponge over native field `F`,
+/// which absorbs field elements directly, coordinates of elliptic curve points
+/// in non-native field as limbs of 68 bits, and hashes as limbs of 128 bits,
+/// so it is cheap to be verified in circuit.
+#[derive(Debug)]
+pub struct PoseidonTranscript<F: PrimeField, S> {
+    state: Poseidon<F, POSEIDON_T, POSEIDON_RATE>,
+    stream: S,
+}
+
+impl<F: FromUniformBytes<64>, S: Default> Default for PoseidonTranscript<F, S> {
+    fn default() -> Self {
+        Self {
+            state: Poseidon::new(POSEIDON_R_F, POSEIDON_R_P),
+            stream: S::default(),
+        }
+    }
+}
+
+impl<F: FromUniformBytes<64>> InMemoryTranscript for PoseidonTranscript<F, Cursor<Vec<u8>>> {
+    type Param = ();
+
+    fn new(_: Self::Param) -> Self {
+        Self::default()
+    }
+
+    fn into_proof(self) -> Vec<u8> {
+        self.stream.into_inner()
+    }
+
+    fn from_proof(_: Self::Param, proof: &[u8]) -> Self {
+        Self {
+            state: Poseidon::new(POSEIDON_R_F, POSEIDON_R_P),
+            stream: Cursor::new(proof.to_vec()),
+        }
+    }
+}
+
+impl<F: FromUniformBytes<64>, S> FieldTranscript<F> for PoseidonTranscript<F, S> {
+    fn squeeze_challenge(&mut self) -> F {
+        self.state.squeeze()
+    }
+
+    fn common_field_element(&mut self, fe: &F) -> Result<(), Error> {
+        self.state.update(&[*fe]);
+        Ok(())
+    }
+}
+
+impl<F: FromUniformBytes<64>, R: io::Read> FieldTranscriptRead<F> for PoseidonTranscript<F, R> {
+    fn read_field_element(&mut self) -> Result<F, Error> {
+        let fe = read_field_element(&mut self.stream)?;
+        self.common_field_element(&fe)?;
+        Ok(fe)
+    }
+}
+
+impl<F: FromUniformBytes<64>, W: io::Write> FieldTranscriptWrite<F> for PoseidonTranscript<F, W> {
+    fn write_field_element(&mut self, fe: &F) -> Result<(), Error> {
+        self.common_field_element(fe)?;
+        write_field_element(&mut self.stream, fe)
+    }
+}
+
+macro_rules! impl_poseidon_transcript_curve_commitment {
+    ($($curve:ty),*$(,)?) => {
+        $(
+            impl<S> Transcript<$curve, <$curve as CurveAffine>::ScalarExt>
+                for PoseidonTranscript<<$curve as CurveAffine>::ScalarExt, S>
+            {
+                fn common_commitment(&mut self, comm: &$curve) -> Result<(), Error> {
+                    let coordinates = coordinates(comm)?;
+                    for coordinate in [coordinates.x(), coordinates.y()] {
+                        let limbs = fe_to_limbs::<_, <$curve as CurveAffine>::ScalarExt>(*coordinate, NUM_LIMB_BITS);
+                        self.state.update(&limbs);
+                    }
+                    Ok(())
+                }
+            }
+
+            impl<R: io::Read> TranscriptRead<$curve, <$curve as CurveAffine>::ScalarExt>
+                for PoseidonTranscript<<$curve as CurveAffine>::ScalarExt, R>
+            {
+                fn read_commitment(&mut self) -> Result<$curve, Error> {
+                    let ec_point = read_ec_point(&mut self.stream)?;
+                    self.common_commitment(&ec_point)?;
+                    Ok(ec_point)
+                }
+            }
+
+            impl<W: io::Write> TranscriptWrite<$curve, <$curve as CurveAffine>::ScalarExt>
+                for PoseidonTranscript<<$curve as CurveAffine>::ScalarExt, W>
+            {
+                fn write_commitment(&mut self, ec_point: &$curve) -> Result<(), Error> {
+                    self.common_commitment(ec_point)?;
+                    write_ec_point(&mut self.stream, ec_point)
+                }
+            }
+        )*
+    };
+}
+
+impl_poseidon_transcript_curve_commitment!(
+    bn256::G1Affine,
+    grumpkin::G1Affine,
+    pasta::EpAffine,
+    pasta::EqAffine,
+);
+
+macro_rules! impl_poseidon_transcript_field_commitment {
+    ($($field:ty),*$(,)?) => {
+        $(
+            impl<S> Transcript<$field, $field> for PoseidonTranscript<$field, S> {
+                fn common_commitment(&mut self, comm: &$field) -> Result<(), Error> {
+                    self.common_field_element(comm)
+                }
+            }
+
+            impl<R: io::Read> TranscriptRead<$field, $field> for PoseidonTranscript<$field, R> {
+                fn read_commitment(&mut self) -> Result<$field, Error> {
+                    self.read_field_element()
+                }
+            }
+
+            impl<W: io::Write> TranscriptWrite<$field, $field> for PoseidonTranscript<$field, W> {
+                fn write_commitment(&mut self, comm: &$field) -> Result<(), Error> {
+                    self.write_field_element(comm)
+                }
+            }
+        )*
+    };
+}
+
+impl_poseidon_transcript_field_commitment!(bn256::Fr, bn256::Fq, pasta::Fp, pasta::Fq);
+
+impl<F: FromUniformBytes<64>, S> Transcript<Output<Keccak256>, F> for PoseidonTranscript<F, S> {
+    fn common_commitment(&mut self, comm: &Output<Keccak256>) -> Result<(), Error> {
+        let limbs: Vec<F> = comm.chunks(16).map(fe_from_le_bytes).collect();
+        self.state.update(&limbs);
         Ok(())
     }
 }
+
+impl<F: FromUniformBytes<64>, R: io::Read> TranscriptRead<Output<Keccak256>, F>
+    for PoseidonTranscript<F, R>
+{
+    fn read_commitment(&mut self) -> Result<Output<Keccak256>, Error> {
+        let hash = read_hash::<Keccak256>(&mut self.stream)?;
+        self.common_commitment(&hash)?;
+        Ok(hash)
+    }
+}
+
+impl<F: FromUniformBytes<64>, W: io::Write> TranscriptWrite<Output<Keccak256>, F>
+    for PoseidonTranscript<F, W>
+{
+    fn write_commitment(&mut self, hash: &Output<Keccak256>) -> Result<(), Error> {
+        self.common_commitment(hash)?;
+        write_hash::<Keccak256>(&mut self.stream, hash)
+    }
+}
+
+fn coordinates<C: CurveAffine>(ec_point: &C) -> Result<Coordinates<C>, Error> {
+    Option::<Coordinates<_>>::from(ec_point.coordinates()).ok_or_else(|| {
+        Error::Transcript(
+            io::ErrorKind::Other,
+            "Invalid elliptic curve point encoding".to_string(),
+        )
+    })
+}
+
+fn read_field_element<F: PrimeField>(reader: &mut impl io::Read) -> Result<F, Error> {
+    let mut repr = F::Repr::default();
+    reader
+        .read_exact(repr.as_mut())
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
+    repr.as_mut().reverse();
+    F::from_repr_vartime(repr).ok_or_else(|| {
+        Error::Transcript(
+            io::ErrorKind::Other,
+            "Invalid field element encoding in proof".to_string(),
+        )
+    })
+}
+
+fn write_field_element<F: PrimeField>(writer: &mut impl io::Write, fe: &F) -> Result<(), Error> {
+    let mut repr = fe.to_repr();
+    repr.as_mut().reverse();
+    writer
+        .write_all(repr.as_ref())
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))
+}
+
+fn read_ec_point<C: CurveAffine>(reader: &mut impl io::Read) -> Result<C, Error> {
+    let mut reprs = [<C::Base as PrimeField>::Repr::default(); 2];
+    for repr in &mut reprs {
+        reader
+            .read_exact(repr.as_mut())
+            .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
+        repr.as_mut().reverse();
+    }
+    let [x, y] = reprs.map(<C::Base as PrimeField>::from_repr_vartime);
+    x.zip(y)
+        .and_then(|(x, y)| C::from_xy(x, y).into())
+        .ok_or_else(|| {
+            Error::Transcript(
+                io::ErrorKind::Other,
+                "Invalid elliptic curve point encoding in proof".to_string(),
+            )
+        })
+}
+
+fn write_ec_point<C: CurveAffine>(writer: &mut impl io::Write, ec_point: &C) -> Result<(), Error> {
+    let coordinates = ec_point.coordinates().unwrap();
+    for coordinate in [coordinates.x(), coordinates.y()] {
+        write_field_element(writer, coordinate)?;
+    }
+    Ok(())
+}
+
+fn read_hash<H: Hash>(reader: &mut impl io::Read) -> Result<Output<H>, Error> {
+    let mut hash = Output::<H>::default();
+    reader
+        .read_exact(hash.as_mut())
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
+    Ok(hash)
+}
+
+fn write_hash<H: Hash>(writer: &mut impl io::Write, hash: &Output<H>) -> Result<(), Error> {
+    writer
+        .write_all(hash)
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))
+}